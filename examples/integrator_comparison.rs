@@ -0,0 +1,92 @@
+//! Compares the energy drift of the integration schemes
+//!
+//! Builds one seeded scene with a symmetric interaction table and no
+//! damping, so the total energy should stay constant, then runs it once
+//! per integrator and reports how far the energy drifts.
+//!
+//! Run with `cargo run --release --example integrator_comparison [steps] [particles] [dt]`.
+
+use bevy::math::Vec3;
+use particle_life::components::ParticleType;
+use particle_life::resources::{
+    Integrator, ParticleConfig, ParticleInteractionTable, SimRng, SpeciesRegistry,
+};
+use particle_life::simulation::Simulation;
+use rand::Rng;
+use std::time::Instant;
+
+fn build_simulation(particle_num: usize, dt: f32, integrator: Integrator) -> Simulation {
+    let mut config = ParticleConfig::default();
+    // Keep the density of the default 2000 particle scene.
+    let scale = (particle_num as f32 / 2000.0).sqrt();
+    config.map_width *= scale;
+    config.map_height *= scale;
+    config.init_particle_num = particle_num;
+    config.dt = dt;
+    config.dt_half = f32::INFINITY;
+    config.integrator = integrator;
+
+    // A symmetric table makes every pair force conservative.
+    let mut rng = SimRng::new(config.seed);
+    let mut table = ParticleInteractionTable::new();
+    let types: Vec<ParticleType> = SpeciesRegistry::default().types().collect();
+    for (i, target) in types.iter().enumerate() {
+        for source in &types[i..] {
+            let strength = rng.random_range(-1.0..1.0);
+            table.set_interaction(*target, *source, strength);
+            table.set_interaction(*source, *target, strength);
+        }
+    }
+
+    let mut simulation = Simulation::new(config.clone(), table);
+    for _ in 0..particle_num {
+        let x = rng.random_range(-config.map_width / 2.0..config.map_width / 2.0);
+        let y = rng.random_range(-config.map_height / 2.0..config.map_height / 2.0);
+        let particle_type = types[rng.random_range(0..types.len())];
+        simulation.add_particle(particle_type, Vec3::new(x, y, 0.0), Vec3::ZERO);
+    }
+    simulation
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let steps: u32 = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(500);
+    let particle_num: usize = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(1000);
+    let dt: f32 = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(0.01);
+
+    println!("{particle_num} particles, {steps} steps, dt {dt}");
+    println!(
+        "{:>8} {:>14} {:>14} {:>12} {:>12} {:>10}",
+        "scheme", "initial E", "final E", "drift", "max drift", "ms/step"
+    );
+    for integrator in Integrator::all() {
+        let mut simulation = build_simulation(particle_num, dt, integrator);
+        let initial = simulation.total_energy();
+        // Relative to the potential scale when the scene starts at rest.
+        let reference = initial
+            .abs()
+            .max(simulation.potential_energy().abs())
+            .max(1.0);
+
+        let mut max_drift = 0.0f64;
+        let mut elapsed = 0.0;
+        for _ in 0..steps {
+            let start = Instant::now();
+            simulation.step();
+            elapsed += start.elapsed().as_secs_f64();
+            let drift = (simulation.total_energy() - initial) / reference;
+            max_drift = max_drift.max(drift.abs());
+        }
+        let last = simulation.total_energy();
+
+        println!(
+            "{:>8} {:>14.4} {:>14.4} {:>12.3e} {:>12.3e} {:>10.3}",
+            integrator.as_str(),
+            initial,
+            last,
+            (last - initial) / reference,
+            max_drift,
+            elapsed * 1000.0 / f64::from(steps)
+        );
+    }
+}
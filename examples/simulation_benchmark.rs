@@ -0,0 +1,123 @@
+//! Measures the force computation of the simulation core
+//!
+//! Compares the persistent `SpatialGrid` against the previous approach
+//! (a `HashMap` of chunks rebuilt every step, with the neighboring
+//! chunks copied into a fresh `Vec` for every particle) and checks that
+//! both produce the same accelerations.
+//!
+//! Run with `cargo run --release --example simulation_benchmark [steps]`.
+
+use bevy::math::Vec3;
+use particle_life::components::ParticleType;
+use particle_life::resources::{ParticleConfig, ParticleInteractionTable, SimRng, SpeciesRegistry};
+use particle_life::simulation::Simulation;
+use rand::Rng;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+fn build_simulation(particle_num: usize) -> Simulation {
+    let mut config = ParticleConfig::default();
+    // Keep the density of the default 2000 particle scene.
+    let scale = (particle_num as f32 / 2000.0).sqrt();
+    config.map_width *= scale;
+    config.map_height *= scale;
+    config.init_particle_num = particle_num;
+
+    let mut rng = SimRng::new(config.seed);
+    let mut table = ParticleInteractionTable::new();
+    let types: Vec<ParticleType> = SpeciesRegistry::default().types().collect();
+    for &target in &types {
+        for &source in &types {
+            table.set_interaction(target, source, rng.random_range(-1.0..1.0));
+        }
+    }
+
+    let mut simulation = Simulation::new(config.clone(), table);
+    for _ in 0..particle_num {
+        let x = rng.random_range(-config.map_width / 2.0..config.map_width / 2.0);
+        let y = rng.random_range(-config.map_height / 2.0..config.map_height / 2.0);
+        let particle_type = types[rng.random_range(0..types.len())];
+        simulation.add_particle(particle_type, Vec3::new(x, y, 0.0), Vec3::ZERO);
+    }
+    simulation
+}
+
+fn hashmap_accelerations(simulation: &Simulation) -> Vec<Vec3> {
+    let config = &simulation.config;
+    let positions = simulation.positions();
+    let types = simulation.types();
+    let cell = |value: f32| (value / config.r).floor() as i32;
+
+    let mut chunk: HashMap<(i32, i32), Vec<usize>> = HashMap::with_capacity(1000);
+    for (index, position) in positions.iter().enumerate() {
+        chunk
+            .entry((cell(position.x), cell(position.y)))
+            .or_default()
+            .push(index);
+    }
+
+    positions
+        .iter()
+        .enumerate()
+        .map(|(index, position)| {
+            let (chunk_x, chunk_y) = (cell(position.x), cell(position.y));
+            let mut components: Vec<usize> = Vec::with_capacity(1000);
+            for x in chunk_x - 1..=chunk_x + 1 {
+                for y in chunk_y - 1..=chunk_y + 1 {
+                    if let Some(inner) = chunk.get(&(x, y)) {
+                        components.extend_from_slice(inner);
+                    }
+                }
+            }
+            components
+                .iter()
+                .filter(|&&other| other != index)
+                .fold(Vec3::ZERO, |acc, &other| {
+                    let offset = positions[other] - *position;
+                    acc + simulation.pair_force(types[index], types[other], offset)
+                })
+        })
+        .collect()
+}
+
+fn time_steps(steps: u32, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..steps {
+        f();
+    }
+    start.elapsed() / steps
+}
+
+fn main() {
+    let steps: u32 = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(20);
+
+    println!(
+        "{:>10} {:>14} {:>14} {:>9} {:>12}",
+        "particles", "hashmap (ms)", "grid (ms)", "speedup", "max diff"
+    );
+    for particle_num in [2_000, 5_000, 10_000, 20_000, 50_000] {
+        let mut simulation = build_simulation(particle_num);
+
+        let mut expected = Vec::new();
+        let hashmap = time_steps(steps, || expected = hashmap_accelerations(&simulation));
+        let grid = time_steps(steps, || simulation.compute_accelerations());
+
+        let max_diff = expected
+            .iter()
+            .zip(simulation.accelerations())
+            .map(|(a, b)| (*a - *b).abs().max_element())
+            .fold(0.0f32, f32::max);
+
+        println!(
+            "{:>10} {:>14.3} {:>14.3} {:>8.2}x {:>12.2e}",
+            particle_num,
+            hashmap.as_secs_f64() * 1000.0,
+            grid.as_secs_f64() * 1000.0,
+            hashmap.as_secs_f64() / grid.as_secs_f64(),
+            max_diff
+        );
+    }
+}
@@ -4,4 +4,4 @@
 
 mod particle;
 
-pub use particle::Particle;
+pub use particle::{Particle, ParticleAssets};
@@ -1,28 +1,73 @@
 //! Bundle for spawning a particle entity
 //!
-//! Contains all components needed for a particle:
+//! Contains all components the simulation needs for a particle:
 //! - Particle type marker
 //! - Particle type enum
 //! - Velocity for physics
 //! - Position for physics (separate from Transform)
-//! - Mesh for rendering
-//! - Material for rendering
-//! - Transform for rendering
+//!
+//! Spawning only needs a command queue, so particles can be created
+//! without any render assets, for example under `MinimalPlugins`. The
+//! transform, previous position, mesh and material are attached
+//! separately with [`Particle::insert_render`]; the mesh depends on the
+//! [`Dimension`]: circles in 2D, spheres in 3D, sized and colored by the
+//! particle's species.
+//!
+//! Entities of dead particles can be turned back into particles with
+//! [`Particle::revive`] instead of spawning new ones.
 
-use crate::components::{ParticleMarker, ParticleType, Position, Velocity};
+use crate::components::{
+    Dormant, ParticleMarker, ParticleType, Position, PreviousPosition, Velocity,
+};
+use crate::resources::{Dimension, ParticleMeshCache, Species};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
 
+/// Asset storages needed to spawn particle meshes and materials
+#[derive(SystemParam)]
+pub struct ParticleAssets<'w> {
+    /// Mesh assets
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    /// Materials for 2D particles
+    pub materials_2d: ResMut<'w, Assets<ColorMaterial>>,
+    /// Materials for 3D particles
+    pub materials_3d: ResMut<'w, Assets<StandardMaterial>>,
+    /// Handles shared by all particles of the same size or color
+    pub cache: ResMut<'w, ParticleMeshCache>,
+}
+
+impl ParticleAssets<'_> {
+    /// Returns the shared 2D mesh and material of a species
+    pub fn handles_2d(&mut self, species: &Species) -> (Mesh2d, MeshMaterial2d<ColorMaterial>) {
+        (
+            Mesh2d(self.cache.circle(&mut self.meshes, species.properties.size)),
+            MeshMaterial2d(
+                self.cache
+                    .material_2d(&mut self.materials_2d, species.color),
+            ),
+        )
+    }
+
+    /// Returns the shared 3D mesh and material of a species
+    pub fn handles_3d(&mut self, species: &Species) -> (Mesh3d, MeshMaterial3d<StandardMaterial>) {
+        (
+            Mesh3d(self.cache.sphere(&mut self.meshes, species.properties.size)),
+            MeshMaterial3d(
+                self.cache
+                    .material_3d(&mut self.materials_3d, species.color),
+            ),
+        )
+    }
+}
+
 /// Bundle for spawning a particle entity
 ///
-/// Contains all components needed for a particle:
+/// Contains all components the simulation needs for a particle:
 /// - Particle type marker
 /// - Particle type enum
 /// - Velocity for physics
 /// - Position for physics (separate from Transform)
-/// - Mesh for rendering
-/// - Material for rendering
-/// - Transform for rendering
 #[derive(Bundle, Debug, Clone)]
 pub struct Particle {
     /// Marker component identifying this as a particle
@@ -33,12 +78,6 @@ pub struct Particle {
     pub velocity: Velocity,
     /// Position for physics (separate from Transform)
     pub position: Position,
-    /// 2D mesh for rendering
-    pub mesh: Mesh2d,
-    /// Material for rendering
-    pub material: MeshMaterial2d<ColorMaterial>,
-    /// Transform for rendering (synced from Position)
-    pub transform: Transform,
 }
 
 impl Particle {
@@ -46,27 +85,59 @@ impl Particle {
     ///
     /// # Arguments
     /// - `commands`: Bevy command queue
-    /// - `meshes`: Mesh assets resource
-    /// - `material`: Material assets resource
-    /// - `transform`: Initial transform (position will be copied to Position component)
+    /// - `position`: Initial physics position
     /// - `particle_type`: Type of particle to spawn
-    pub fn spawn(
+    ///
+    /// Returns the spawned entity.
+    pub fn spawn(commands: &mut Commands, position: Vec3, particle_type: ParticleType) -> Entity {
+        let entity = commands.spawn_empty().id();
+        Self::revive(commands, entity, position, particle_type);
+        entity
+    }
+
+    /// Turns an existing entity into a particle with given properties
+    ///
+    /// Used to recycle [`Dormant`] entities: the marker is removed and
+    /// the particle components are replaced. Takes the same arguments as
+    /// [`spawn`](Self::spawn) plus the `entity` to reuse.
+    pub fn revive(
         commands: &mut Commands,
-        meshes: &mut ResMut<Assets<Mesh>>,
-        material: &mut ResMut<Assets<ColorMaterial>>,
-        transform: Transform,
+        entity: Entity,
+        position: Vec3,
         particle_type: ParticleType,
     ) {
-        commands.spawn(Self {
+        commands.entity(entity).remove::<Dormant>().insert(Self {
             marker: ParticleMarker,
             particle_type,
             velocity: Velocity::new(Vec3::default()),
-            position: Position::new(transform.translation),
-            mesh: Mesh2d(meshes.add(Circle::new(5.0))),
-            material: MeshMaterial2d(
-                material.add(ColorMaterial::from_color(particle_type.to_color())),
-            ),
-            transform,
+            position: Position::new(position),
         });
     }
+
+    /// Inserts the render components of a particle
+    ///
+    /// Adds the [`Transform`] and [`PreviousPosition`] that
+    /// `sync_transform` interpolates between, makes the entity visible and
+    /// gives it a mesh and material: circles in 2D, spheres in 3D, sized
+    /// and colored by the species. Particles of the same species share
+    /// the handles of the [`ParticleMeshCache`]. Called by the
+    /// `attach_particle_mesh` observer whenever an entity becomes a
+    /// particle.
+    pub fn insert_render(
+        entity: &mut EntityCommands,
+        assets: &mut ParticleAssets,
+        position: Vec3,
+        species: &Species,
+        dimension: Dimension,
+    ) {
+        entity.insert((
+            Transform::from_translation(position),
+            PreviousPosition::new(position),
+            Visibility::Inherited,
+        ));
+        match dimension {
+            Dimension::Two => entity.insert(assets.handles_2d(species)),
+            Dimension::Three => entity.insert(assets.handles_3d(species)),
+        };
+    }
 }
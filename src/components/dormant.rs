@@ -0,0 +1,12 @@
+//! Marker component for recycled particle entities
+
+use bevy::ecs::component::Component;
+
+/// Marker component for recycled particle entities
+///
+/// Particles that die in ecosystem mode lose their
+/// [`ParticleMarker`](crate::components::ParticleMarker), are hidden and
+/// get this marker instead of being despawned. Newborn particles reuse
+/// dormant entities before spawning new ones.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Dormant;
@@ -0,0 +1,25 @@
+//! Energy component for particles
+//!
+//! Stores the energy of a particle in ecosystem mode. Particles without
+//! it start with the ecosystem's initial energy.
+
+use bevy::ecs::component::Component;
+
+/// Energy component for particles
+///
+/// Drains over time and is gained by feeding. A particle dies when its
+/// energy runs out and splits in two above the split threshold (see
+/// [`Ecosystem`](crate::resources::Ecosystem)).
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Energy {
+    /// Stored energy
+    pub value: f32,
+}
+
+impl Energy {
+    /// Creates a new energy component
+    #[must_use]
+    pub const fn new(value: f32) -> Self {
+        Self { value }
+    }
+}
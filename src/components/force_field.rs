@@ -0,0 +1,99 @@
+//! External force field component
+//!
+//! A force field entity applies an acceleration to every particle within
+//! its range, independent of the particle pairs.
+
+use crate::components::{ForceFieldKind, ParticleType};
+use bevy::ecs::component::Component;
+use bevy::math::Vec3;
+
+/// External force field
+///
+/// Accelerates particles within `radius` of `center`. The acceleration
+/// is not divided by the particle mass, so all species feel the same
+/// field. It fades towards the edge of the range by
+/// `(1 - distance / radius)^falloff`; an infinite radius covers the
+/// whole map at full strength.
+///
+/// - [`ForceFieldKind::Gravity`]: `strength` along `direction`
+/// - [`ForceFieldKind::Radial`]: `strength` towards the center, negative
+///   values repel
+/// - [`ForceFieldKind::Vortex`]: `strength` tangentially around the axis
+///   `direction` through the center (counter-clockwise for positive
+///   values)
+/// - [`ForceFieldKind::Damping`]: `-strength * velocity`, so `strength`
+///   is a damping rate
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ForceField {
+    /// How the field accelerates particles
+    pub kind: ForceFieldKind,
+    /// Center of the field
+    pub center: Vec3,
+    /// Gravity direction or vortex axis
+    pub direction: Vec3,
+    /// Magnitude of the acceleration
+    pub strength: f32,
+    /// Range of the field
+    pub radius: f32,
+    /// Exponent of the fade towards the edge (0 keeps full strength)
+    pub falloff: f32,
+    /// Species the field acts on, all species if `None`
+    pub species: Option<ParticleType>,
+}
+
+impl ForceField {
+    /// Creates a field of the given kind with default parameters
+    ///
+    /// Gravity points down the y axis with infinite range; the other
+    /// kinds use the z axis, a radius of 300 and linear falloff.
+    #[must_use]
+    pub const fn new(kind: ForceFieldKind, center: Vec3) -> Self {
+        let (direction, radius, falloff) = match kind {
+            ForceFieldKind::Gravity => (Vec3::NEG_Y, f32::INFINITY, 0.0),
+            _ => (Vec3::Z, 300.0, 1.0),
+        };
+        Self {
+            kind,
+            center,
+            direction,
+            strength: 1.0,
+            radius,
+            falloff,
+            species: None,
+        }
+    }
+
+    /// Returns true if the field acts on particles of this type
+    #[must_use]
+    pub fn applies_to(&self, particle_type: ParticleType) -> bool {
+        self.species.is_none_or(|species| species == particle_type)
+    }
+
+    /// Returns the acceleration of a particle
+    ///
+    /// `offset` points from the particle to the field center (honoring
+    /// the boundary modes) and `velocity` is the particle velocity.
+    #[must_use]
+    pub fn acceleration(&self, offset: Vec3, velocity: Vec3) -> Vec3 {
+        let distance = offset.length();
+        if distance >= self.radius {
+            return Vec3::ZERO;
+        }
+        let magnitude = self.strength * self.weight(distance);
+        match self.kind {
+            ForceFieldKind::Gravity => self.direction.normalize_or_zero() * magnitude,
+            ForceFieldKind::Radial => offset.normalize_or_zero() * magnitude,
+            ForceFieldKind::Vortex => offset.cross(self.direction).normalize_or_zero() * magnitude,
+            ForceFieldKind::Damping => -velocity * magnitude,
+        }
+    }
+
+    /// Returns the fade factor at `distance` from the center
+    fn weight(&self, distance: f32) -> f32 {
+        if self.radius.is_finite() && self.falloff > 0.0 {
+            (1.0 - distance / self.radius).max(0.0).powf(self.falloff)
+        } else {
+            1.0
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Kinds of external force fields
+//!
+//! Names how a [`ForceField`](crate::components::ForceField) accelerates
+//! the particles in its range.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Kind of external force field
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ForceFieldKind {
+    /// Uniform acceleration along the field direction
+    #[default]
+    Gravity,
+    /// Pull towards the center (positive strength) or push away (negative)
+    Radial,
+    /// Tangential acceleration around the field axis through the center
+    Vortex,
+    /// Extra velocity damping
+    Damping,
+}
+
+impl ForceFieldKind {
+    /// Returns string representation of this field kind
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gravity => "gravity",
+            Self::Radial => "radial",
+            Self::Vortex => "vortex",
+            Self::Damping => "damping",
+        }
+    }
+}
+
+impl Display for ForceFieldKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an invalid force field kind string
+#[derive(Debug)]
+pub struct ForceFieldKindError;
+
+impl Display for ForceFieldKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid force field kind. Expected one of: gravity, radial, vortex, damping"
+        )
+    }
+}
+
+impl Error for ForceFieldKindError {}
+
+impl FromStr for ForceFieldKind {
+    type Err = ForceFieldKindError;
+
+    fn from_str(s: &str) -> Result<Self, ForceFieldKindError> {
+        match s.to_lowercase().as_str() {
+            "gravity" => Ok(Self::Gravity),
+            "radial" | "attractor" => Ok(Self::Radial),
+            "vortex" => Ok(Self::Vortex),
+            "damping" => Ok(Self::Damping),
+            _ => Err(ForceFieldKindError),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Genome component for particles
+//!
+//! A particle's own copy of its row of the interaction table, used in
+//! evolution mode instead of the table.
+
+use crate::resources::SimRng;
+use bevy::ecs::component::Component;
+use serde::{Deserialize, Serialize};
+
+/// Genome component for particles
+///
+/// `strengths[source]` is the force a particle of the given source
+/// species exerts on this particle, replacing the interaction table value
+/// while evolution is enabled (see
+/// [`Evolution`](crate::resources::Evolution)). Children inherit the
+/// genome of their parent with mutations.
+#[derive(Component, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Genome {
+    /// Interaction strength per source species
+    pub strengths: Vec<f32>,
+    /// Identifier shared by all descendants of the same founder
+    pub lineage: u64,
+    /// Number of ancestors since the founder
+    pub generation: u32,
+}
+
+impl Genome {
+    /// Creates the genome of a founder with the given strengths
+    #[must_use]
+    pub const fn founder(strengths: Vec<f32>, lineage: u64) -> Self {
+        Self {
+            strengths,
+            lineage,
+            generation: 0,
+        }
+    }
+
+    /// Returns true if the genome has not been seeded yet
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.strengths.is_empty()
+    }
+
+    /// Returns the genome of a child
+    ///
+    /// Every strength gets Gaussian noise with standard deviation `sigma`
+    /// and is clamped to `[-limit, limit]`. The child keeps the lineage
+    /// and is one generation further.
+    #[must_use]
+    pub fn child(&self, sigma: f32, limit: f32, rng: &mut SimRng) -> Self {
+        let strengths = self
+            .strengths
+            .iter()
+            .map(|strength| {
+                let mutation = if sigma > 0.0 {
+                    rng.gaussian() * sigma
+                } else {
+                    0.0
+                };
+                (strength + mutation).clamp(-limit, limit)
+            })
+            .collect();
+        Self {
+            strengths,
+            lineage: self.lineage,
+            generation: self.generation + 1,
+        }
+    }
+}
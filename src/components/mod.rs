@@ -2,12 +2,26 @@
 //!
 //! This module contains all Bevy components used in the game.
 
+mod dormant;
+mod energy;
+mod force_field;
+mod force_field_kind;
+mod genome;
+mod orbit_camera;
 mod particle_marker;
 mod particle_type;
 mod position;
+mod previous_position;
 mod velocity;
 
+pub use dormant::Dormant;
+pub use energy::Energy;
+pub use force_field::ForceField;
+pub use force_field_kind::{ForceFieldKind, ForceFieldKindError};
+pub use genome::Genome;
+pub use orbit_camera::OrbitCamera;
 pub use particle_marker::ParticleMarker;
 pub use particle_type::{ParticleType, ParticleTypeError};
 pub use position::Position;
+pub use previous_position::PreviousPosition;
 pub use velocity::Velocity;
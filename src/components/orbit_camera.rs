@@ -0,0 +1,49 @@
+//! Orbit camera component for the 3D mode
+//!
+//! Keeps the camera on a sphere around a focus point. The `orbit_camera`
+//! system changes the angles and radius from keyboard input.
+
+use bevy::ecs::component::Component;
+use bevy::math::Vec3;
+use bevy::transform::components::Transform;
+
+/// Orbit camera component for the 3D mode
+///
+/// The camera sits at `radius` from `focus`, rotated by `yaw` around the
+/// y axis and tilted by `pitch`, and always looks at `focus`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct OrbitCamera {
+    /// Point the camera looks at
+    pub focus: Vec3,
+    /// Distance from the focus point
+    pub radius: f32,
+    /// Rotation around the y axis in radians
+    pub yaw: f32,
+    /// Elevation above the xz plane in radians
+    pub pitch: f32,
+}
+
+impl OrbitCamera {
+    /// Creates an orbit camera looking at the origin from `radius` away
+    #[must_use]
+    pub const fn new(radius: f32) -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            radius,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+
+    /// Returns the camera transform for the current angles and radius
+    #[must_use]
+    pub fn transform(&self) -> Transform {
+        let offset = Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        );
+        Transform::from_translation(self.focus + offset * self.radius)
+            .looking_at(self.focus, Vec3::Y)
+    }
+}
@@ -0,0 +1,27 @@
+//! Previous position component for particles
+//!
+//! Stores the position at the start of the last physics tick.
+//! `sync_transform` interpolates between this and `Position` so that
+//! motion stays smooth when physics and rendering run at different rates.
+
+use bevy::ecs::component::Component;
+use bevy::math::Vec3;
+
+/// Previous position component for particles
+///
+/// Stores the position at the start of the last physics tick.
+/// `sync_transform` interpolates between this and `Position` so that
+/// motion stays smooth when physics and rendering run at different rates.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct PreviousPosition {
+    /// Position vector in world space before the last physics tick
+    pub value: Vec3,
+}
+
+impl PreviousPosition {
+    /// Creates a new previous position from a vector
+    #[must_use]
+    pub const fn new(value: Vec3) -> Self {
+        Self { value }
+    }
+}
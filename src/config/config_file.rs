@@ -0,0 +1,245 @@
+//! TOML config file
+//!
+//! The file has three tables and a species array, each optional and each
+//! falling back to the defaults for missing keys:
+//!
+//! - `[startup]`: [`StartupConfig`] (matrix and reactions files, paused start, snapshot)
+//! - `[particles]`: [`ParticleConfig`]
+//! - `[camera]`: [`CameraMoveConfig`]
+//! - `[[species]]`: [`SpeciesConfig`], one entry per species
+//!
+//! Unknown tables or keys and out-of-range values are errors, so typos
+//! do not silently fall back to a default.
+
+use crate::config::SpeciesConfig;
+use crate::resources::{CameraMoveConfig, ParticleConfig, SpeciesRegistry, StartupConfig};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::path::Path;
+
+/// Config file read when no other path is given
+pub const DEFAULT_CONFIG_PATH: &str = "particle_life.toml";
+
+/// Environment variable naming a config file to read instead of the default
+pub const CONFIG_PATH_ENV: &str = "PARTICLE_LIFE_CONFIG";
+
+/// Error returned when a config file cannot be read or is invalid
+#[derive(Debug)]
+pub struct ConfigError {
+    /// Path of the config file
+    pub path: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid config file {}: {}", self.path, self.message)
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Contents of the TOML config file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Matrix file and startup behavior
+    pub startup: StartupConfig,
+    /// Particle simulation configuration
+    pub particles: ParticleConfig,
+    /// Camera movement controls
+    pub camera: CameraMoveConfig,
+    /// Species to simulate, in registry order
+    ///
+    /// Empty means the 17 built-in species.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub species: Vec<SpeciesConfig>,
+}
+
+impl ConfigFile {
+    /// Loads the config file at `path`, or the default one
+    ///
+    /// A given `path` must exist. Without one, [`DEFAULT_CONFIG_PATH`] is
+    /// read if it exists and the defaults are used otherwise.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, does not parse or
+    /// holds an unknown key or invalid value
+    pub fn load(path: Option<&str>) -> Result<Self, ConfigError> {
+        match path {
+            Some(path) => Self::from_file(path),
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => Self::from_file(DEFAULT_CONFIG_PATH),
+            None => {
+                bevy::log::info!("No {} found, using default config", DEFAULT_CONFIG_PATH);
+                Ok(Self::default())
+            }
+        }
+    }
+
+    /// Loads a config file
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, does not parse or
+    /// holds an unknown key or invalid value
+    pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+        let error = |message: String| ConfigError {
+            path: path.to_string(),
+            message,
+        };
+        let text = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        let config: Self = toml::from_str(&text).map_err(|e| error(e.to_string()))?;
+        config.validate().map_err(error)?;
+        bevy::log::info!("Loaded config from {}", path);
+        Ok(config)
+    }
+
+    /// Saves the config file
+    ///
+    /// # Errors
+    /// Returns an error if the config cannot be serialized or the file
+    /// cannot be written
+    pub fn to_file(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        bevy::log::info!("Saved config to {}", path);
+        Ok(())
+    }
+
+    /// Builds the species registry
+    ///
+    /// Without `[[species]]` entries the default registry is used. A
+    /// color that does not parse falls back to the default color; loaded
+    /// files have already been checked by [`Self::validate`].
+    #[must_use]
+    pub fn registry(&self) -> SpeciesRegistry {
+        if self.species.is_empty() {
+            return SpeciesRegistry::default();
+        }
+        let mut registry = SpeciesRegistry::new();
+        for species in &self.species {
+            let color = species.color().ok().flatten();
+            let particle_type = registry.add(species.name.trim(), color);
+            if let Some(entry) = registry.get_mut(particle_type) {
+                entry.properties = species.properties();
+            }
+        }
+        registry
+    }
+
+    /// Checks that all values are within their valid ranges
+    ///
+    /// The `[particles]` table goes through [`ParticleConfig::validate`],
+    /// the same check the `set` console command applies.
+    ///
+    /// # Errors
+    /// Returns a message naming the first invalid key
+    pub fn validate(&self) -> Result<(), String> {
+        self.particles
+            .validate()
+            .map_err(|e| format!("particles.{e}"))?;
+        let camera = &self.camera;
+        let checks = [
+            (camera.min_scale > 0.0, "camera.min_scale must be positive"),
+            (
+                camera.max_scale >= camera.min_scale,
+                "camera.max_scale must not be below camera.min_scale",
+            ),
+        ];
+        if let Some((_, message)) = checks.into_iter().find(|(valid, _)| !valid) {
+            return Err(message.to_string());
+        }
+        for (index, species) in self.species.iter().enumerate() {
+            species
+                .validate()
+                .map_err(|e| format!("species[{index}].{e}"))?;
+            if self.species[..index]
+                .iter()
+                .any(|other| other.name.trim().eq_ignore_ascii_case(species.name.trim()))
+            {
+                return Err(format!(
+                    "species[{index}].name {} is used twice",
+                    species.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::MAX_SEED;
+
+    #[test]
+    fn species_round_trip_through_toml() {
+        let mut registry = SpeciesRegistry::generate(3);
+        let first = registry.types().next().unwrap();
+        registry.get_mut(first).unwrap().properties.mass = 2.5;
+        let file = ConfigFile {
+            species: registry
+                .types()
+                .filter_map(|particle_type| registry.get(particle_type))
+                .map(SpeciesConfig::from)
+                .collect(),
+            ..ConfigFile::default()
+        };
+
+        let text = toml::to_string_pretty(&file).unwrap();
+        let loaded: ConfigFile = toml::from_str(&text).unwrap();
+
+        assert_eq!(loaded.validate(), Ok(()));
+        assert_eq!(loaded.registry(), registry);
+    }
+
+    #[test]
+    fn rejects_seeds_toml_cannot_store() {
+        let mut file = ConfigFile::default();
+        file.particles.seed = MAX_SEED;
+        assert!(toml::to_string_pretty(&file).is_ok());
+        assert_eq!(file.validate(), Ok(()));
+
+        file.particles.seed = MAX_SEED + 1;
+        assert!(file.validate().is_err());
+    }
+
+    #[test]
+    fn rejects_non_finite_particle_values() {
+        let invalid = |change: fn(&mut ParticleConfig)| {
+            let mut file = ConfigFile::default();
+            change(&mut file.particles);
+            file.validate()
+        };
+
+        assert_eq!(
+            invalid(|config| config.repel_force = f32::NAN),
+            Err("particles.repel_force must be finite and not negative".to_string())
+        );
+        assert!(invalid(|config| config.repel_force = f32::INFINITY).is_err());
+        assert!(invalid(|config| config.repel_force = -1.0).is_err());
+        assert!(invalid(|config| config.r = f32::INFINITY).is_err());
+        assert!(invalid(|config| config.dt = f32::INFINITY).is_err());
+        assert!(invalid(|config| config.map_width = f32::INFINITY).is_err());
+        assert!(invalid(|config| config.substeps = 0).is_err());
+        assert_eq!(invalid(|config| config.max_speed = f32::INFINITY), Ok(()));
+        assert_eq!(invalid(|config| config.repel_force = 0.0), Ok(()));
+    }
+
+    #[test]
+    fn rejects_invalid_species() {
+        let parse = |text: &str| toml::from_str::<ConfigFile>(text).unwrap().validate();
+
+        assert_eq!(
+            parse("[[species]]\nname = \"A\"\n[[species]]\nname = \"a\""),
+            Err("species[1].name a is used twice".to_string())
+        );
+        assert_eq!(
+            parse("[[species]]\nname = \"A\"\nmass = 0.0"),
+            Err("species[0].mass must be a positive number".to_string())
+        );
+        assert!(parse("[[species]]\nname = \"A\"\ncolor = \"nope\"").is_err());
+        assert!(toml::from_str::<ConfigFile>("[[species]]\nname = \"A\"\nspeed = 1.0").is_err());
+    }
+}
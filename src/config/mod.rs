@@ -0,0 +1,10 @@
+//! Config module
+//!
+//! This module reads and writes the TOML config file that sets the
+//! particle configuration, species, camera controls and startup behavior.
+
+mod config_file;
+mod species_config;
+
+pub use config_file::{CONFIG_PATH_ENV, ConfigError, ConfigFile, DEFAULT_CONFIG_PATH};
+pub use species_config::SpeciesConfig;
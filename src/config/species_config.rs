@@ -0,0 +1,108 @@
+//! Species entry of the config file
+//!
+//! Each `[[species]]` entry registers one species with its name, optional
+//! color and physical properties, in registry order.
+
+use crate::resources::{DEFAULT_MASS, DEFAULT_SIZE, Species, SpeciesProperties};
+use bevy::color::{Color, Srgba};
+use serde::{Deserialize, Serialize};
+
+/// One `[[species]]` entry of the config file
+///
+/// Properties mirror [`SpeciesProperties`]; optional ones fall back to
+/// the `[particles]` setting when unset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpeciesConfig {
+    /// Display name, unique ignoring case
+    pub name: String,
+    /// Render color as a hex string like `"#f59e0b"`
+    ///
+    /// Without one the default color for the species' position is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Mass, accelerations are forces divided by it
+    pub mass: f32,
+    /// Velocity half-life, overriding `particles.dt_half`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dt_half: Option<f32>,
+    /// Speed cap, overriding `particles.max_speed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_speed: Option<f32>,
+    /// Collision radius
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collision_radius: Option<f32>,
+    /// Render radius
+    pub size: f32,
+}
+
+impl Default for SpeciesConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            color: None,
+            mass: DEFAULT_MASS,
+            dt_half: None,
+            max_speed: None,
+            collision_radius: None,
+            size: DEFAULT_SIZE,
+        }
+    }
+}
+
+impl SpeciesConfig {
+    /// Returns the physical properties of this species
+    #[must_use]
+    pub const fn properties(&self) -> SpeciesProperties {
+        SpeciesProperties {
+            mass: self.mass,
+            dt_half: self.dt_half,
+            max_speed: self.max_speed,
+            collision_radius: self.collision_radius,
+            size: self.size,
+        }
+    }
+
+    /// Returns the color of this species, or `None` if it has none
+    ///
+    /// # Errors
+    /// Returns an error if the color is not a valid hex color
+    pub fn color(&self) -> Result<Option<Color>, String> {
+        self.color
+            .as_deref()
+            .map(|hex| {
+                Srgba::hex(hex)
+                    .map(Color::Srgba)
+                    .map_err(|e| format!("color is invalid: {e}"))
+            })
+            .transpose()
+    }
+
+    /// Checks that the name is set, the color parses and all properties
+    /// pass [`SpeciesProperties::validate`]
+    ///
+    /// # Errors
+    /// Returns a message naming the first invalid key
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+        self.color()?;
+        self.properties().validate()
+    }
+}
+
+impl From<&Species> for SpeciesConfig {
+    fn from(species: &Species) -> Self {
+        let properties = species.properties;
+        Self {
+            name: species.name.clone(),
+            color: Some(species.color.to_srgba().to_hex()),
+            mass: properties.mass,
+            dt_half: properties.dt_half,
+            max_speed: properties.max_speed,
+            collision_radius: properties.collision_radius,
+            size: properties.size,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! `anneal` console command
+//!
+//! Ramps the temperature over simulated time.
+
+use crate::resources::{Annealing, ParticleConfig};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::Parser;
+
+/// Console command to ramp the temperature over simulated time
+///
+/// Without arguments the current ramp is stopped.
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "anneal")]
+pub struct AnnealCommand {
+    /// Temperature at the end of the ramp
+    target: Option<f32>,
+    /// Length of the ramp in simulated time
+    #[arg(default_value_t = 100.0)]
+    duration: f32,
+}
+
+/// Handle the `anneal` console command
+///
+/// Starts a linear ramp from the current temperature to `target`,
+/// applied by the `anneal_temperature` system. A zero duration sets the
+/// temperature at once.
+pub fn anneal(
+    mut log: ConsoleCommand<AnnealCommand>,
+    mut annealing: ResMut<Annealing>,
+    mut config: ResMut<ParticleConfig>,
+) {
+    if let Some(Ok(AnnealCommand { target, duration })) = log.take() {
+        let Some(target) = target else {
+            annealing.stop();
+            reply!(
+                log,
+                "Stopped annealing at temperature {:.3}",
+                config.temperature
+            );
+            return;
+        };
+        if target < 0.0 || duration < 0.0 {
+            reply!(log, "temperature and duration must not be negative");
+            return;
+        }
+        if duration <= 0.0 {
+            annealing.stop();
+            config.temperature = target;
+            reply!(log, "set temperature to {:.3} successfully", target);
+            return;
+        }
+        *annealing = Annealing::new(config.temperature, target, duration);
+        reply!(
+            log,
+            "Annealing from {:.3} to {:.3} over {:.1}",
+            config.temperature,
+            target,
+            duration
+        );
+    }
+}
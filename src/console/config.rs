@@ -0,0 +1,55 @@
+//! `save_config` console command
+//!
+//! Writes the current settings to a TOML config file.
+
+use crate::config::{ConfigFile, DEFAULT_CONFIG_PATH, SpeciesConfig};
+use crate::resources::{
+    CameraMoveConfig, ParticleConfig, ParticleUpdateToggle, SpeciesRegistry, StartupConfig,
+};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::Parser;
+
+/// Console command to save the current configuration to a TOML config file
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "save_config")]
+pub struct SaveConfigCommand {
+    /// File to write, defaults to `particle_life.toml`
+    path: Option<String>,
+}
+
+/// Handle the `save_config` console command
+///
+/// Writes the current [`ParticleConfig`], [`CameraMoveConfig`],
+/// [`StartupConfig`] and species to a config file. `paused` records
+/// whether physics updates are currently off.
+#[allow(clippy::needless_pass_by_value)]
+pub fn save_config(
+    mut log: ConsoleCommand<SaveConfigCommand>,
+    config: Res<ParticleConfig>,
+    registry: Res<SpeciesRegistry>,
+    camera: Res<CameraMoveConfig>,
+    startup: Res<StartupConfig>,
+    toggle: Res<ParticleUpdateToggle>,
+) {
+    if let Some(Ok(SaveConfigCommand { path })) = log.take() {
+        let path = path.as_deref().unwrap_or(DEFAULT_CONFIG_PATH);
+        let file = ConfigFile {
+            startup: StartupConfig {
+                paused: !toggle.is_enabled(),
+                ..startup.clone()
+            },
+            particles: config.clone(),
+            camera: *camera,
+            species: registry
+                .types()
+                .filter_map(|particle_type| registry.get(particle_type))
+                .map(SpeciesConfig::from)
+                .collect(),
+        };
+        match file.to_file(path) {
+            Ok(()) => reply!(log, "Saved config to file: {}", path),
+            Err(e) => reply!(log, "Error: {}", e),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! `ecosystem` console command
+//!
+//! Configures ecosystem mode.
+
+use super::parse::parse_pair;
+use crate::resources::{Ecosystem, EcosystemStats, SpeciesRegistry};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::{Parser, Subcommand};
+
+/// Subcommands for the `ecosystem` console command
+#[derive(Subcommand, Clone, PartialEq)]
+enum EcosystemSubcommand {
+    /// Turn ecosystem mode on
+    On,
+    /// Turn ecosystem mode off; energies are kept
+    Off,
+    /// Show the settings, feeding rates, predation chances and caps
+    Show,
+    /// Show population, energy, births and deaths per species
+    Stats,
+    /// Set the energy of particles that have none yet
+    Initial { energy: f32 },
+    /// Set the energy every particle loses per second
+    Drain { rate: f32 },
+    /// Set the energy at which a particle splits into two
+    Split { energy: f32 },
+    /// Set the fraction of a prey's energy gained by eating it
+    Efficiency { fraction: f32 },
+    /// Set the feeding distance; without a value it follows r
+    Range { distance: Option<f32> },
+    /// Set the total population cap; without a value there is none
+    Population { max: Option<usize> },
+    /// Set the population cap of a species; without a value there is none
+    Cap { species: String, max: Option<usize> },
+    /// Set the energy per second an eater gains from each food particle in range (0 removes)
+    Feed {
+        eater: String,
+        food: String,
+        #[arg(allow_hyphen_values = true)]
+        rate: f32,
+    },
+    /// Set the chance per step that an eater eats a prey in contact (0 removes)
+    Prey {
+        eater: String,
+        prey: String,
+        probability: f32,
+    },
+}
+
+/// Console command to configure ecosystem mode
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "ecosystem")]
+pub struct EcosystemCommand {
+    #[command(subcommand)]
+    subcommand: EcosystemSubcommand,
+}
+
+/// Formats the ecosystem settings for the console
+fn describe_ecosystem(ecosystem: &Ecosystem, registry: &SpeciesRegistry) -> String {
+    use std::fmt::Write;
+    let range = ecosystem
+        .feeding_distance
+        .map_or_else(|| "r".to_string(), |distance| format!("{distance:.0}"));
+    let population = ecosystem
+        .max_population
+        .map_or_else(|| "unlimited".to_string(), |max| max.to_string());
+    let mut output = format!(
+        "Ecosystem {}: initial {}, drain {}/s, split {}, efficiency {}, range {}, population {}\n",
+        if ecosystem.enabled { "on" } else { "off" },
+        ecosystem.initial_energy,
+        ecosystem.drain,
+        ecosystem.split_energy,
+        ecosystem.efficiency,
+        range,
+        population
+    );
+    for (eater, food, rate) in ecosystem.feeding_rates() {
+        writeln!(
+            output,
+            "  {} feeds on {}: {}/s",
+            registry.name(eater),
+            registry.name(food),
+            rate
+        )
+        .unwrap();
+    }
+    for (eater, prey, probability) in ecosystem.predation_chances() {
+        writeln!(
+            output,
+            "  {} eats {}: p {}",
+            registry.name(eater),
+            registry.name(prey),
+            probability
+        )
+        .unwrap();
+    }
+    for (ptype, cap) in ecosystem.caps() {
+        writeln!(output, "  {} capped at {}", registry.name(ptype), cap).unwrap();
+    }
+    output
+}
+
+/// Handle the `ecosystem` console command
+///
+/// Edits the [`Ecosystem`] settings and reports the [`EcosystemStats`].
+#[allow(clippy::needless_pass_by_value, clippy::too_many_lines)]
+pub fn ecosystem(
+    mut log: ConsoleCommand<EcosystemCommand>,
+    mut ecosystem: ResMut<Ecosystem>,
+    stats: Res<EcosystemStats>,
+    registry: Res<SpeciesRegistry>,
+) {
+    use std::fmt::Write;
+    let Some(Ok(EcosystemCommand { subcommand })) = log.take() else {
+        return;
+    };
+    match subcommand {
+        EcosystemSubcommand::On => {
+            ecosystem.enabled = true;
+            reply!(log, "Ecosystem mode on");
+        }
+        EcosystemSubcommand::Off => {
+            ecosystem.enabled = false;
+            reply!(log, "Ecosystem mode off");
+        }
+        EcosystemSubcommand::Show => {
+            reply!(log, "{}", describe_ecosystem(&ecosystem, &registry));
+        }
+        EcosystemSubcommand::Stats => {
+            let mut output = format!("Population {}:\n", stats.population());
+            for ptype in registry.types() {
+                let census = stats.get(ptype);
+                writeln!(
+                    output,
+                    "{:>12} {:>6} alive, mean energy {:.1}, {} born, {} starved, {} eaten",
+                    registry.name(ptype),
+                    census.population,
+                    census.mean_energy(),
+                    census.births,
+                    census.starvations,
+                    census.eaten
+                )
+                .unwrap();
+            }
+            reply!(log, "{}", output);
+        }
+        EcosystemSubcommand::Initial { energy } => {
+            if energy > 0.0 {
+                ecosystem.initial_energy = energy;
+                reply!(log, "initial energy: {}", energy);
+            } else {
+                reply!(log, "initial energy must be positive");
+            }
+        }
+        EcosystemSubcommand::Drain { rate } => {
+            if rate >= 0.0 {
+                ecosystem.drain = rate;
+                reply!(log, "drain: {}/s", rate);
+            } else {
+                reply!(log, "drain must not be negative");
+            }
+        }
+        EcosystemSubcommand::Split { energy } => {
+            if energy > 0.0 {
+                ecosystem.split_energy = energy;
+                reply!(log, "split energy: {}", energy);
+            } else {
+                reply!(log, "split energy must be positive");
+            }
+        }
+        EcosystemSubcommand::Efficiency { fraction } => {
+            if fraction >= 0.0 {
+                ecosystem.efficiency = fraction;
+                reply!(log, "efficiency: {}", fraction);
+            } else {
+                reply!(log, "efficiency must not be negative");
+            }
+        }
+        EcosystemSubcommand::Range { distance } => {
+            if distance.is_some_and(|distance| distance <= 0.0) {
+                reply!(log, "feeding distance must be positive");
+            } else {
+                ecosystem.feeding_distance = distance;
+                reply!(log, "{}", describe_ecosystem(&ecosystem, &registry));
+            }
+        }
+        EcosystemSubcommand::Population { max } => {
+            ecosystem.max_population = max;
+            reply!(log, "{}", describe_ecosystem(&ecosystem, &registry));
+        }
+        EcosystemSubcommand::Cap { species, max } => match registry.parse(&species) {
+            Ok(ptype) => {
+                ecosystem.set_cap(ptype, max);
+                reply!(log, "{}", describe_ecosystem(&ecosystem, &registry));
+            }
+            Err(e) => reply!(log, "{}", e),
+        },
+        EcosystemSubcommand::Feed { eater, food, rate } => {
+            match parse_pair(&registry, &eater, &food) {
+                Ok((eater, food)) => {
+                    ecosystem.set_feeding(eater, food, rate);
+                    reply!(log, "{}", describe_ecosystem(&ecosystem, &registry));
+                }
+                Err(e) => reply!(log, "{}", e),
+            }
+        }
+        EcosystemSubcommand::Prey {
+            eater,
+            prey,
+            probability,
+        } => match parse_pair(&registry, &eater, &prey) {
+            Ok(_) if !(0.0..=1.0).contains(&probability) => {
+                reply!(log, "probability must be between 0 and 1");
+            }
+            Ok((eater, prey)) => {
+                ecosystem.set_predation(eater, prey, probability);
+                reply!(log, "{}", describe_ecosystem(&ecosystem, &registry));
+            }
+            Err(e) => reply!(log, "{}", e),
+        },
+    }
+}
@@ -0,0 +1,184 @@
+//! `field` console command
+//!
+//! Manages external force fields and the field brush.
+
+use super::parse::parse_vec3;
+use crate::components::{ForceField, ForceFieldKind, ParticleTypeError};
+use crate::resources::{FieldBrush, SpeciesRegistry};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::{Args, Parser, Subcommand};
+
+/// Optional parameters of a force field
+#[derive(Args, Clone, PartialEq)]
+struct FieldOptions {
+    /// Magnitude of the acceleration (negative radial fields repel)
+    #[arg(long, allow_hyphen_values = true)]
+    strength: Option<f32>,
+    /// Range of the field (inf for the whole map)
+    #[arg(long)]
+    radius: Option<f32>,
+    /// Exponent of the fade towards the edge of the range
+    #[arg(long)]
+    falloff: Option<f32>,
+    /// Gravity direction or vortex axis as x,y[,z]
+    #[arg(long, value_parser = parse_vec3, allow_hyphen_values = true)]
+    direction: Option<Vec3>,
+    /// Only affect this species
+    #[arg(long)]
+    species: Option<String>,
+}
+
+impl FieldOptions {
+    /// Applies the given options to a field
+    ///
+    /// # Errors
+    /// Returns an error if the species is not registered
+    fn apply(
+        &self,
+        field: &mut ForceField,
+        registry: &SpeciesRegistry,
+    ) -> Result<(), ParticleTypeError> {
+        if let Some(strength) = self.strength {
+            field.strength = strength;
+        }
+        if let Some(radius) = self.radius {
+            field.radius = radius;
+        }
+        if let Some(falloff) = self.falloff {
+            field.falloff = falloff;
+        }
+        if let Some(direction) = self.direction {
+            field.direction = direction;
+        }
+        if let Some(species) = &self.species {
+            field.species = Some(registry.parse(species)?);
+        }
+        Ok(())
+    }
+}
+
+/// Subcommands for the `field` console command
+#[derive(Subcommand, Clone, PartialEq)]
+enum FieldSubcommand {
+    /// Spawn a force field (gravity, radial, vortex, damping)
+    Add {
+        kind: ForceFieldKind,
+        /// Center of the field as x,y[,z]
+        #[arg(long, value_parser = parse_vec3, allow_hyphen_values = true)]
+        at: Option<Vec3>,
+        #[command(flatten)]
+        options: FieldOptions,
+    },
+    /// Set the field placed by left clicks; without a kind, clicks place nothing
+    Brush {
+        kind: Option<ForceFieldKind>,
+        #[command(flatten)]
+        options: FieldOptions,
+    },
+    /// List all force fields
+    List,
+    /// Remove the force field with the given index from `field list`
+    Remove { index: usize },
+    /// Remove all force fields
+    Clear,
+}
+
+/// Console command to manage external force fields
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "field")]
+pub struct FieldCommand {
+    #[command(subcommand)]
+    subcommand: FieldSubcommand,
+}
+
+/// Formats a force field for the console
+fn describe_field(field: &ForceField, registry: &SpeciesRegistry) -> String {
+    format!(
+        "{} at ({:.0}, {:.0}, {:.0}), strength {:.2}, radius {:.0}, falloff {:.1}, direction ({:.2}, {:.2}, {:.2}), species {}",
+        field.kind,
+        field.center.x,
+        field.center.y,
+        field.center.z,
+        field.strength,
+        field.radius,
+        field.falloff,
+        field.direction.x,
+        field.direction.y,
+        field.direction.z,
+        field
+            .species
+            .map_or("all", |species| registry.name(species))
+    )
+}
+
+/// Handle the `field` console command
+///
+/// Spawns, lists and removes [`ForceField`] entities and sets the
+/// [`FieldBrush`] used for placing fields with the mouse. Fields are
+/// listed in spawn order.
+#[allow(clippy::needless_pass_by_value)]
+pub fn field(
+    mut log: ConsoleCommand<FieldCommand>,
+    mut commands: Commands,
+    fields: Query<(Entity, &ForceField)>,
+    mut brush: ResMut<FieldBrush>,
+    registry: Res<SpeciesRegistry>,
+) {
+    use std::fmt::Write;
+    if let Some(Ok(FieldCommand { subcommand })) = log.take() {
+        let mut fields: Vec<(Entity, &ForceField)> = fields.iter().collect();
+        fields.sort_by_key(|(entity, _)| *entity);
+
+        match subcommand {
+            FieldSubcommand::Add { kind, at, options } => {
+                let mut field = ForceField::new(kind, at.unwrap_or(Vec3::ZERO));
+                if let Err(e) = options.apply(&mut field, &registry) {
+                    reply!(log, "{}", e);
+                    return;
+                }
+                commands.spawn(field);
+                reply!(log, "Added {}", describe_field(&field, &registry));
+            }
+            FieldSubcommand::Brush { kind, options } => {
+                let Some(kind) = kind else {
+                    brush.field = None;
+                    reply!(log, "Cleared field brush");
+                    return;
+                };
+                let mut field = ForceField::new(kind, Vec3::ZERO);
+                if let Err(e) = options.apply(&mut field, &registry) {
+                    reply!(log, "{}", e);
+                    return;
+                }
+                brush.field = Some(field);
+                reply!(
+                    log,
+                    "Left click places {}, right click removes",
+                    describe_field(&field, &registry)
+                );
+            }
+            FieldSubcommand::List => {
+                let mut output = format!("{} force fields:\n", fields.len());
+                for (index, (_, field)) in fields.iter().enumerate() {
+                    writeln!(output, "{index:>3} {}", describe_field(field, &registry)).unwrap();
+                }
+                reply!(log, "{}", output);
+            }
+            FieldSubcommand::Remove { index } => {
+                if let Some((entity, field)) = fields.get(index) {
+                    commands.entity(*entity).despawn();
+                    reply!(log, "Removed {}", describe_field(field, &registry));
+                } else {
+                    reply!(log, "no force field with index {}", index);
+                }
+            }
+            FieldSubcommand::Clear => {
+                for (entity, _) in &fields {
+                    commands.entity(*entity).despawn();
+                }
+                reply!(log, "Removed {} force fields", fields.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! `genome` console command
+//!
+//! Configures evolution mode.
+
+use crate::resources::{Evolution, GenomeStats, ParticleInteractionTable, SpeciesRegistry};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::{Parser, Subcommand};
+
+/// Subcommands for the `genome` console command
+#[derive(Subcommand, Clone, PartialEq)]
+enum GenomeSubcommand {
+    /// Turn evolution mode on; particles without a genome copy their table row
+    On,
+    /// Turn evolution mode off; the interaction table applies again
+    Off,
+    /// Show the evolution settings
+    Show,
+    /// Show lineages, diversity and the dominant genome per species
+    Stats,
+    /// Set the standard deviation of the mutation of every strength
+    Mutation { sigma: f32 },
+    /// Set the largest absolute strength a mutation can reach
+    Limit { limit: f32 },
+    /// Respawn a fraction of the particles every interval steps as mutated copies of others; without an interval periodic respawns stop
+    Respawn {
+        interval: Option<u64>,
+        #[arg(default_value_t = 0.01)]
+        fraction: f32,
+    },
+    /// Save the interaction table with the dominant genomes as rows to a CSV file
+    Export {
+        #[arg(default_value = "evolved_interactions.csv")]
+        path: String,
+    },
+}
+
+/// Console command to configure evolution mode
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "genome")]
+pub struct GenomeCommand {
+    #[command(subcommand)]
+    subcommand: GenomeSubcommand,
+}
+
+/// Formats the evolution settings for the console
+fn describe_evolution(evolution: &Evolution) -> String {
+    let respawn = evolution.respawn_interval.map_or_else(
+        || "off".to_string(),
+        |interval| {
+            format!(
+                "{:.1}% every {} steps",
+                evolution.respawn_fraction * 100.0,
+                interval
+            )
+        },
+    );
+    format!(
+        "Evolution {}: mutation {}, limit {}, respawn {}",
+        if evolution.enabled { "on" } else { "off" },
+        evolution.mutation,
+        evolution.limit,
+        respawn
+    )
+}
+
+/// Handle the `genome` console command
+///
+/// Edits the [`Evolution`] settings, reports the [`GenomeStats`] and
+/// exports the dominant genomes as an interaction CSV file.
+#[allow(clippy::needless_pass_by_value)]
+pub fn genome(
+    mut log: ConsoleCommand<GenomeCommand>,
+    mut evolution: ResMut<Evolution>,
+    stats: Res<GenomeStats>,
+    interaction_table: Res<ParticleInteractionTable>,
+    registry: Res<SpeciesRegistry>,
+) {
+    use std::fmt::Write;
+    let Some(Ok(GenomeCommand { subcommand })) = log.take() else {
+        return;
+    };
+    match subcommand {
+        GenomeSubcommand::On => {
+            evolution.enabled = true;
+            reply!(log, "Evolution mode on");
+        }
+        GenomeSubcommand::Off => {
+            evolution.enabled = false;
+            reply!(log, "Evolution mode off");
+        }
+        GenomeSubcommand::Show => reply!(log, "{}", describe_evolution(&evolution)),
+        GenomeSubcommand::Stats => {
+            let mut output = String::from("Genomes:\n");
+            for ptype in registry.types() {
+                let Some(genomes) = stats.get(ptype) else {
+                    continue;
+                };
+                let dominant: Vec<String> = genomes
+                    .dominant_genome
+                    .iter()
+                    .map(|strength| format!("{strength:.2}"))
+                    .collect();
+                writeln!(
+                    output,
+                    "{:>12} {:>6} genomes, {} lineages, diversity {:.3}, generation {}, dominant #{} ({}): [{}]",
+                    registry.name(ptype),
+                    genomes.population,
+                    genomes.lineages,
+                    genomes.diversity,
+                    genomes.max_generation,
+                    genomes.dominant_lineage,
+                    genomes.dominant_count,
+                    dominant.join(", ")
+                )
+                .unwrap();
+            }
+            reply!(log, "{}", output);
+        }
+        GenomeSubcommand::Mutation { sigma } => {
+            if sigma >= 0.0 {
+                evolution.mutation = sigma;
+                reply!(log, "{}", describe_evolution(&evolution));
+            } else {
+                reply!(log, "mutation must not be negative");
+            }
+        }
+        GenomeSubcommand::Limit { limit } => {
+            if limit > 0.0 {
+                evolution.limit = limit;
+                reply!(log, "{}", describe_evolution(&evolution));
+            } else {
+                reply!(log, "limit must be positive");
+            }
+        }
+        GenomeSubcommand::Respawn { interval, fraction } => {
+            if interval == Some(0) || !(0.0..=1.0).contains(&fraction) {
+                reply!(
+                    log,
+                    "interval must be positive and fraction between 0 and 1"
+                );
+            } else {
+                evolution.respawn_interval = interval;
+                evolution.respawn_fraction = fraction;
+                reply!(log, "{}", describe_evolution(&evolution));
+            }
+        }
+        GenomeSubcommand::Export { path } => {
+            let table = stats.dominant_table(&interaction_table);
+            match table.to_csv_file(&path, &registry) {
+                Ok(()) => reply!(log, "Saved dominant genomes to {}", path),
+                Err(e) => reply!(log, "Failed to save dominant genomes: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Interaction console commands
+//!
+//! Sets, resets, randomizes and saves the particle interactions
+//! and their per-pair profiles.
+
+use super::parse::parse_pair;
+use crate::resources::{
+    ForceKernelKind, ParticleInteractionTable, SimRng, SpeciesRegistry, StartupConfig,
+};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::Parser;
+use rand::Rng;
+
+/// Console command to set interaction between particle types
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "interaction")]
+pub struct InteractionCommand {
+    /// Target species name
+    target: String,
+    /// Source species name
+    source: String,
+    /// Interaction force value
+    value: f32,
+}
+
+/// Console command to set per-pair interaction radius, collision ratio and kernel
+///
+/// Without options the current profile of the pair is printed.
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "interaction_profile")]
+pub struct InteractionProfileCommand {
+    /// Target species name
+    target: String,
+    /// Source species name
+    source: String,
+    /// Interaction radius for this pair
+    #[arg(long)]
+    radius: Option<f32>,
+    /// Collision distance as a fraction of the radius for this pair
+    #[arg(long)]
+    collision_ratio: Option<f32>,
+    /// Force kernel for this pair
+    #[arg(long)]
+    kernel: Option<ForceKernelKind>,
+    /// Remove all overrides so the pair uses the global values again
+    #[arg(long)]
+    clear: bool,
+}
+
+/// Console command to reset interactions from CSV file
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "reset_interaction")]
+pub struct ResetInteractionCommand;
+
+/// Console command to save interactions and species properties to a CSV file
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "save_interaction")]
+pub struct SaveInteractionCommand {
+    /// File to write, defaults to the matrix file of the config
+    path: Option<String>,
+}
+
+/// Console command to set all interactions to random values
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "random_interaction")]
+pub struct RandomInteractionCommand;
+
+/// Handle the `interaction` console command
+///
+/// Sets the interaction force between two particle types.
+///
+/// Positive values cause attraction, negative values cause repulsion.
+#[allow(clippy::needless_pass_by_value)]
+pub fn interaction(
+    mut log: ConsoleCommand<InteractionCommand>,
+    mut interaction_table: ResMut<ParticleInteractionTable>,
+    registry: Res<SpeciesRegistry>,
+) {
+    if let Some(Ok(InteractionCommand {
+        target,
+        source,
+        value,
+    })) = log.take()
+    {
+        let (target, source) = match parse_pair(&registry, &target, &source) {
+            Ok(pair) => pair,
+            Err(e) => {
+                reply!(log, "{}", e);
+                return;
+            }
+        };
+        interaction_table.set_interaction(target, source, value);
+        reply!(
+            log,
+            "Set interaction {}[{}] <- {}[{}] = {:.1}",
+            registry.name(target),
+            target.index(),
+            registry.name(source),
+            source.index(),
+            value
+        );
+    }
+}
+
+/// Handle the `interaction_profile` console command
+///
+/// Sets the per-pair overrides that are given and prints the resulting
+/// profile. Unset fields fall back to the global configuration.
+#[allow(clippy::needless_pass_by_value)]
+pub fn interaction_profile(
+    mut log: ConsoleCommand<InteractionProfileCommand>,
+    mut interaction_table: ResMut<ParticleInteractionTable>,
+    registry: Res<SpeciesRegistry>,
+) {
+    if let Some(Ok(InteractionProfileCommand {
+        target,
+        source,
+        radius,
+        collision_ratio,
+        kernel,
+        clear,
+    })) = log.take()
+    {
+        let (target, source) = match parse_pair(&registry, &target, &source) {
+            Ok(pair) => pair,
+            Err(e) => {
+                reply!(log, "{}", e);
+                return;
+            }
+        };
+        let mut profile = interaction_table.get_profile(target, source);
+        if clear {
+            profile.r = None;
+            profile.collision_ratio = None;
+            profile.kernel = None;
+        }
+        profile.r = radius.or(profile.r);
+        profile.collision_ratio = collision_ratio.or(profile.collision_ratio);
+        profile.kernel = kernel.or(profile.kernel);
+        if let Err(e) = profile.validate() {
+            reply!(log, "{}", e);
+            return;
+        }
+        interaction_table.set_profile(target, source, profile);
+
+        let or_default = |value: Option<String>| value.unwrap_or_else(|| "default".to_string());
+        reply!(
+            log,
+            "Profile {} <- {}: strength {:.2}, radius {}, collision_ratio {}, kernel {}",
+            registry.name(target),
+            registry.name(source),
+            profile.strength,
+            or_default(profile.r.map(|r| format!("{r:.2}"))),
+            or_default(profile.collision_ratio.map(|b| format!("{b:.3}"))),
+            or_default(profile.kernel.map(|k| k.as_str().to_owned()))
+        );
+    }
+}
+
+/// Handle the `reset_interaction` console command
+///
+/// Resets all particle interactions to the values stored in the matrix CSV
+/// file named by the [`StartupConfig`].
+/// The species in the CSV header replace the registered species.
+#[allow(clippy::needless_pass_by_value)]
+pub fn reset_interaction(
+    mut log: ConsoleCommand<ResetInteractionCommand>,
+    mut interaction_table: ResMut<ParticleInteractionTable>,
+    mut registry: ResMut<SpeciesRegistry>,
+    startup: Res<StartupConfig>,
+) {
+    if matches!(log.take(), Some(Ok(ResetInteractionCommand))) {
+        let csv_path = startup.matrix.as_str();
+        match ParticleInteractionTable::from_csv_file(csv_path, &mut registry) {
+            Ok(loaded_table) => {
+                *interaction_table = loaded_table;
+                reply!(
+                    log,
+                    "Reset interactions and {} species from file: {}",
+                    registry.len(),
+                    csv_path
+                );
+            }
+            Err(e) => {
+                reply!(
+                    log,
+                    "Warning: Could not load {}, keeping current interactions",
+                    csv_path
+                );
+                reply!(log, "Error: {}", e);
+            }
+        }
+    }
+}
+
+/// Handle the `save_interaction` console command
+///
+/// Writes the interaction table, its per-pair overrides and the species
+/// properties in the format read by `reset_interaction`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn save_interaction(
+    mut log: ConsoleCommand<SaveInteractionCommand>,
+    interaction_table: Res<ParticleInteractionTable>,
+    registry: Res<SpeciesRegistry>,
+    startup: Res<StartupConfig>,
+) {
+    if let Some(Ok(SaveInteractionCommand { path })) = log.take() {
+        let csv_path = path.as_deref().unwrap_or(&startup.matrix);
+        match interaction_table.to_csv_file(csv_path, &registry) {
+            Ok(()) => reply!(log, "Saved interactions to file: {}", csv_path),
+            Err(e) => reply!(log, "Error: {}", e),
+        }
+    }
+}
+
+/// Handle the `random_interaction` console command
+///
+/// Sets all particle interactions to random values between -100.0 and 100.0.
+/// Values are drawn from the seeded [`SimRng`].
+#[allow(clippy::needless_pass_by_value)]
+pub fn random_interaction(
+    mut log: ConsoleCommand<RandomInteractionCommand>,
+    mut interaction_table: ResMut<ParticleInteractionTable>,
+    registry: Res<SpeciesRegistry>,
+    mut rng: ResMut<SimRng>,
+) {
+    if matches!(log.take(), Some(Ok(RandomInteractionCommand))) {
+        interaction_table.resize(registry.len());
+        for target in registry.types() {
+            for source in registry.types() {
+                let value = rng.random_range(-1.0..1.0);
+                interaction_table.set_interaction(target, source, value);
+            }
+        }
+        reply!(
+            log,
+            "Set all interactions to random values between -100.0 and 100.0"
+        );
+        interaction_table.print_table(&registry);
+    }
+}
@@ -0,0 +1,131 @@
+//! `membrane` console command
+//!
+//! Manages type-selective membranes.
+
+use crate::resources::{Membrane, Membranes, SpeciesRegistry, WallInteraction};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::{Parser, Subcommand};
+
+/// Subcommands for the `membrane` console command
+#[derive(Subcommand, Clone, PartialEq)]
+enum MembraneSubcommand {
+    /// List all membranes with their non-solid or attracting species
+    List,
+    /// Set how a membrane treats a species: solid, pass, solid:<s> or pass:<s>
+    ///
+    /// Creates the membrane if it does not exist. Positive strengths
+    /// attract the species to the membrane, negative ones repel it.
+    Set {
+        name: String,
+        species: String,
+        #[arg(allow_hyphen_values = true)]
+        rule: WallInteraction,
+    },
+    /// Set the range of the membrane forces; without a value it follows r
+    Range { name: String, range: Option<f32> },
+    /// Remove a membrane; obstacles using it become solid for all
+    Remove { name: String },
+    /// Save all membranes to a CSV file
+    Save {
+        #[arg(default_value = "membranes.csv")]
+        path: String,
+    },
+    /// Replace all membranes with those from a CSV file
+    Load {
+        #[arg(default_value = "membranes.csv")]
+        path: String,
+    },
+}
+
+/// Console command to manage type-selective membranes
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "membrane")]
+pub struct MembraneCommand {
+    #[command(subcommand)]
+    subcommand: MembraneSubcommand,
+}
+
+/// Formats a membrane for the console
+fn describe_membrane(membrane: &Membrane, registry: &SpeciesRegistry) -> String {
+    let range = membrane
+        .range
+        .map_or_else(|| "r".to_string(), |range| format!("{range:.0}"));
+    let rules: Vec<String> = membrane
+        .custom()
+        .map(|(ptype, rule)| format!("{}={}", registry.name(ptype), rule))
+        .collect();
+    let rules = if rules.is_empty() {
+        "solid for all".to_string()
+    } else {
+        rules.join(", ")
+    };
+    format!("{} (range {}): {}", membrane.name, range, rules)
+}
+
+/// Handle the `membrane` console command
+///
+/// Edits, saves and loads the [`Membranes`] that obstacles refer to by
+/// name.
+#[allow(clippy::needless_pass_by_value)]
+pub fn membrane(
+    mut log: ConsoleCommand<MembraneCommand>,
+    mut membranes: ResMut<Membranes>,
+    registry: Res<SpeciesRegistry>,
+) {
+    use std::fmt::Write;
+    if let Some(Ok(MembraneCommand { subcommand })) = log.take() {
+        match subcommand {
+            MembraneSubcommand::List => {
+                let mut output = format!("{} membranes:\n", membranes.len());
+                for membrane in membranes.iter() {
+                    writeln!(output, "  {}", describe_membrane(membrane, &registry)).unwrap();
+                }
+                reply!(log, "{}", output);
+            }
+            MembraneSubcommand::Set {
+                name,
+                species,
+                rule,
+            } => {
+                let ptype = match registry.parse(&species) {
+                    Ok(ptype) => ptype,
+                    Err(e) => {
+                        reply!(log, "{}", e);
+                        return;
+                    }
+                };
+                let membrane = membranes.get_or_insert(&name);
+                membrane.set(ptype, rule);
+                reply!(log, "{}", describe_membrane(membrane, &registry));
+            }
+            MembraneSubcommand::Range { name, range } => {
+                if range.is_some_and(|range| range <= 0.0) {
+                    reply!(log, "range must be positive");
+                    return;
+                }
+                let membrane = membranes.get_or_insert(&name);
+                membrane.range = range;
+                reply!(log, "{}", describe_membrane(membrane, &registry));
+            }
+            MembraneSubcommand::Remove { name } => {
+                if let Some(removed) = membranes.remove(&name) {
+                    reply!(log, "Removed membrane {}", removed.name);
+                } else {
+                    reply!(log, "no membrane named {}", name);
+                }
+            }
+            MembraneSubcommand::Save { path } => match membranes.to_csv_file(&path, &registry) {
+                Ok(()) => reply!(log, "Saved {} membranes to {}", membranes.len(), path),
+                Err(e) => reply!(log, "Failed to save membranes: {}", e),
+            },
+            MembraneSubcommand::Load { path } => match Membranes::from_csv_file(&path, &registry) {
+                Ok(loaded) => {
+                    *membranes = loaded;
+                    reply!(log, "Loaded {} membranes from {}", membranes.len(), path);
+                }
+                Err(e) => reply!(log, "Failed to load membranes: {}", e),
+            },
+        }
+    }
+}
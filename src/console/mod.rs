@@ -0,0 +1,42 @@
+//! Console module
+//!
+//! This module contains the console commands registered by the
+//! [`CommandPlugin`](crate::CommandPlugin), one file per command group.
+
+mod anneal;
+mod config;
+mod ecosystem;
+mod field;
+mod genome;
+mod interaction;
+mod membrane;
+mod obstacle;
+mod parse;
+mod print;
+mod reaction;
+mod respawn;
+mod set;
+mod snapshot;
+mod species;
+
+pub use anneal::{AnnealCommand, anneal};
+pub use config::{SaveConfigCommand, save_config};
+pub use ecosystem::{EcosystemCommand, ecosystem};
+pub use field::{FieldCommand, field};
+pub use genome::{GenomeCommand, genome};
+pub use interaction::{
+    InteractionCommand, InteractionProfileCommand, RandomInteractionCommand,
+    ResetInteractionCommand, SaveInteractionCommand, interaction, interaction_profile,
+    random_interaction, reset_interaction, save_interaction,
+};
+pub use membrane::{MembraneCommand, membrane};
+pub use obstacle::{ObstacleCommand, obstacle};
+pub use print::{PrintCommand, print};
+pub use reaction::{ReactionCommand, reaction};
+pub use respawn::{RespawnParticle, respawn_particle_console};
+pub use set::{SetCommand, set};
+pub use snapshot::{LoadSnapshotCommand, SaveSnapshotCommand, load_snapshot, save_snapshot};
+pub use species::{
+    AddSpeciesCommand, GenerateSpeciesCommand, ListSpeciesCommand, SpeciesCommand, add_species,
+    generate_species, list_species, species,
+};
@@ -0,0 +1,260 @@
+//! `obstacle` console command
+//!
+//! Manages static obstacles.
+
+use super::parse::parse_vec2;
+use crate::resources::{Obstacle, ObstacleShape, Obstacles};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::{Args, Parser, Subcommand};
+
+/// Surface parameters of an obstacle
+#[derive(Args, Clone, PartialEq)]
+struct ObstacleOptions {
+    /// Fraction of the normal velocity kept on impact (1 bounces fully)
+    #[arg(long)]
+    restitution: Option<f32>,
+    /// Fraction of the tangential velocity removed on impact, 0 to 1
+    #[arg(long)]
+    friction: Option<f32>,
+    /// Membrane deciding per species whether the obstacle is solid
+    #[arg(long)]
+    membrane: Option<String>,
+}
+
+impl ObstacleOptions {
+    /// Creates an obstacle with the given shape and options
+    ///
+    /// # Errors
+    /// Returns an error if restitution is negative or friction is outside
+    /// `[0, 1]`
+    fn build(&self, shape: ObstacleShape) -> Result<Obstacle, String> {
+        let mut obstacle = Obstacle::new(shape);
+        if let Some(restitution) = self.restitution {
+            if restitution < 0.0 {
+                return Err("restitution must not be negative".to_string());
+            }
+            obstacle.restitution = restitution;
+        }
+        if let Some(friction) = self.friction {
+            if !(0.0..=1.0).contains(&friction) {
+                return Err("friction must be between 0 and 1".to_string());
+            }
+            obstacle.friction = friction;
+        }
+        obstacle.membrane.clone_from(&self.membrane);
+        Ok(obstacle)
+    }
+}
+
+/// Subcommands for the `obstacle` console command
+#[derive(Subcommand, Clone, PartialEq)]
+enum ObstacleSubcommand {
+    /// Add a wall between two points given as x,y
+    Segment {
+        #[arg(value_parser = parse_vec2, allow_hyphen_values = true)]
+        from: Vec2,
+        #[arg(value_parser = parse_vec2, allow_hyphen_values = true)]
+        to: Vec2,
+        #[command(flatten)]
+        options: ObstacleOptions,
+    },
+    /// Add a solid disc
+    Circle {
+        /// Center as x,y
+        #[arg(value_parser = parse_vec2, allow_hyphen_values = true)]
+        center: Vec2,
+        radius: f32,
+        #[command(flatten)]
+        options: ObstacleOptions,
+    },
+    /// Add a solid rectangle
+    #[command(alias = "rect")]
+    Rectangle {
+        /// Center as x,y
+        #[arg(value_parser = parse_vec2, allow_hyphen_values = true)]
+        center: Vec2,
+        /// Width and height as w,h
+        #[arg(value_parser = parse_vec2)]
+        size: Vec2,
+        /// Counter-clockwise rotation in degrees
+        #[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+        angle: f32,
+        #[command(flatten)]
+        options: ObstacleOptions,
+    },
+    /// Add a solid polygon from three or more corners given as x,y
+    Polygon {
+        #[arg(value_parser = parse_vec2, num_args = 3.., required = true, allow_hyphen_values = true)]
+        points: Vec<Vec2>,
+        #[command(flatten)]
+        options: ObstacleOptions,
+    },
+    /// List all obstacles
+    List,
+    /// Remove the obstacle with the given index from `obstacle list`
+    Remove { index: usize },
+    /// Remove all obstacles
+    Clear,
+    /// Set the membrane of an obstacle; without a name it is solid for all
+    Membrane { index: usize, name: Option<String> },
+    /// Save all obstacles to a CSV file
+    Save {
+        #[arg(default_value = "obstacles.csv")]
+        path: String,
+    },
+    /// Replace all obstacles with those from a CSV file
+    Load {
+        #[arg(default_value = "obstacles.csv")]
+        path: String,
+    },
+}
+
+/// Console command to manage static obstacles
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "obstacle")]
+pub struct ObstacleCommand {
+    #[command(subcommand)]
+    subcommand: ObstacleSubcommand,
+}
+
+/// Formats an obstacle for the console
+fn describe_obstacle(obstacle: &Obstacle) -> String {
+    let shape = match &obstacle.shape {
+        ObstacleShape::Segment { a, b } => {
+            format!(
+                "segment ({:.0}, {:.0}) to ({:.0}, {:.0})",
+                a.x, a.y, b.x, b.y
+            )
+        }
+        ObstacleShape::Circle { center, radius } => {
+            format!(
+                "circle at ({:.0}, {:.0}), radius {radius:.0}",
+                center.x, center.y
+            )
+        }
+        ObstacleShape::Rectangle {
+            center,
+            half_size,
+            angle,
+        } => format!(
+            "rectangle at ({:.0}, {:.0}), size {:.0}x{:.0}, angle {:.0}°",
+            center.x,
+            center.y,
+            half_size.x * 2.0,
+            half_size.y * 2.0,
+            angle.to_degrees()
+        ),
+        ObstacleShape::Polygon { points } => format!("polygon with {} corners", points.len()),
+    };
+    format!(
+        "{shape}, restitution {:.2}, friction {:.2}, membrane {}",
+        obstacle.restitution,
+        obstacle.friction,
+        obstacle.membrane.as_deref().unwrap_or("none")
+    )
+}
+
+/// Handle the `obstacle` console command
+///
+/// Adds, lists, removes, saves and loads the static [`Obstacles`]
+/// particles collide with.
+pub fn obstacle(mut log: ConsoleCommand<ObstacleCommand>, mut obstacles: ResMut<Obstacles>) {
+    use std::fmt::Write;
+    if let Some(Ok(ObstacleCommand { subcommand })) = log.take() {
+        let built = match subcommand {
+            ObstacleSubcommand::Segment { from, to, options } => {
+                options.build(ObstacleShape::Segment { a: from, b: to })
+            }
+            ObstacleSubcommand::Circle {
+                center,
+                radius,
+                options,
+            } => {
+                if radius <= 0.0 {
+                    reply!(log, "radius must be positive");
+                    return;
+                }
+                options.build(ObstacleShape::Circle { center, radius })
+            }
+            ObstacleSubcommand::Rectangle {
+                center,
+                size,
+                angle,
+                options,
+            } => {
+                if size.min_element() <= 0.0 {
+                    reply!(log, "width and height must be positive");
+                    return;
+                }
+                options.build(ObstacleShape::Rectangle {
+                    center,
+                    half_size: size / 2.0,
+                    angle: angle.to_radians(),
+                })
+            }
+            ObstacleSubcommand::Polygon { points, options } => {
+                options.build(ObstacleShape::Polygon { points })
+            }
+            ObstacleSubcommand::List => {
+                let mut output = format!("{} obstacles:\n", obstacles.len());
+                for (index, obstacle) in obstacles.as_slice().iter().enumerate() {
+                    writeln!(output, "{index:>3} {}", describe_obstacle(obstacle)).unwrap();
+                }
+                reply!(log, "{}", output);
+                return;
+            }
+            ObstacleSubcommand::Remove { index } => {
+                if let Some(removed) = obstacles.remove(index) {
+                    reply!(log, "Removed {}", describe_obstacle(&removed));
+                } else {
+                    reply!(log, "no obstacle with index {}", index);
+                }
+                return;
+            }
+            ObstacleSubcommand::Clear => {
+                reply!(log, "Removed {} obstacles", obstacles.len());
+                obstacles.clear();
+                return;
+            }
+            ObstacleSubcommand::Membrane { index, name } => {
+                if let Some(obstacle) = obstacles.get_mut(index) {
+                    obstacle.membrane = name;
+                    reply!(
+                        log,
+                        "Set obstacle {} to {}",
+                        index,
+                        describe_obstacle(obstacle)
+                    );
+                } else {
+                    reply!(log, "no obstacle with index {}", index);
+                }
+                return;
+            }
+            ObstacleSubcommand::Save { path } => {
+                match obstacles.to_csv_file(&path) {
+                    Ok(()) => reply!(log, "Saved {} obstacles to {}", obstacles.len(), path),
+                    Err(e) => reply!(log, "Failed to save obstacles: {}", e),
+                }
+                return;
+            }
+            ObstacleSubcommand::Load { path } => {
+                match Obstacles::from_csv_file(&path) {
+                    Ok(loaded) => {
+                        *obstacles = loaded;
+                        reply!(log, "Loaded {} obstacles from {}", obstacles.len(), path);
+                    }
+                    Err(e) => reply!(log, "Failed to load obstacles: {}", e),
+                }
+                return;
+            }
+        };
+        match built {
+            Ok(built) => {
+                reply!(log, "Added {}", describe_obstacle(&built));
+                obstacles.add(built);
+            }
+            Err(e) => reply!(log, "{}", e),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Argument parsers shared by the console commands
+
+use crate::components::{ParticleType, ParticleTypeError};
+use crate::resources::SpeciesRegistry;
+use bevy::prelude::*;
+
+/// Parses a vector from `x,y` or `x,y,z`
+///
+/// # Errors
+/// Returns an error if there are not two or three numbers
+pub fn parse_vec3(value: &str) -> Result<Vec3, String> {
+    let parts = value
+        .split(',')
+        .map(|part| part.trim().parse::<f32>())
+        .collect::<Result<Vec<f32>, _>>()
+        .map_err(|e| e.to_string())?;
+    match parts[..] {
+        [x, y] => Ok(Vec3::new(x, y, 0.0)),
+        [x, y, z] => Ok(Vec3::new(x, y, z)),
+        _ => Err("expected x,y or x,y,z".to_string()),
+    }
+}
+
+/// Parses a point from `x,y`
+///
+/// # Errors
+/// Returns an error if there are not exactly two numbers
+pub fn parse_vec2(value: &str) -> Result<Vec2, String> {
+    if value.split(',').count() != 2 {
+        return Err("expected x,y".to_string());
+    }
+    parse_vec3(value).map(Vec3::truncate)
+}
+
+/// Resolves a target and source species name against the registry
+///
+/// # Errors
+/// Returns the error of the first name that is not registered
+pub fn parse_pair(
+    registry: &SpeciesRegistry,
+    target: &str,
+    source: &str,
+) -> Result<(ParticleType, ParticleType), ParticleTypeError> {
+    Ok((registry.parse(target)?, registry.parse(source)?))
+}
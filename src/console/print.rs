@@ -0,0 +1,226 @@
+//! `print` console command
+//!
+//! Shows configuration values and simulation metrics.
+
+use crate::components::{ParticleMarker, ParticleType, Velocity};
+use crate::resources::{
+    Annealing, ParticleConfig, ParticleInteractionTable, SimulationHealth, SpeciesRegistry,
+};
+use crate::simulation::kinetic_temperature;
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::{Parser, Subcommand};
+
+/// Subcommands for the `print` console command
+#[derive(Subcommand, Clone, PartialEq)]
+enum PrintSubcommand {
+    /// Print map boundary dimensions and modes
+    Boundary,
+    /// Print the spatial dimension
+    Dimension,
+    /// Print particle interaction table
+    Interaction,
+    /// Print interaction distance
+    R,
+    /// Print repel force magnitude for collisions
+    RepelForce,
+    /// Print force kernel and collision ratio
+    Kernel,
+    /// Print the temperature setpoint, the measured kinetic temperature and the damping
+    Temperature,
+    /// Print time step for particle updates
+    Dt,
+    /// Print the integration scheme
+    Integrator,
+    /// Print numerical health counters (coincident pairs, speed caps, repairs)
+    Health,
+    /// Print fixed timestep settings (tick rate, substeps, time scale)
+    Timestep,
+    /// Print the random seed
+    Seed,
+    /// Print all configuration values
+    Config,
+}
+
+/// Console command for printing current configuration values
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "print")]
+pub struct PrintCommand {
+    #[command(subcommand)]
+    subcommand: PrintSubcommand,
+}
+
+/// Handle the `print` console command
+///
+/// Displays the current value of the specified configuration parameter.
+#[allow(clippy::needless_pass_by_value, clippy::too_many_lines)]
+pub fn print(
+    mut log: ConsoleCommand<PrintCommand>,
+    config: Res<ParticleConfig>,
+    interaction_table: Res<ParticleInteractionTable>,
+    health: Res<SimulationHealth>,
+    registry: Res<SpeciesRegistry>,
+    annealing: Res<Annealing>,
+    particles: Query<(&ParticleType, &Velocity), With<ParticleMarker>>,
+) {
+    use std::fmt::Write;
+    if let Some(Ok(PrintCommand { subcommand })) = log.take() {
+        match subcommand {
+            PrintSubcommand::Boundary => {
+                reply!(
+                    log,
+                    "map width: {:.2}, height: {:.2}, depth: {:.2}, boundary mode x: {}, y: {}, z: {}",
+                    config.map_width,
+                    config.map_height,
+                    config.map_depth,
+                    config.boundary_x,
+                    config.boundary_y,
+                    config.boundary_z
+                );
+            }
+            PrintSubcommand::Dimension => {
+                reply!(log, "dimension: {}", config.dimension);
+            }
+            PrintSubcommand::Interaction => {
+                let types: Vec<ParticleType> = registry.types().collect();
+                let mut output = String::from("Particle Interaction Table:\n");
+                write!(output, "{:>8} ", "target\\source").unwrap();
+                for source_type in &types {
+                    write!(output, "{:>6} ", registry.name(*source_type)).unwrap();
+                }
+                output.push('\n');
+
+                for target_type in &types {
+                    write!(output, "{:>8} ", registry.name(*target_type)).unwrap();
+                    for source_type in &types {
+                        let strength =
+                            interaction_table.get_interaction(*target_type, *source_type);
+                        write!(output, "{strength:>6.1} ").unwrap();
+                    }
+                    output.push('\n');
+                }
+                reply!(log, "{}", output);
+            }
+            PrintSubcommand::R => {
+                reply!(log, "r: {:.2}", config.r);
+            }
+            PrintSubcommand::RepelForce => {
+                reply!(log, "repel_force: {:.2}", config.repel_force);
+            }
+            PrintSubcommand::Kernel => {
+                reply!(
+                    log,
+                    "kernel: {}, collision_ratio: {:.3}",
+                    config.kernel,
+                    config.collision_ratio
+                );
+            }
+            PrintSubcommand::Temperature => {
+                let measured = kinetic_temperature(
+                    particles.iter().map(|(particle_type, velocity)| {
+                        (registry.properties(*particle_type).mass, velocity.value)
+                    }),
+                    config.degrees_of_freedom(),
+                );
+                reply!(
+                    log,
+                    "temperature: {:.3} (measured {:.3}), dt_half: {:.3}",
+                    config.temperature,
+                    measured,
+                    config.dt_half
+                );
+                if annealing.is_active() {
+                    reply!(
+                        log,
+                        "annealing {:.3} -> {:.3}: {:.0}% of {:.1}",
+                        annealing.from,
+                        annealing.to,
+                        annealing.progress() * 100.0,
+                        annealing.duration
+                    );
+                }
+            }
+            PrintSubcommand::Dt => {
+                reply!(log, "dt: {:.3}", config.dt);
+            }
+            PrintSubcommand::Integrator => {
+                reply!(log, "integrator: {}", config.integrator);
+            }
+            PrintSubcommand::Health => {
+                reply!(
+                    log,
+                    "steps: {}, coincident pairs: {}, speed caps: {}, repairs: {} ({} last step), max_speed: {:.2}, repair_mode: {}",
+                    health.steps,
+                    health.coincident_pairs,
+                    health.speed_caps,
+                    health.repairs,
+                    health.last_step_repairs,
+                    config.max_speed,
+                    config.repair_mode
+                );
+            }
+            PrintSubcommand::Timestep => {
+                reply!(
+                    log,
+                    "tick_rate: {:.1}, substeps: {}, time_scale: {:.3}",
+                    config.tick_rate,
+                    config.substeps,
+                    config.time_scale
+                );
+            }
+            PrintSubcommand::Seed => {
+                reply!(log, "seed: {}", config.seed);
+            }
+            PrintSubcommand::Config => {
+                reply!(
+                    log,
+                    "ParticleConfig:\n\
+                     - init_particle_num: {}\n\
+                     - dimension: {}\n\
+                     - map_width: {:.2}\n\
+                     - map_height: {:.2}\n\
+                     - map_depth: {:.2}\n\
+                     - boundary_x: {}\n\
+                     - boundary_y: {}\n\
+                     - boundary_z: {}\n\
+                     - r: {:.2}\n\
+                     - repel_force: {:.2}\n\
+                     - collision_ratio: {:.3}\n\
+                     - kernel: {}\n\
+                     - dt_half: {:.3}\n\
+                     - temperature: {:.3}\n\
+                     - dt: {:.3}\n\
+                     - integrator: {}\n\
+                     - max_speed: {:.2}\n\
+                     - repair_mode: {}\n\
+                     - tick_rate: {:.1}\n\
+                     - substeps: {}\n\
+                     - time_scale: {:.3}\n\
+                     - seed: {}",
+                    config.init_particle_num,
+                    config.dimension,
+                    config.map_width,
+                    config.map_height,
+                    config.map_depth,
+                    config.boundary_x,
+                    config.boundary_y,
+                    config.boundary_z,
+                    config.r,
+                    config.repel_force,
+                    config.collision_ratio,
+                    config.kernel,
+                    config.dt_half,
+                    config.temperature,
+                    config.dt,
+                    config.integrator,
+                    config.max_speed,
+                    config.repair_mode,
+                    config.tick_rate,
+                    config.substeps,
+                    config.time_scale,
+                    config.seed
+                );
+            }
+        }
+    }
+}
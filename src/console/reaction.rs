@@ -0,0 +1,145 @@
+//! `reaction` console command
+//!
+//! Manages species conversion rules.
+
+use crate::resources::{
+    ReactionRule, ReactionStats, ReactionTable, SpeciesRegistry, StartupConfig,
+};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::{Parser, Subcommand};
+
+/// Subcommands for the `reaction` console command
+#[derive(Subcommand, Clone, PartialEq)]
+enum ReactionSubcommand {
+    /// Add a rule like "A + B -> A + A" (contact), "A + C -> B + C" (catalyst) or "A -> B" (decay)
+    Add {
+        /// Reaction equation, quoted if it contains spaces
+        equation: String,
+        /// Chance to fire per step and reactant, 0 to 1
+        probability: f32,
+        /// Largest reactant-partner distance (default: contact distance)
+        #[arg(long)]
+        distance: Option<f32>,
+    },
+    /// List all rules with their firing counts
+    List,
+    /// Remove the rule with the given index from `reaction list`
+    Remove { index: usize },
+    /// Remove all rules
+    Clear,
+    /// Save all rules to a CSV file
+    Save {
+        /// File to write, defaults to the reactions file of the config
+        path: Option<String>,
+    },
+    /// Replace all rules with those from a CSV file
+    Load {
+        /// File to read, defaults to the reactions file of the config
+        path: Option<String>,
+    },
+}
+
+/// Console command to manage species conversion rules
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "reaction")]
+pub struct ReactionCommand {
+    #[command(subcommand)]
+    subcommand: ReactionSubcommand,
+}
+
+/// Formats a reaction rule for the console
+fn describe_reaction(rule: &ReactionRule, registry: &SpeciesRegistry) -> String {
+    let distance = if rule.is_spontaneous() {
+        String::new()
+    } else {
+        rule.distance.map_or_else(
+            || ", contact distance".to_string(),
+            |distance| format!(", distance {distance:.1}"),
+        )
+    };
+    let kind = if rule.is_spontaneous() {
+        "decay"
+    } else if rule.is_catalyzed() {
+        "catalyzed"
+    } else {
+        "contact"
+    };
+    format!(
+        "{} ({kind}), p {}{distance}",
+        rule.equation(registry),
+        rule.probability
+    )
+}
+
+/// Handle the `reaction` console command
+///
+/// Adds, lists, removes, saves and loads the rules of the
+/// [`ReactionTable`]. `reaction list` also shows the [`ReactionStats`].
+#[allow(clippy::needless_pass_by_value)]
+pub fn reaction(
+    mut log: ConsoleCommand<ReactionCommand>,
+    mut reactions: ResMut<ReactionTable>,
+    stats: Res<ReactionStats>,
+    registry: Res<SpeciesRegistry>,
+    startup: Res<StartupConfig>,
+) {
+    use std::fmt::Write;
+    if let Some(Ok(ReactionCommand { subcommand })) = log.take() {
+        match subcommand {
+            ReactionSubcommand::Add {
+                equation,
+                probability,
+                distance,
+            } => match ReactionRule::parse(&equation, distance, probability, &registry) {
+                Ok(rule) => {
+                    reactions.add(rule);
+                    reply!(log, "Added {}", describe_reaction(&rule, &registry));
+                }
+                Err(e) => reply!(log, "{}", e),
+            },
+            ReactionSubcommand::List => {
+                let mut output = format!("{} reactions:\n", reactions.len());
+                for (index, rule) in reactions.rules().iter().enumerate() {
+                    writeln!(
+                        output,
+                        "{index:>3} {}, fired {} ({} last step)",
+                        describe_reaction(rule, &registry),
+                        stats.fired(index),
+                        stats.last_step(index)
+                    )
+                    .unwrap();
+                }
+                reply!(log, "{}", output);
+            }
+            ReactionSubcommand::Remove { index } => {
+                if let Some(removed) = reactions.remove(index) {
+                    reply!(log, "Removed {}", describe_reaction(&removed, &registry));
+                } else {
+                    reply!(log, "no reaction with index {}", index);
+                }
+            }
+            ReactionSubcommand::Clear => {
+                reply!(log, "Removed {} reactions", reactions.len());
+                reactions.clear();
+            }
+            ReactionSubcommand::Save { path } => {
+                let path = path.as_deref().unwrap_or(&startup.reactions);
+                match reactions.to_csv_file(path, &registry) {
+                    Ok(()) => reply!(log, "Saved {} reactions to {}", reactions.len(), path),
+                    Err(e) => reply!(log, "Failed to save reactions: {}", e),
+                }
+            }
+            ReactionSubcommand::Load { path } => {
+                let path = path.as_deref().unwrap_or(&startup.reactions);
+                match ReactionTable::from_csv_file(path, &registry) {
+                    Ok(loaded) => {
+                        *reactions = loaded;
+                        reply!(log, "Loaded {} reactions from {}", reactions.len(), path);
+                    }
+                    Err(e) => reply!(log, "Failed to load reactions: {}", e),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! `respawn_particle` console command
+
+use crate::components::{Dormant, ParticleMarker};
+use crate::resources::{ParticleConfig, SimRng, SpeciesRegistry};
+use crate::systems::{clean_particle, spawn_particle};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::Parser;
+
+/// Console command to respawn all particles
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "respawn_particle")]
+pub struct RespawnParticle;
+
+/// Handle the `respawn_particle` console command
+///
+/// Removes all existing particles and spawns a new set according to the
+/// current configuration.
+#[allow(clippy::type_complexity)]
+pub fn respawn_particle_console(
+    mut log: ConsoleCommand<RespawnParticle>,
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<ParticleMarker>, With<Dormant>)>>,
+    config: Res<ParticleConfig>,
+    registry: Res<SpeciesRegistry>,
+    rng: ResMut<SimRng>,
+) {
+    if matches!(log.take(), Some(Ok(RespawnParticle))) {
+        clean_particle(commands.reborrow(), query);
+        spawn_particle(commands, config, registry, rng);
+        reply!(log, "Respawned all particles");
+    }
+}
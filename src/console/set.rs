@@ -0,0 +1,198 @@
+//! `set` console command
+//!
+//! Changes simulation parameters of the running simulation.
+
+use crate::resources::{
+    Annealing, BoundaryMode, Dimension, ForceKernelKind, Integrator, ParticleConfig, RepairMode,
+    SimRng,
+};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::{Parser, Subcommand};
+
+/// Subcommands for the `set` console command
+#[derive(Subcommand, Clone, PartialEq)]
+enum SetSubcommand {
+    /// Set map boundary dimensions (depth is used in 3D mode)
+    Boundary {
+        width: f32,
+        height: f32,
+        depth: Option<f32>,
+    },
+    /// Set boundary handling mode (reflect, wrap, clamp, absorb)
+    ///
+    /// A second mode applies to the y axis only, allowing mixed setups.
+    /// A third mode applies to the z axis and defaults to the first one.
+    BoundaryMode {
+        mode: BoundaryMode,
+        y_mode: Option<BoundaryMode>,
+        z_mode: Option<BoundaryMode>,
+    },
+    /// Set the spatial dimension (2d, 3d); respawn particles to apply
+    Dimension { dimension: Dimension },
+    /// Set interaction distance
+    R { value: f32 },
+    /// Set the repel force magnitude for collision
+    RepelForce { value: f32 },
+    /// Set the collision distance as a fraction of r
+    CollisionRatio { value: f32 },
+    /// Set the force kernel (classic, smooth, lennard-jones, gaussian)
+    Kernel { kernel: ForceKernelKind },
+    /// Set half life period of velocity
+    DTHalf { value: f32 },
+    /// Set the heat bath temperature (0 disables thermal noise); stops annealing
+    Temperature { value: f32 },
+    /// Set the time step for particle updates
+    Dt { value: f32 },
+    /// Set the integration scheme (euler, verlet, rk4)
+    Integrator { integrator: Integrator },
+    /// Set the maximum particle speed (inf disables the cap)
+    MaxSpeed { value: f32 },
+    /// Set the repair for non-finite particles (quarantine, respawn)
+    RepairMode { mode: RepairMode },
+    /// Set the number of physics ticks per second
+    TickRate { value: f64 },
+    /// Set the number of substeps per physics tick
+    Substeps { value: u32 },
+    /// Set the simulation speed multiplier
+    TimeScale { value: f32 },
+    /// Set the initial number of particles to spawn
+    InitParticleNum { value: usize },
+    /// Set the seed for all random operations
+    Seed { value: u64 },
+}
+
+/// Console command for setting simulation parameters
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "set")]
+pub struct SetCommand {
+    #[command(subcommand)]
+    subcommand: SetSubcommand,
+}
+
+/// Handle the `set` console command
+///
+/// Updates particle configuration with the specified parameter value.
+/// The change is applied to a copy that must pass
+/// [`ParticleConfig::validate`], the same check config files go
+/// through, so invalid values leave the running simulation untouched.
+/// Changes take effect immediately in the running simulation.
+#[allow(clippy::too_many_lines)]
+pub fn set(
+    mut log: ConsoleCommand<SetCommand>,
+    mut config: ResMut<ParticleConfig>,
+    mut rng: ResMut<SimRng>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut annealing: ResMut<Annealing>,
+) {
+    if let Some(Ok(SetCommand { subcommand })) = log.take() {
+        let mut candidate = config.clone();
+        let message = match &subcommand {
+            SetSubcommand::Boundary {
+                width,
+                height,
+                depth,
+            } => {
+                candidate.map_width = *width;
+                candidate.map_height = *height;
+                if let Some(depth) = depth {
+                    candidate.map_depth = *depth;
+                }
+                format!(
+                    "set map width: {:.2}, height: {:.2}, depth: {:.2} successfully",
+                    width, height, candidate.map_depth
+                )
+            }
+            SetSubcommand::BoundaryMode {
+                mode,
+                y_mode,
+                z_mode,
+            } => {
+                candidate.boundary_x = *mode;
+                candidate.boundary_y = y_mode.unwrap_or(*mode);
+                candidate.boundary_z = z_mode.unwrap_or(*mode);
+                format!(
+                    "set boundary mode x: {}, y: {}, z: {} successfully",
+                    candidate.boundary_x, candidate.boundary_y, candidate.boundary_z
+                )
+            }
+            SetSubcommand::Dimension { dimension } => {
+                candidate.dimension = *dimension;
+                format!("set dimension to {dimension} successfully, respawn particles to apply")
+            }
+            SetSubcommand::R { value } => {
+                candidate.r = *value;
+                format!("set r to {value:.2} successfully")
+            }
+            SetSubcommand::RepelForce { value } => {
+                candidate.repel_force = *value;
+                format!("set repel_force to {value:.2} successfully")
+            }
+            SetSubcommand::CollisionRatio { value } => {
+                candidate.collision_ratio = *value;
+                format!("set collision_ratio to {value:.3} successfully")
+            }
+            SetSubcommand::Kernel { kernel } => {
+                candidate.kernel = *kernel;
+                format!("set kernel to {kernel} successfully")
+            }
+            SetSubcommand::DTHalf { value } => {
+                candidate.dt_half = *value;
+                format!("set dt_half to {value:.3} successfully")
+            }
+            SetSubcommand::Temperature { value } => {
+                candidate.temperature = *value;
+                format!("set temperature to {value:.3} successfully")
+            }
+            SetSubcommand::Dt { value } => {
+                candidate.dt = *value;
+                format!("set dt to {value:.3} successfully")
+            }
+            SetSubcommand::Integrator { integrator } => {
+                candidate.integrator = *integrator;
+                format!("set integrator to {integrator} successfully")
+            }
+            SetSubcommand::MaxSpeed { value } => {
+                candidate.max_speed = *value;
+                format!("set max_speed to {value:.2} successfully")
+            }
+            SetSubcommand::RepairMode { mode } => {
+                candidate.repair_mode = *mode;
+                format!("set repair_mode to {mode} successfully")
+            }
+            SetSubcommand::TickRate { value } => {
+                candidate.tick_rate = *value;
+                format!("set tick_rate to {value:.1} successfully")
+            }
+            SetSubcommand::Substeps { value } => {
+                candidate.substeps = *value;
+                format!("set substeps to {value} successfully")
+            }
+            SetSubcommand::TimeScale { value } => {
+                candidate.time_scale = *value;
+                format!("set time_scale to {value:.3} successfully")
+            }
+            SetSubcommand::InitParticleNum { value } => {
+                candidate.init_particle_num = *value;
+                format!("set init_particle_num to {value} successfully")
+            }
+            SetSubcommand::Seed { value } => {
+                candidate.seed = *value;
+                format!("set seed to {value} successfully")
+            }
+        };
+
+        if let Err(e) = candidate.validate() {
+            reply!(log, "{}", e);
+            return;
+        }
+        match subcommand {
+            SetSubcommand::Temperature { .. } => annealing.stop(),
+            SetSubcommand::TickRate { value } => fixed_time.set_timestep_hz(value),
+            SetSubcommand::Seed { value } => rng.reseed(value),
+            _ => {}
+        }
+        *config = candidate;
+        reply!(log, "{}", message);
+    }
+}
@@ -0,0 +1,73 @@
+//! Snapshot console commands
+//!
+//! Saves and loads snapshot files.
+
+use crate::snapshot::{Snapshot, SnapshotFormat};
+use crate::systems::{SnapshotSource, SnapshotTarget};
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::Parser;
+
+/// Console command to save the particles and simulation state to a snapshot file
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "save_snapshot")]
+pub struct SaveSnapshotCommand {
+    /// File to write
+    path: String,
+    /// File format (ron, binary), defaults to binary for `.bin` and `.snap` files and RON otherwise
+    #[arg(long)]
+    format: Option<SnapshotFormat>,
+}
+
+/// Console command to replace the particles and simulation state with a snapshot file
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "load_snapshot")]
+pub struct LoadSnapshotCommand {
+    /// File to read, in either format
+    path: String,
+}
+
+/// Handle the `save_snapshot` console command
+///
+/// Writes the snapshot captured by [`SnapshotSource::capture`]: every
+/// particle, the configuration, species, interaction table, [`SimRng`](crate::resources::SimRng)
+/// state, physics state and camera.
+#[allow(clippy::needless_pass_by_value)]
+pub fn save_snapshot(mut log: ConsoleCommand<SaveSnapshotCommand>, source: SnapshotSource) {
+    if let Some(Ok(SaveSnapshotCommand { path, format })) = log.take() {
+        let format = format.unwrap_or_else(|| SnapshotFormat::from_path(&path));
+        let snapshot = source.capture();
+        match snapshot.to_file(&path, format) {
+            Ok(()) => reply!(
+                log,
+                "Saved {} particles to {} ({})",
+                snapshot.particles.len(),
+                path,
+                format
+            ),
+            Err(e) => reply!(log, "Error: {}", e),
+        }
+    }
+}
+
+/// Handle the `load_snapshot` console command
+///
+/// Replaces all particles with the ones in the snapshot and restores the
+/// configuration, species, interaction table, [`SimRng`](crate::resources::SimRng) and physics
+/// state and camera without restarting the app (see [`SnapshotTarget::apply`]).
+pub fn load_snapshot(mut log: ConsoleCommand<LoadSnapshotCommand>, mut target: SnapshotTarget) {
+    if let Some(Ok(LoadSnapshotCommand { path })) = log.take() {
+        match Snapshot::from_file(&path) {
+            Ok(snapshot) => {
+                target.apply(&snapshot);
+                reply!(
+                    log,
+                    "Loaded {} particles and {} species from {}",
+                    snapshot.particles.len(),
+                    snapshot.species.len(),
+                    path
+                );
+            }
+            Err(e) => reply!(log, "Error: {}", e),
+        }
+    }
+}
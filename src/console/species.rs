@@ -0,0 +1,194 @@
+//! Species console commands
+//!
+//! Lists, adds and generates species and shows or sets their
+//! physical properties.
+
+use crate::resources::{
+    ParticleInteractionTable, SpeciesProperties, SpeciesProperty, SpeciesRegistry,
+};
+use bevy::prelude::*;
+use bevy_console::{ConsoleCommand, clap, reply};
+use clap::Parser;
+
+/// Console command to list all registered species
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "list_species")]
+pub struct ListSpeciesCommand;
+
+/// Console command to register a new species
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "add_species")]
+pub struct AddSpeciesCommand {
+    /// Name of the new species
+    name: String,
+    /// Render color as a hex string (e.g. `#ff8800`)
+    #[arg(long)]
+    color: Option<String>,
+}
+
+/// Console command to replace all species with a generated set
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "generate_species")]
+pub struct GenerateSpeciesCommand {
+    /// Number of species to generate
+    count: usize,
+}
+
+/// Console command to show or set the physical properties of a species
+///
+/// Without a property all properties are printed, without a value only
+/// the given one.
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "species")]
+#[allow(clippy::doc_markdown)]
+pub struct SpeciesCommand {
+    /// Species name
+    name: String,
+    /// Property (mass, dt_half, max_speed, collision_radius, size)
+    property: Option<SpeciesProperty>,
+    /// New positive value, or `default` to restore the default
+    value: Option<String>,
+}
+
+/// Handle the `list_species` console command
+///
+/// Prints the index, name and color of every registered species.
+#[allow(clippy::needless_pass_by_value)]
+pub fn list_species(mut log: ConsoleCommand<ListSpeciesCommand>, registry: Res<SpeciesRegistry>) {
+    use std::fmt::Write;
+    if matches!(log.take(), Some(Ok(ListSpeciesCommand))) {
+        let mut output = format!("{} species:\n", registry.len());
+        for particle_type in registry.types() {
+            writeln!(
+                output,
+                "{:>3} {:<10} {}",
+                particle_type.index(),
+                registry.name(particle_type),
+                registry.color(particle_type).to_srgba().to_hex()
+            )
+            .unwrap();
+        }
+        reply!(log, "{}", output);
+    }
+}
+
+/// Handle the `add_species` console command
+///
+/// Registers a new species and grows the interaction table to match.
+/// The new species interacts with nothing until interactions are set.
+pub fn add_species(
+    mut log: ConsoleCommand<AddSpeciesCommand>,
+    mut registry: ResMut<SpeciesRegistry>,
+    mut interaction_table: ResMut<ParticleInteractionTable>,
+) {
+    if let Some(Ok(AddSpeciesCommand { name, color })) = log.take() {
+        let name = name.trim();
+        if name.is_empty() {
+            reply!(log, "species name must not be empty");
+            return;
+        }
+        if registry.find(name).is_some() {
+            reply!(log, "species {} already exists", name);
+            return;
+        }
+        let color = match color.map(|hex| Srgba::hex(&hex)).transpose() {
+            Ok(color) => color.map(Color::Srgba),
+            Err(e) => {
+                reply!(log, "invalid color: {}", e);
+                return;
+            }
+        };
+        let particle_type = registry.add(name, color);
+        interaction_table.resize(registry.len());
+        reply!(
+            log,
+            "Added species {}[{}]",
+            registry.name(particle_type),
+            particle_type.index()
+        );
+    }
+}
+
+/// Handle the `generate_species` console command
+///
+/// Replaces the registry with `count` generated species and resizes the
+/// interaction table, keeping the values of pairs that still exist.
+/// Respawn particles to use the new species.
+pub fn generate_species(
+    mut log: ConsoleCommand<GenerateSpeciesCommand>,
+    mut registry: ResMut<SpeciesRegistry>,
+    mut interaction_table: ResMut<ParticleInteractionTable>,
+) {
+    if let Some(Ok(GenerateSpeciesCommand { count })) = log.take() {
+        *registry = SpeciesRegistry::generate(count);
+        interaction_table.resize(count);
+        reply!(
+            log,
+            "Generated {} species, respawn particles to apply",
+            count
+        );
+    }
+}
+
+/// Formats one species property, unset optional values read `default`
+fn format_property(properties: &SpeciesProperties, property: SpeciesProperty) -> String {
+    properties.get(property).map_or_else(
+        || format!("{property} default"),
+        |value| format!("{property} {value:.3}"),
+    )
+}
+
+/// Handle the `species` console command
+///
+/// Sets a property of a species if a value is given and prints the
+/// result. Changed sizes apply to particles spawned afterwards.
+pub fn species(mut log: ConsoleCommand<SpeciesCommand>, mut registry: ResMut<SpeciesRegistry>) {
+    if let Some(Ok(SpeciesCommand {
+        name,
+        property,
+        value,
+    })) = log.take()
+    {
+        let particle_type = match registry.parse(&name) {
+            Ok(particle_type) => particle_type,
+            Err(e) => {
+                reply!(log, "{}", e);
+                return;
+            }
+        };
+        let Some(entry) = registry.get_mut(particle_type) else {
+            return;
+        };
+
+        let Some(property) = property else {
+            let output = SpeciesProperty::all()
+                .iter()
+                .map(|property| format_property(&entry.properties, *property))
+                .collect::<Vec<_>>()
+                .join(", ");
+            reply!(log, "{}: {}", entry.name, output);
+            return;
+        };
+
+        if let Some(value) = value {
+            let value = if value.eq_ignore_ascii_case("default") {
+                None
+            } else {
+                match value.parse::<f32>() {
+                    Ok(value) if property.accepts(value) => Some(value),
+                    _ => {
+                        reply!(log, "{} must be a positive number or default", property);
+                        return;
+                    }
+                }
+            };
+            entry.properties.set(property, value);
+        }
+        reply!(
+            log,
+            "{}: {}",
+            entry.name,
+            format_property(&entry.properties, property)
+        );
+    }
+}
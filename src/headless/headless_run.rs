@@ -0,0 +1,161 @@
+//! Headless simulation run
+//!
+//! Runs the [`SimulationPlugin`] in an [`App`] with only the
+//! [`MinimalPlugins`] and writes its state as [`Snapshot`] files. Ticks
+//! run the `FixedUpdate` schedule directly instead of waiting for the
+//! clock, so each one executes the same `anneal_temperature` and
+//! `update_particle` systems as a fixed tick of the windowed app.
+
+use crate::SimulationPlugin;
+use crate::components::ParticleMarker;
+use crate::resources::{ParticleConfig, SpeciesRegistry, StartupConfig};
+use crate::snapshot::{Snapshot, SnapshotFormat};
+use crate::systems::{SnapshotSource, SnapshotTarget};
+use bevy::MinimalPlugins;
+use bevy::app::{App, FixedUpdate};
+use bevy::ecs::query::With;
+use bevy::ecs::system::In;
+use std::error::Error;
+use std::path::Path;
+
+/// Headless simulation run
+///
+/// One [`step`](Self::step) runs `FixedUpdate` once, which matches one
+/// fixed tick of the app: `config.substeps` substeps of
+/// `config.substep_dt()` each.
+pub struct HeadlessRun {
+    app: App,
+    steps: u64,
+}
+
+impl HeadlessRun {
+    /// Creates a run from the startup settings
+    ///
+    /// Starts the app, whose `Startup` systems load the species and
+    /// interactions from `startup.matrix` (keeping `registry` and default
+    /// interactions if it cannot be read) and the reaction rules from
+    /// `startup.reactions`, and spawn the initial particles. If
+    /// `startup.snapshot` is set, the snapshot then replaces them and the
+    /// run continues its generators and step count. Physics runs even if
+    /// `startup.paused` is set.
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot cannot be loaded
+    pub fn load(
+        startup: &StartupConfig,
+        config: ParticleConfig,
+        registry: SpeciesRegistry,
+    ) -> Result<Self, Box<dyn Error>> {
+        let snapshot = startup
+            .snapshot
+            .as_deref()
+            .map(Snapshot::from_file)
+            .transpose()?;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            SimulationPlugin {
+                config,
+                species: registry,
+                startup: StartupConfig {
+                    paused: false,
+                    snapshot: None,
+                    ..startup.clone()
+                },
+            },
+        ));
+        app.finish();
+        app.cleanup();
+        // The first update runs the startup schedules; its clock advances
+        // by zero, so no fixed tick runs yet.
+        app.update();
+
+        if let Some(snapshot) = snapshot {
+            app.world_mut()
+                .run_system_cached_with(apply_snapshot, snapshot)?;
+        }
+        Ok(Self { app, steps: 0 })
+    }
+
+    /// Returns the number of living particles
+    pub fn particle_count(&mut self) -> usize {
+        let world = self.app.world_mut();
+        world
+            .query_filtered::<(), With<ParticleMarker>>()
+            .iter(world)
+            .count()
+    }
+
+    /// Returns the number of steps run so far
+    #[must_use]
+    pub const fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Advances the simulation by one fixed tick
+    pub fn step(&mut self) {
+        self.app.world_mut().run_schedule(FixedUpdate);
+        self.steps += 1;
+    }
+
+    /// Returns a snapshot of the current state, without camera
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot system cannot run
+    pub fn snapshot(&mut self) -> Result<Snapshot, Box<dyn Error>> {
+        Ok(self.app.world_mut().run_system_cached(capture_snapshot)?)
+    }
+
+    /// Runs `steps` steps and writes the final snapshot to `output`
+    ///
+    /// With `every`, a snapshot is also written after every `every`
+    /// steps, next to `output` with the step number appended to its name
+    /// (`final.ron` becomes `final_000100.ron`).
+    ///
+    /// # Errors
+    /// Returns an error if a snapshot cannot be written
+    pub fn run(
+        &mut self,
+        steps: u64,
+        every: Option<u64>,
+        output: &str,
+        format: SnapshotFormat,
+    ) -> Result<(), Box<dyn Error>> {
+        let every = every.filter(|every| *every > 0);
+        for _ in 0..steps {
+            self.step();
+            if every.is_some_and(|every| self.steps.is_multiple_of(every)) {
+                self.snapshot()?
+                    .to_file(&periodic_path(output, self.steps), format)?;
+            }
+        }
+        self.snapshot()?.to_file(output, format)
+    }
+}
+
+/// Replaces the spawned particles and state with a snapshot
+#[allow(clippy::needless_pass_by_value)]
+fn apply_snapshot(In(snapshot): In<Snapshot>, mut target: SnapshotTarget) {
+    target.apply(&snapshot);
+}
+
+/// Captures the current particles and state
+#[allow(clippy::needless_pass_by_value)]
+fn capture_snapshot(source: SnapshotSource) -> Snapshot {
+    source.capture()
+}
+
+/// Returns the path of the periodic snapshot after `step` steps
+fn periodic_path(output: &str, step: u64) -> String {
+    let path = Path::new(output);
+    let base = path.file_stem().map_or_else(
+        || "snapshot".into(),
+        |file_stem| file_stem.to_string_lossy(),
+    );
+    let name = path.extension().map_or_else(
+        || format!("{base}_{step:06}"),
+        |extension| format!("{base}_{step:06}.{}", extension.to_string_lossy()),
+    );
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
@@ -0,0 +1,8 @@
+//! Headless module
+//!
+//! Runs the simulation in an `App` without a window, renderer or
+//! console, for batch runs on machines without a GPU or display.
+
+mod headless_run;
+
+pub use headless_run::HeadlessRun;
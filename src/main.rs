@@ -3,15 +3,137 @@ use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy::prelude::*;
 use bevy::sprite_render::Wireframe2dPlugin;
 use bevy_console::{ConsoleConfiguration, ConsolePlugin};
+use clap::{Args, Parser, Subcommand};
 use particle_life::{
-    CameraMovePlugin, CommandPlugin, InputFocusPlugin, ParticlePlugin, resources::*,
+    CameraMovePlugin, CommandPlugin, InputFocusPlugin, ParticleInputPlugin, ParticleRenderPlugin,
+    SimulationPlugin,
+    config::{CONFIG_PATH_ENV, ConfigFile},
+    headless::HeadlessRun,
+    snapshot::SnapshotFormat,
 };
 
+/// Particle life simulation
+///
+/// Settings are read from the config file first; the flags below
+/// override them.
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Cli {
+    /// Config file to read instead of particle_life.toml (also set by PARTICLE_LIFE_CONFIG)
+    #[arg(long, value_name = "TOML", global = true)]
+    config: Option<String>,
+    /// Interaction matrix CSV file
+    #[arg(long, value_name = "CSV", global = true)]
+    matrix: Option<String>,
+    /// Reaction rules CSV file
+    #[arg(long, value_name = "CSV", global = true)]
+    reactions: Option<String>,
+    /// Seed for spawning and all random operations
+    #[arg(long, global = true)]
+    seed: Option<u64>,
+    /// Number of particles to spawn
+    #[arg(long, global = true)]
+    particles: Option<usize>,
+    /// Map size as WIDTHxHEIGHT or WIDTHxHEIGHTxDEPTH
+    #[arg(long, value_name = "WxH", value_parser = parse_boundary, global = true)]
+    boundary: Option<(f32, f32, Option<f32>)>,
+    /// Start with physics updates paused (toggle with T)
+    #[arg(long)]
+    paused: bool,
+    /// Snapshot file to load once the app has started
+    #[arg(long, value_name = "FILE", global = true)]
+    snapshot: Option<String>,
+    /// Run without a window instead of opening the app
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Subcommands of the command line
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the physics without window, renderer or console and write snapshots
+    Simulate(SimulateArgs),
+}
+
+/// Arguments of the `simulate` subcommand
+#[derive(Args, Debug)]
+struct SimulateArgs {
+    /// Number of fixed ticks to simulate
+    #[arg(long)]
+    steps: u64,
+    /// Also write a snapshot every N ticks, named after the output file
+    #[arg(long, value_name = "N")]
+    every: Option<u64>,
+    /// File to write the final snapshot to
+    #[arg(long, value_name = "FILE", default_value = "final.ron")]
+    output: String,
+    /// Snapshot format: ron or binary (default: from the file extension)
+    #[arg(long)]
+    format: Option<SnapshotFormat>,
+}
+
+impl Cli {
+    /// Loads the config file and applies the flags on top of it
+    fn load_config(&self) -> Result<ConfigFile, String> {
+        let path = self
+            .config
+            .clone()
+            .or_else(|| std::env::var(CONFIG_PATH_ENV).ok());
+        let mut config = ConfigFile::load(path.as_deref()).map_err(|e| e.to_string())?;
+
+        if let Some(matrix) = &self.matrix {
+            config.startup.matrix.clone_from(matrix);
+        }
+        if let Some(reactions) = &self.reactions {
+            config.startup.reactions.clone_from(reactions);
+        }
+        if let Some(seed) = self.seed {
+            config.particles.seed = seed;
+        }
+        if let Some(particles) = self.particles {
+            config.particles.init_particle_num = particles;
+        }
+        if let Some((width, height, depth)) = self.boundary {
+            config.particles.map_width = width;
+            config.particles.map_height = height;
+            if let Some(depth) = depth {
+                config.particles.map_depth = depth;
+            }
+        }
+        if self.paused {
+            config.startup.paused = true;
+        }
+        if let Some(snapshot) = &self.snapshot {
+            config.startup.snapshot = Some(snapshot.clone());
+        }
+        config
+            .validate()
+            .map_err(|e| format!("Invalid command line: {e}"))?;
+        Ok(config)
+    }
+}
+
+/// Parses a map size from `WxH` or `WxHxD`
+fn parse_boundary(value: &str) -> Result<(f32, f32, Option<f32>), String> {
+    let parts = value
+        .split(['x', 'X'])
+        .map(|part| part.trim().parse::<f32>())
+        .collect::<Result<Vec<f32>, _>>()
+        .map_err(|e| format!("{value}: {e}"))?;
+    match parts.as_slice() {
+        [width, height] => Ok((*width, *height, None)),
+        [width, height, depth] => Ok((*width, *height, Some(*depth))),
+        _ => Err(format!(
+            "{value}: expected WIDTHxHEIGHT or WIDTHxHEIGHTxDEPTH"
+        )),
+    }
+}
+
 fn setup(mut commands: Commands) {
     commands.spawn(Camera2d);
     commands.spawn((
         Text::new(
-            "Press ` (backtick) to toggle console\nGame: T=toggle update, WASD=move, -/+=zoom\nConsole: Type commands and press Enter",
+            "Press ` (backtick) to toggle console\nGame: T=toggle update, WASD=move/orbit, -/+=zoom\nConsole: Type commands and press Enter",
         ),
         Node {
             position_type: PositionType::Absolute,
@@ -28,14 +150,48 @@ fn update_fps(mut query: Query<&mut Text>, diagnostics: Res<DiagnosticsStore>) {
             && let Some(value) = fps.smoothed()
         {
             text.0 = format!(
-                "FPS: {:.1}\nPress ` (backtick) to toggle console\nGame: T=toggle update, R=respawn particle, WASD=move, -/+=zoom\nConsole: Type commands and press Enter",
+                "FPS: {:.1}\nPress ` (backtick) to toggle console\nGame: T=toggle update, R=respawn particle, WASD=move/orbit, -/+=zoom\nConsole: Type commands and press Enter",
                 value
             );
         }
     }
 }
 
+/// Runs the `simulate` subcommand without a window
+fn simulate(args: &SimulateArgs, config: ConfigFile) -> Result<(), Box<dyn std::error::Error>> {
+    bevy::log::tracing_subscriber::fmt::init();
+    let format = args
+        .format
+        .unwrap_or_else(|| SnapshotFormat::from_path(&args.output));
+    let mut run = HeadlessRun::load(&config.startup, config.particles.clone(), config.registry())?;
+    bevy::log::info!(
+        "Simulating {} particles for {} steps",
+        run.particle_count(),
+        args.steps
+    );
+    run.run(args.steps, args.every, &args.output, format)?;
+    bevy::log::info!("Finished with {} particles", run.particle_count());
+    Ok(())
+}
+
 fn main() {
+    let cli = Cli::parse();
+    let config = match cli.load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(Command::Simulate(args)) = &cli.command {
+        if let Err(e) = simulate(args, config) {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     App::new()
         .add_plugins((
             DefaultPlugins,
@@ -44,10 +200,17 @@ fn main() {
             ConsolePlugin,
             InputFocusPlugin,
             CommandPlugin,
-            CameraMovePlugin,
-            ParticlePlugin::default(),
+            CameraMovePlugin {
+                config: config.camera,
+            },
+            SimulationPlugin {
+                species: config.registry(),
+                config: config.particles,
+                startup: config.startup,
+            },
+            ParticleRenderPlugin,
+            ParticleInputPlugin,
         ))
-        .insert_resource(ParticleInteractionTable::new())
         .insert_resource(ConsoleConfiguration {
             ..Default::default()
         })
@@ -0,0 +1,33 @@
+//! Camera movement plugin
+//!
+//! Registers the 2D and 3D camera controls.
+
+use crate::resources::{CameraMoveConfig, ParticleConfig};
+use crate::systems::{move_camera, orbit_camera, sync_camera_mode};
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::*;
+
+/// Plugin that registers camera movement system
+///
+/// This plugin:
+/// - Inserts its [`CameraMoveConfig`] resource
+/// - Registers the [`move_camera`] (2D) and [`orbit_camera`] (3D) systems
+///   to run in the `Update` schedule
+/// - Registers [`sync_camera_mode`] to switch cameras when the
+///   [`ParticleConfig`] changes
+#[derive(Debug, Default)]
+pub struct CameraMovePlugin {
+    /// Camera movement speed and zoom limits
+    pub config: CameraMoveConfig,
+}
+
+impl Plugin for CameraMovePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config);
+        app.add_systems(Update, (move_camera, orbit_camera));
+        app.add_systems(
+            Update,
+            sync_camera_mode.run_if(resource_changed::<ParticleConfig>),
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Console command plugin
+//!
+//! Registers the console commands of the `console` module.
+
+use crate::console::{
+    AddSpeciesCommand, AnnealCommand, EcosystemCommand, FieldCommand, GenerateSpeciesCommand,
+    GenomeCommand, InteractionCommand, InteractionProfileCommand, ListSpeciesCommand,
+    LoadSnapshotCommand, MembraneCommand, ObstacleCommand, PrintCommand, RandomInteractionCommand,
+    ReactionCommand, ResetInteractionCommand, RespawnParticle, SaveConfigCommand,
+    SaveInteractionCommand, SaveSnapshotCommand, SetCommand, SpeciesCommand, add_species, anneal,
+    ecosystem, field, generate_species, genome, interaction, interaction_profile, list_species,
+    load_snapshot, membrane, obstacle, print, random_interaction, reaction, reset_interaction,
+    respawn_particle_console, save_config, save_interaction, save_snapshot, set, species,
+};
+use bevy::app::{App, Plugin};
+use bevy_console::AddConsoleCommand;
+
+/// Plugin that registers all console commands
+///
+/// This plugin registers:
+/// - `set` command
+/// - `print` command
+/// - `interaction` command
+/// - `interaction_profile` command
+/// - `reset_interaction` command
+/// - `save_interaction` command
+/// - `save_config` command
+/// - `random_interaction` command
+/// - `list_species`, `add_species` and `generate_species` commands
+/// - `species` command
+/// - `anneal` command
+/// - `field` command
+/// - `obstacle`, `membrane` and `reaction` commands
+/// - `ecosystem` and `genome` commands
+/// - `save_snapshot` and `load_snapshot` commands
+/// - `respawn_particle` command
+pub struct CommandPlugin;
+
+impl Plugin for CommandPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command::<SetCommand, _>(set);
+        app.add_console_command::<PrintCommand, _>(print);
+        app.add_console_command::<InteractionCommand, _>(interaction);
+        app.add_console_command::<InteractionProfileCommand, _>(interaction_profile);
+        app.add_console_command::<ResetInteractionCommand, _>(reset_interaction);
+        app.add_console_command::<SaveInteractionCommand, _>(save_interaction);
+        app.add_console_command::<SaveConfigCommand, _>(save_config);
+        app.add_console_command::<RandomInteractionCommand, _>(random_interaction);
+        app.add_console_command::<ListSpeciesCommand, _>(list_species);
+        app.add_console_command::<AddSpeciesCommand, _>(add_species);
+        app.add_console_command::<GenerateSpeciesCommand, _>(generate_species);
+        app.add_console_command::<SpeciesCommand, _>(species);
+        app.add_console_command::<AnnealCommand, _>(anneal);
+        app.add_console_command::<FieldCommand, _>(field);
+        app.add_console_command::<ObstacleCommand, _>(obstacle);
+        app.add_console_command::<MembraneCommand, _>(membrane);
+        app.add_console_command::<ReactionCommand, _>(reaction);
+        app.add_console_command::<EcosystemCommand, _>(ecosystem);
+        app.add_console_command::<GenomeCommand, _>(genome);
+        app.add_console_command::<SaveSnapshotCommand, _>(save_snapshot);
+        app.add_console_command::<LoadSnapshotCommand, _>(load_snapshot);
+        app.add_console_command::<RespawnParticle, _>(respawn_particle_console);
+    }
+}
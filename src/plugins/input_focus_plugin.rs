@@ -0,0 +1,21 @@
+//! Input focus plugin
+//!
+//! Moves keyboard focus between the game and the console.
+
+use crate::resources::InputFocus;
+use crate::systems::update_input_focus;
+use bevy::app::{App, Plugin, Update};
+
+/// Plugin that registers input focus management system
+///
+/// This plugin:
+/// - Inserts the default [`InputFocus`] resource
+/// - Registers the [`update_input_focus`] system
+pub struct InputFocusPlugin;
+
+impl Plugin for InputFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputFocus::default());
+        app.add_systems(Update, update_input_focus);
+    }
+}
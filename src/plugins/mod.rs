@@ -0,0 +1,17 @@
+//! Plugins module
+//!
+//! This module contains all Bevy plugins used in the game.
+
+mod camera_move_plugin;
+mod command_plugin;
+mod input_focus_plugin;
+mod particle_input_plugin;
+mod particle_render_plugin;
+mod simulation_plugin;
+
+pub use camera_move_plugin::CameraMovePlugin;
+pub use command_plugin::CommandPlugin;
+pub use input_focus_plugin::InputFocusPlugin;
+pub use particle_input_plugin::ParticleInputPlugin;
+pub use particle_render_plugin::ParticleRenderPlugin;
+pub use simulation_plugin::SimulationPlugin;
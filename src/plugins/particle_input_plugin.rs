@@ -0,0 +1,29 @@
+//! Particle input plugin
+//!
+//! Registers the keyboard and mouse controls.
+
+use crate::resources::InputFocus;
+use crate::systems::{place_force_field, respawn_particle, toggle_particle_update};
+use bevy::app::{App, Plugin, Update};
+
+/// Plugin for the keyboard and mouse controls of the simulation
+///
+/// Requires the [`SimulationPlugin`](crate::SimulationPlugin). Controls only react while the game
+/// has the [`InputFocus`], which the [`InputFocusPlugin`](crate::InputFocusPlugin) moves between
+/// the game and the console.
+///
+/// # Systems
+/// - `toggle_particle_update` (Update): Toggles physics updates with T key
+/// - `respawn_particle` (Update): Respawns particles with R key
+/// - `place_force_field` (Update): Places and removes force fields with the mouse
+pub struct ParticleInputPlugin;
+
+impl Plugin for ParticleInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputFocus>();
+        app.add_systems(
+            Update,
+            (toggle_particle_update, respawn_particle, place_force_field),
+        );
+    }
+}
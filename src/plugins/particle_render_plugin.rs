@@ -0,0 +1,46 @@
+//! Particle render plugin
+//!
+//! Draws particles, force fields and obstacles.
+
+use crate::resources::ParticleMeshCache;
+use crate::systems::{
+    attach_particle_mesh, draw_force_fields, draw_obstacles, hide_dormant_particle,
+    sync_particle_species, sync_transform,
+};
+use bevy::app::{App, Plugin, Update};
+
+/// Plugin that draws the particle simulation
+///
+/// Requires the [`SimulationPlugin`](crate::SimulationPlugin) and the rendering plugins of
+/// `DefaultPlugins`. Particles of a species share one mesh and material
+/// from the [`ParticleMeshCache`].
+///
+/// # Systems
+/// - `sync_transform` (Update): Interpolates Position into Transform for rendering
+/// - `sync_particle_species` (Update): Switches particles converted by reactions to
+///   the mesh and material of their new species
+/// - `draw_force_fields` (Update): Draws force fields with gizmos
+/// - `draw_obstacles` (Update): Draws obstacles with gizmos
+///
+/// # Observers
+/// - `attach_particle_mesh`: Gives new and revived particles a transform and
+///   the mesh and material of their species
+/// - `hide_dormant_particle`: Hides entities of dead particles
+pub struct ParticleRenderPlugin;
+
+impl Plugin for ParticleRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticleMeshCache>();
+        app.add_observer(attach_particle_mesh);
+        app.add_observer(hide_dormant_particle);
+        app.add_systems(
+            Update,
+            (
+                sync_transform,
+                sync_particle_species,
+                draw_force_fields,
+                draw_obstacles,
+            ),
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Particle simulation plugin
+//!
+//! Inserts the simulation resources and registers the physics systems.
+
+use crate::resources::{
+    Annealing, Ecosystem, EcosystemStats, Evolution, FieldBrush, GenomeStats, Membranes, Obstacles,
+    ParticleConfig, ParticleInteractionTable, ParticleUpdateToggle, ReactionStats, ReactionTable,
+    SimRng, SimulationHealth, SpeciesRegistry, StartupConfig,
+};
+use crate::simulation::Simulation;
+use crate::systems::{anneal_temperature, load_startup_snapshot, setup, update_particle};
+use bevy::app::{App, FixedUpdate, Plugin, PostStartup, Startup};
+use bevy::prelude::*;
+
+/// Plugin for particle simulation system
+///
+/// Only needs `MinimalPlugins`: particles are spawned with their
+/// simulation components alone ([`ParticleMarker`](crate::components::ParticleMarker), [`ParticleType`](crate::components::ParticleType),
+/// [`Position`](crate::components::Position) and [`Velocity`](crate::components::Velocity)). Add the [`ParticleRenderPlugin`](crate::ParticleRenderPlugin) to
+/// draw them and the [`ParticleInputPlugin`](crate::ParticleInputPlugin) for keyboard and mouse controls.
+///
+/// This plugin:
+/// - Inserts the particle configuration resource
+/// - Inserts the [`StartupConfig`] resource and starts paused if it says so
+/// - Inserts the [`SpeciesRegistry`] and [`ParticleInteractionTable`] resources
+/// - Inserts the seeded [`SimRng`] resource
+/// - Inserts the [`Simulation`] resource `update_particle` steps
+/// - Inserts the [`Annealing`] resource
+/// - Inserts the [`FieldBrush`] resource
+/// - Inserts the [`Obstacles`] and [`Membranes`] resources
+/// - Inserts the [`ReactionTable`] and [`ReactionStats`] resources
+/// - Inserts the [`Ecosystem`] and [`EcosystemStats`] resources
+/// - Inserts the [`Evolution`] and [`GenomeStats`] resources
+/// - Registers all particle simulation systems
+/// - Spawns initial particles
+///
+/// # Systems
+/// - `setup` (Startup): Loads interactions and spawns particles
+/// - `load_startup_snapshot` (`PostStartup`): Replaces them with the startup
+///   snapshot, if one is configured
+/// - `anneal_temperature` (`FixedUpdate`, conditional): Follows the temperature ramp
+/// - `update_particle` (`FixedUpdate`, conditional): Updates particle physics,
+///   births and deaths
+#[derive(Debug, Default)]
+pub struct SimulationPlugin {
+    /// Configuration for the particle system
+    pub config: ParticleConfig,
+    /// Species to simulate, replaced by the header of the interaction CSV if present
+    pub species: SpeciesRegistry,
+    /// Matrix file and startup behavior
+    pub startup: StartupConfig,
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config.clone());
+        app.insert_resource(self.species.clone());
+        app.insert_resource(ParticleInteractionTable::new());
+        app.insert_resource(SimRng::new(self.config.seed));
+        app.insert_resource(Simulation::new(
+            self.config.clone(),
+            ParticleInteractionTable::new(),
+        ));
+        app.insert_resource(Time::<Fixed>::from_hz(self.config.tick_rate));
+        app.insert_resource(self.startup.clone());
+        app.insert_resource(ParticleUpdateToggle::with_enabled(!self.startup.paused));
+        app.insert_resource(SimulationHealth::default());
+        app.insert_resource(Annealing::default());
+        app.insert_resource(FieldBrush::default());
+        app.insert_resource(Obstacles::new());
+        app.insert_resource(Membranes::new());
+        app.insert_resource(ReactionTable::new());
+        app.insert_resource(ReactionStats::default());
+        app.insert_resource(Ecosystem::new());
+        app.insert_resource(EcosystemStats::default());
+        app.insert_resource(Evolution::new());
+        app.insert_resource(GenomeStats::default());
+        app.add_systems(Startup, setup);
+        app.add_systems(PostStartup, load_startup_snapshot);
+        app.add_systems(
+            FixedUpdate,
+            (anneal_temperature, update_particle)
+                .chain()
+                .run_if(|toggle: Res<ParticleUpdateToggle>| toggle.is_enabled()),
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Temperature ramps for annealing
+//!
+//! Moves `config.temperature` linearly from one value to another over a
+//! span of simulated time, e.g. to cool a hot system slowly into a
+//! structured state.
+
+use bevy::ecs::resource::Resource;
+
+/// Linear temperature ramp applied by the `anneal_temperature` system
+///
+/// The default ramp has zero duration and is inactive.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Annealing {
+    /// Temperature at the start of the ramp
+    pub from: f32,
+    /// Temperature at the end of the ramp
+    pub to: f32,
+    /// Length of the ramp in simulated time
+    pub duration: f32,
+    /// Simulated time since the ramp started
+    pub elapsed: f32,
+}
+
+impl Annealing {
+    /// Creates a ramp from `from` to `to` over `duration`
+    #[must_use]
+    pub const fn new(from: f32, to: f32, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Returns true while the ramp has not reached its end
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    /// Stops the ramp at its current temperature
+    pub const fn stop(&mut self) {
+        self.duration = self.elapsed;
+    }
+
+    /// Returns the fraction of the ramp completed, in `[0, 1]`
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the temperature at the current point of the ramp
+    #[must_use]
+    pub fn temperature(&self) -> f32 {
+        (self.to - self.from).mul_add(self.progress(), self.from)
+    }
+
+    /// Advances the ramp by `dt` of simulated time and returns the new
+    /// temperature
+    pub fn advance(&mut self, dt: f32) -> f32 {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+        self.temperature()
+    }
+}
@@ -0,0 +1,212 @@
+//! Boundary handling modes
+//!
+//! Describes what happens to a particle when it leaves the map along
+//! one axis.
+
+use bevy::math::Vec3;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Boundary handling mode for one axis of the map
+///
+/// Each axis of the map can use its own mode, which allows mixed setups
+/// such as a horizontally periodic channel with reflecting walls at the
+/// top and bottom.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundaryMode {
+    /// Mirror the position back inside and reverse the velocity
+    #[default]
+    #[serde(rename = "reflect")]
+    Reflect,
+    /// Periodic boundary: leaving one edge re-enters at the opposite edge
+    #[serde(rename = "wrap")]
+    Wrap,
+    /// Clamp the position to the edge and drop the outward velocity
+    #[serde(rename = "clamp")]
+    Clamp,
+    /// Clamp the position to the edge and drop all velocity
+    #[serde(rename = "absorb")]
+    Absorb,
+}
+
+impl BoundaryMode {
+    /// Returns string representation of this boundary mode
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Reflect => "reflect",
+            Self::Wrap => "wrap",
+            Self::Clamp => "clamp",
+            Self::Absorb => "absorb",
+        }
+    }
+
+    /// Returns true if this mode is periodic
+    #[must_use]
+    pub fn is_wrap(&self) -> bool {
+        *self == Self::Wrap
+    }
+
+    /// Returns the shortest signed offset along this axis
+    ///
+    /// In [`BoundaryMode::Wrap`] mode this is the minimum-image offset,
+    /// so particles on opposite sides of the seam see each other as
+    /// neighbors. All other modes return `delta` unchanged.
+    #[must_use]
+    pub fn delta(self, delta: f32, extent: f32) -> f32 {
+        if self.is_wrap() && extent > 0.0 {
+            extent.mul_add(-(delta / extent).round(), delta)
+        } else {
+            delta
+        }
+    }
+
+    /// Applies this boundary to one axis of a particle
+    ///
+    /// `axis` selects the vector component (0 = x, 1 = y, 2 = z) and
+    /// `half_extent` is half of the map size along that axis.
+    pub fn apply(self, axis: usize, position: &mut Vec3, velocity: &mut Vec3, half_extent: f32) {
+        let p = position[axis];
+        if p.abs() <= half_extent || half_extent <= 0.0 {
+            return;
+        }
+
+        match self {
+            Self::Reflect => {
+                let edge = half_extent.copysign(p);
+                position[axis] = 2.0f32.mul_add(edge, -p).clamp(-half_extent, half_extent);
+                velocity[axis] = -velocity[axis].abs().copysign(p);
+            }
+            Self::Wrap => {
+                let extent = half_extent * 2.0;
+                position[axis] = (p + half_extent).rem_euclid(extent) - half_extent;
+            }
+            Self::Clamp => {
+                position[axis] = p.clamp(-half_extent, half_extent);
+                if velocity[axis] * p > 0.0 {
+                    velocity[axis] = 0.0;
+                }
+            }
+            Self::Absorb => {
+                position[axis] = p.clamp(-half_extent, half_extent);
+                *velocity = Vec3::ZERO;
+            }
+        }
+    }
+}
+
+impl Display for BoundaryMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an invalid boundary mode string
+#[derive(Debug)]
+pub struct BoundaryModeError;
+
+impl Display for BoundaryModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid boundary mode. Expected one of: reflect, wrap, clamp, absorb"
+        )
+    }
+}
+
+impl Error for BoundaryModeError {}
+
+impl FromStr for BoundaryMode {
+    type Err = BoundaryModeError;
+
+    fn from_str(s: &str) -> Result<Self, BoundaryModeError> {
+        match s.to_lowercase().as_str() {
+            "reflect" => Ok(Self::Reflect),
+            "wrap" => Ok(Self::Wrap),
+            "clamp" => Ok(Self::Clamp),
+            "absorb" => Ok(Self::Absorb),
+            _ => Err(BoundaryModeError),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_delta_uses_minimum_image() {
+        assert_eq!(BoundaryMode::Wrap.delta(1990.0, 2000.0), -10.0);
+        assert_eq!(BoundaryMode::Wrap.delta(-1990.0, 2000.0), 10.0);
+        assert_eq!(BoundaryMode::Wrap.delta(900.0, 2000.0), 900.0);
+        assert_eq!(BoundaryMode::Reflect.delta(1990.0, 2000.0), 1990.0);
+        assert_eq!(BoundaryMode::Wrap.delta(1990.0, 0.0), 1990.0);
+    }
+
+    #[test]
+    fn wrap_reenters_at_opposite_edge() {
+        let mut position = Vec3::new(1005.0, -1010.0, 0.0);
+        let mut velocity = Vec3::new(3.0, -4.0, 0.0);
+        BoundaryMode::Wrap.apply(0, &mut position, &mut velocity, 1000.0);
+        BoundaryMode::Wrap.apply(1, &mut position, &mut velocity, 1000.0);
+        assert!((position.x + 995.0).abs() < 1e-3);
+        assert!((position.y - 990.0).abs() < 1e-3);
+        assert_eq!(velocity, Vec3::new(3.0, -4.0, 0.0));
+    }
+
+    #[test]
+    fn reflect_mirrors_position_and_velocity() {
+        let mut position = Vec3::new(1010.0, 0.0, 0.0);
+        let mut velocity = Vec3::new(5.0, 1.0, 0.0);
+        BoundaryMode::Reflect.apply(0, &mut position, &mut velocity, 1000.0);
+        assert!((position.x - 990.0).abs() < 1e-3);
+        assert_eq!(velocity, Vec3::new(-5.0, 1.0, 0.0));
+
+        let mut position = Vec3::new(-1500.0, 0.0, 0.0);
+        let mut velocity = Vec3::new(-5.0, 0.0, 0.0);
+        BoundaryMode::Reflect.apply(0, &mut position, &mut velocity, 1000.0);
+        assert!((position.x + 500.0).abs() < 1e-3);
+        assert_eq!(velocity.x, 5.0);
+
+        // Mirror images beyond the opposite edge are clamped into the map.
+        let mut position = Vec3::new(-5000.0, 0.0, 0.0);
+        BoundaryMode::Reflect.apply(0, &mut position, &mut velocity, 1000.0);
+        assert_eq!(position.x, 1000.0);
+    }
+
+    #[test]
+    fn clamp_and_absorb_stop_at_edge() {
+        let mut position = Vec3::new(0.0, 1200.0, 0.0);
+        let mut velocity = Vec3::new(2.0, 7.0, 0.0);
+        BoundaryMode::Clamp.apply(1, &mut position, &mut velocity, 1000.0);
+        assert_eq!(position.y, 1000.0);
+        assert_eq!(velocity, Vec3::new(2.0, 0.0, 0.0));
+
+        let mut position = Vec3::new(0.0, 1200.0, 0.0);
+        let mut velocity = Vec3::new(2.0, 7.0, 0.0);
+        BoundaryMode::Absorb.apply(1, &mut position, &mut velocity, 1000.0);
+        assert_eq!(position.y, 1000.0);
+        assert_eq!(velocity, Vec3::ZERO);
+    }
+
+    #[test]
+    fn inside_positions_are_untouched() {
+        for mode in [
+            BoundaryMode::Reflect,
+            BoundaryMode::Wrap,
+            BoundaryMode::Clamp,
+            BoundaryMode::Absorb,
+        ] {
+            let mut position = Vec3::new(999.0, -999.0, 0.0);
+            let mut velocity = Vec3::new(1.0, 1.0, 0.0);
+            mode.apply(0, &mut position, &mut velocity, 1000.0);
+            mode.apply(1, &mut position, &mut velocity, 1000.0);
+            assert_eq!(position, Vec3::new(999.0, -999.0, 0.0), "{mode}");
+            assert_eq!(velocity, Vec3::new(1.0, 1.0, 0.0), "{mode}");
+        }
+    }
+}
@@ -1,11 +1,13 @@
 //! Camera movement control parameters
 
 use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
 
 /// Camera movement control parameters
 ///
 /// Configuration for camera movement speed and zoom limits.
-#[derive(Resource, Clone, Copy)]
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CameraMoveConfig {
     /// Camera movement speed in units per second
     pub speed: f32,
@@ -15,6 +17,8 @@ pub struct CameraMoveConfig {
     pub min_scale: f32,
     /// Maximum zoom scale (zoomed in)
     pub max_scale: f32,
+    /// Orbit camera rotation speed in radians per second (3D mode)
+    pub orbit_speed: f32,
 }
 
 impl Default for CameraMoveConfig {
@@ -24,6 +28,7 @@ impl Default for CameraMoveConfig {
             zoom_speed: 1.0,
             min_scale: 0.01,
             max_scale: 50.0,
+            orbit_speed: 1.5,
         }
     }
 }
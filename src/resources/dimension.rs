@@ -0,0 +1,72 @@
+//! Spatial dimension of the simulation
+//!
+//! Selects between the flat 2D map and a 3D box.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Spatial dimension of the simulation
+///
+/// In 2D all particles stay on the `z = 0` plane. In 3D they live in a
+/// box of `map_width * map_height * map_depth`, use 3D spatial cells and
+/// are rendered as spheres seen from an orbit camera.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Dimension {
+    /// Flat map on the `z = 0` plane
+    #[default]
+    #[serde(rename = "2d")]
+    Two,
+    /// Volumetric box
+    #[serde(rename = "3d")]
+    Three,
+}
+
+impl Dimension {
+    /// Returns string representation of this dimension
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Two => "2d",
+            Self::Three => "3d",
+        }
+    }
+
+    /// Returns true if this is the 3D mode
+    #[must_use]
+    pub const fn is_3d(self) -> bool {
+        matches!(self, Self::Three)
+    }
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an invalid dimension string
+#[derive(Debug)]
+pub struct DimensionError;
+
+impl Display for DimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid dimension. Expected one of: 2d, 3d")
+    }
+}
+
+impl Error for DimensionError {}
+
+impl FromStr for Dimension {
+    type Err = DimensionError;
+
+    fn from_str(s: &str) -> Result<Self, DimensionError> {
+        match s.to_lowercase().as_str() {
+            "2d" | "2" => Ok(Self::Two),
+            "3d" | "3" => Ok(Self::Three),
+            _ => Err(DimensionError),
+        }
+    }
+}
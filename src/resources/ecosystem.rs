@@ -0,0 +1,162 @@
+//! Ecosystem mode settings
+//!
+//! In ecosystem mode every particle carries an energy value. Energy
+//! drains over time and is gained by staying near food species or by
+//! eating prey. Particles die when their energy runs out and split in two
+//! above a threshold, so populations grow and shrink while the
+//! simulation runs.
+
+use crate::components::ParticleType;
+use bevy::ecs::resource::Resource;
+use std::collections::BTreeMap;
+
+/// Ecosystem mode settings
+///
+/// Feeding and predation are set per (eater, food) pair of species:
+///
+/// - a feeding rate is the energy per second an eater gains for every
+///   particle of the food species within `feeding_distance`; the food is
+///   not consumed
+/// - a predation chance is the probability per step that an eater eats
+///   one prey particle in contact, gaining `efficiency` times its energy;
+///   the prey dies
+///
+/// Pairs without an entry neither feed nor prey.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Ecosystem {
+    /// Ecosystem mode is active
+    pub enabled: bool,
+    /// Energy of particles that have none yet
+    pub initial_energy: f32,
+    /// Energy every particle loses per second
+    pub drain: f32,
+    /// Energy at which a particle splits into two, each keeping half
+    pub split_energy: f32,
+    /// Largest eater–food distance for feeding, overriding `config.r`
+    pub feeding_distance: Option<f32>,
+    /// Fraction of the prey's energy gained by eating it
+    pub efficiency: f32,
+    /// Largest total number of particles; splits beyond it are skipped
+    pub max_population: Option<usize>,
+    caps: BTreeMap<ParticleType, usize>,
+    feeding: BTreeMap<(ParticleType, ParticleType), f32>,
+    predation: BTreeMap<(ParticleType, ParticleType), f32>,
+}
+
+impl Default for Ecosystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ecosystem {
+    /// Creates disabled ecosystem settings without feeding or predation
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            enabled: false,
+            initial_energy: 50.0,
+            drain: 1.0,
+            split_energy: 100.0,
+            feeding_distance: None,
+            efficiency: 0.5,
+            max_population: None,
+            caps: BTreeMap::new(),
+            feeding: BTreeMap::new(),
+            predation: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the population cap of a species
+    #[must_use]
+    pub fn cap(&self, particle_type: ParticleType) -> Option<usize> {
+        self.caps.get(&particle_type).copied()
+    }
+
+    /// Sets or removes the population cap of a species
+    pub fn set_cap(&mut self, particle_type: ParticleType, cap: Option<usize>) {
+        match cap {
+            Some(cap) => self.caps.insert(particle_type, cap),
+            None => self.caps.remove(&particle_type),
+        };
+    }
+
+    /// Returns all species population caps
+    pub fn caps(&self) -> impl Iterator<Item = (ParticleType, usize)> + '_ {
+        self.caps
+            .iter()
+            .map(|(particle_type, cap)| (*particle_type, *cap))
+    }
+
+    /// Returns the energy per second an eater gains from one nearby food
+    /// particle
+    #[must_use]
+    pub fn feeding(&self, eater: ParticleType, food: ParticleType) -> f32 {
+        self.feeding.get(&(eater, food)).copied().unwrap_or(0.0)
+    }
+
+    /// Sets the feeding rate of a pair; zero removes it
+    pub fn set_feeding(&mut self, eater: ParticleType, food: ParticleType, rate: f32) {
+        if rate.abs() > 0.0 {
+            self.feeding.insert((eater, food), rate);
+        } else {
+            self.feeding.remove(&(eater, food));
+        }
+    }
+
+    /// Returns all feeding rates as `(eater, food, rate)`
+    pub fn feeding_rates(&self) -> impl Iterator<Item = (ParticleType, ParticleType, f32)> + '_ {
+        self.feeding
+            .iter()
+            .map(|((eater, food), rate)| (*eater, *food, *rate))
+    }
+
+    /// Returns the chance per step that an eater eats a prey in contact
+    #[must_use]
+    pub fn predation(&self, eater: ParticleType, prey: ParticleType) -> f32 {
+        self.predation.get(&(eater, prey)).copied().unwrap_or(0.0)
+    }
+
+    /// Sets the predation chance of a pair; zero removes it
+    pub fn set_predation(&mut self, eater: ParticleType, prey: ParticleType, probability: f32) {
+        if probability > 0.0 {
+            self.predation.insert((eater, prey), probability);
+        } else {
+            self.predation.remove(&(eater, prey));
+        }
+    }
+
+    /// Returns all predation chances as `(eater, prey, probability)`
+    pub fn predation_chances(
+        &self,
+    ) -> impl Iterator<Item = (ParticleType, ParticleType, f32)> + '_ {
+        self.predation
+            .iter()
+            .map(|((eater, prey), probability)| (*eater, *prey, *probability))
+    }
+
+    /// Returns true if particles look at their neighbors to feed or prey
+    #[must_use]
+    pub fn forages(&self) -> bool {
+        self.enabled && !(self.feeding.is_empty() && self.predation.is_empty())
+    }
+
+    /// Returns the largest distance at which particles feed or prey
+    ///
+    /// `r` is the fallback feeding distance and `contact_distance` the
+    /// distance at which predators reach their prey.
+    #[must_use]
+    pub fn max_distance(&self, r: f32, contact_distance: f32) -> f32 {
+        let feeding = if self.feeding.is_empty() {
+            0.0
+        } else {
+            self.feeding_distance.unwrap_or(r)
+        };
+        let predation = if self.predation.is_empty() {
+            0.0
+        } else {
+            contact_distance
+        };
+        feeding.max(predation)
+    }
+}
@@ -0,0 +1,72 @@
+//! Per-species ecosystem metrics
+//!
+//! Population, energy, births and deaths of every species in ecosystem
+//! mode. Reported by the `ecosystem stats` console command.
+
+use crate::components::ParticleType;
+use bevy::ecs::resource::Resource;
+
+/// Metrics of one species
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SpeciesCensus {
+    /// Number of living particles
+    pub population: usize,
+    /// Summed energy of the living particles
+    pub energy: f32,
+    /// Number of particles born by splitting
+    pub births: u64,
+    /// Number of particles that ran out of energy
+    pub starvations: u64,
+    /// Number of particles eaten by predators
+    pub eaten: u64,
+}
+
+impl SpeciesCensus {
+    /// Returns the mean energy of the living particles
+    #[must_use]
+    pub fn mean_energy(&self) -> f32 {
+        if self.population == 0 {
+            0.0
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let population = self.population as f32;
+            self.energy / population
+        }
+    }
+}
+
+/// Per-species ecosystem metrics, indexed by [`ParticleType::index`]
+///
+/// Population and energy describe the most recent step; births and
+/// deaths are totals since the simulation started.
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct EcosystemStats {
+    /// Metrics of every species seen so far
+    pub species: Vec<SpeciesCensus>,
+}
+
+impl EcosystemStats {
+    /// Returns the metrics of a species, zero for unseen species
+    #[must_use]
+    pub fn get(&self, particle_type: ParticleType) -> SpeciesCensus {
+        self.species
+            .get(particle_type.index())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the metrics of a species for modification
+    pub fn get_mut(&mut self, particle_type: ParticleType) -> &mut SpeciesCensus {
+        let index = particle_type.index();
+        if index >= self.species.len() {
+            self.species.resize(index + 1, SpeciesCensus::default());
+        }
+        &mut self.species[index]
+    }
+
+    /// Returns the total number of living particles
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.species.iter().map(|census| census.population).sum()
+    }
+}
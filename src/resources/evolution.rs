@@ -0,0 +1,48 @@
+//! Evolution mode settings
+//!
+//! In evolution mode every particle carries a
+//! [`Genome`](crate::components::Genome): its own copy of its row of the
+//! interaction table. Children inherit their parent's genome with seeded
+//! Gaussian mutation, so the effective interaction matrix evolves while
+//! the simulation runs.
+
+use bevy::ecs::resource::Resource;
+
+/// Evolution mode settings
+///
+/// Genomes are inherited when particles split in ecosystem mode and, if
+/// `respawn_interval` is set, when particles are periodically respawned
+/// as mutated copies of others of their species.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Evolution {
+    /// Particles use their genome instead of the interaction table
+    pub enabled: bool,
+    /// Standard deviation of the mutation of every strength
+    pub mutation: f32,
+    /// Largest absolute strength a mutation can reach
+    pub limit: f32,
+    /// Number of steps between periodic respawns, `None` to disable them
+    pub respawn_interval: Option<u64>,
+    /// Fraction of the particles respawned each time
+    pub respawn_fraction: f32,
+}
+
+impl Default for Evolution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Evolution {
+    /// Creates disabled evolution settings
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            enabled: false,
+            mutation: 0.05,
+            limit: 1.0,
+            respawn_interval: None,
+            respawn_fraction: 0.01,
+        }
+    }
+}
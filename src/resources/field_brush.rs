@@ -0,0 +1,14 @@
+//! Force field placed with the mouse
+
+use crate::components::ForceField;
+use bevy::ecs::resource::Resource;
+
+/// Force field placed with the mouse
+///
+/// While a field is set, a left click in the game spawns a copy of it
+/// centered at the cursor. Set with the `field brush` console command.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct FieldBrush {
+    /// Template for new fields, `None` disables placing
+    pub field: Option<ForceField>,
+}
@@ -0,0 +1,78 @@
+//! Force kernel selection
+//!
+//! Names the distance-force profile used for particle interactions.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Distance-force profile used for particle interactions
+///
+/// Selects which [`ForceKernel`](crate::simulation::ForceKernel)
+/// the simulation evaluates for every pair of neighboring particles.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ForceKernelKind {
+    /// Piecewise linear collision ramp and triangular interaction profile
+    #[default]
+    #[serde(rename = "classic")]
+    Classic,
+    /// Smooth collision and interaction profiles with continuous slopes
+    #[serde(rename = "smooth")]
+    Smooth,
+    /// Lennard-Jones-like steep core with a shifted attractive tail
+    #[serde(rename = "lennard-jones")]
+    LennardJones,
+    /// Gaussian interaction bump centered between collision distance and `r`
+    #[serde(rename = "gaussian")]
+    Gaussian,
+}
+
+impl ForceKernelKind {
+    /// Returns string representation of this kernel
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::Smooth => "smooth",
+            Self::LennardJones => "lennard-jones",
+            Self::Gaussian => "gaussian",
+        }
+    }
+}
+
+impl Display for ForceKernelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an invalid force kernel string
+#[derive(Debug)]
+pub struct ForceKernelKindError;
+
+impl Display for ForceKernelKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid force kernel. Expected one of: classic, smooth, lennard-jones, gaussian"
+        )
+    }
+}
+
+impl Error for ForceKernelKindError {}
+
+impl FromStr for ForceKernelKind {
+    type Err = ForceKernelKindError;
+
+    fn from_str(s: &str) -> Result<Self, ForceKernelKindError> {
+        match s.to_lowercase().as_str() {
+            "classic" => Ok(Self::Classic),
+            "smooth" => Ok(Self::Smooth),
+            "lennard-jones" | "lennard_jones" | "lj" => Ok(Self::LennardJones),
+            "gaussian" => Ok(Self::Gaussian),
+            _ => Err(ForceKernelKindError),
+        }
+    }
+}
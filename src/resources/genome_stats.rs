@@ -0,0 +1,68 @@
+//! Genome diversity metrics
+//!
+//! Summarizes the genomes of every species in evolution mode. Reported
+//! by the `genome stats` console command and used by `genome export`.
+
+use crate::components::ParticleType;
+use crate::resources::ParticleInteractionTable;
+use bevy::ecs::resource::Resource;
+
+/// Genome metrics of one species
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SpeciesGenomes {
+    /// Number of particles with a genome
+    pub population: usize,
+    /// Number of distinct lineages among them
+    pub lineages: usize,
+    /// Mean standard deviation of the strengths, averaged over sources
+    pub diversity: f32,
+    /// Highest generation reached
+    pub max_generation: u32,
+    /// Lineage with the most particles
+    pub dominant_lineage: u64,
+    /// Number of particles of the dominant lineage
+    pub dominant_count: usize,
+    /// Mean genome of the dominant lineage
+    pub dominant_genome: Vec<f32>,
+}
+
+/// Genome metrics per species, indexed by [`ParticleType::index`]
+///
+/// Describes the genomes after the most recent step.
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct GenomeStats {
+    /// Metrics of every species seen so far
+    pub species: Vec<SpeciesGenomes>,
+}
+
+impl GenomeStats {
+    /// Returns the metrics of a species, if it has any genomes
+    #[must_use]
+    pub fn get(&self, particle_type: ParticleType) -> Option<&SpeciesGenomes> {
+        self.species
+            .get(particle_type.index())
+            .filter(|genomes| genomes.population > 0)
+    }
+
+    /// Returns an interaction table with the dominant genomes as rows
+    ///
+    /// Starts from `base`, so species without genomes and all per-pair
+    /// overrides keep their values.
+    #[must_use]
+    pub fn dominant_table(&self, base: &ParticleInteractionTable) -> ParticleInteractionTable {
+        let mut table = base.clone();
+        for (target, genomes) in self.species.iter().enumerate() {
+            if genomes.population == 0 {
+                continue;
+            }
+            for (source, strength) in genomes.dominant_genome.iter().enumerate() {
+                table.set_interaction(
+                    ParticleType::new(target),
+                    ParticleType::new(source),
+                    *strength,
+                );
+            }
+        }
+        table
+    }
+}
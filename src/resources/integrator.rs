@@ -0,0 +1,77 @@
+//! Time integration schemes
+//!
+//! Names the scheme used to advance positions and velocities.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Time integration scheme for the particle simulation
+///
+/// All schemes treat the velocity half-life `dt_half` as the same
+/// continuous damping rate `ln 2 / dt_half`, so switching schemes does
+/// not change how quickly motion decays.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Integrator {
+    /// Damped semi-implicit Euler (one force evaluation per step)
+    #[default]
+    #[serde(rename = "euler")]
+    SemiImplicitEuler,
+    /// Velocity Verlet with half-step damping (two force evaluations per step)
+    #[serde(rename = "verlet")]
+    VelocityVerlet,
+    /// Classic fourth-order Runge-Kutta (four force evaluations per step)
+    #[serde(rename = "rk4")]
+    Rk4,
+}
+
+impl Integrator {
+    /// Returns all integration schemes
+    #[must_use]
+    pub const fn all() -> [Self; 3] {
+        [Self::SemiImplicitEuler, Self::VelocityVerlet, Self::Rk4]
+    }
+
+    /// Returns string representation of this integrator
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::SemiImplicitEuler => "euler",
+            Self::VelocityVerlet => "verlet",
+            Self::Rk4 => "rk4",
+        }
+    }
+}
+
+impl Display for Integrator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an invalid integrator string
+#[derive(Debug)]
+pub struct IntegratorError;
+
+impl Display for IntegratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid integrator. Expected one of: euler, verlet, rk4")
+    }
+}
+
+impl Error for IntegratorError {}
+
+impl FromStr for Integrator {
+    type Err = IntegratorError;
+
+    fn from_str(s: &str) -> Result<Self, IntegratorError> {
+        match s.to_lowercase().as_str() {
+            "euler" | "semi-implicit-euler" | "semi_implicit_euler" => Ok(Self::SemiImplicitEuler),
+            "verlet" | "velocity-verlet" | "velocity_verlet" => Ok(Self::VelocityVerlet),
+            "rk4" => Ok(Self::Rk4),
+            _ => Err(IntegratorError),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Type-selective membranes
+//!
+//! A membrane gives an obstacle a per-species behavior: solid for some
+//! species, transparent for others, and attracting or repelling each
+//! species with its own strength, like a row of the interaction table
+//! for a wall.
+
+use crate::components::ParticleType;
+use crate::resources::SpeciesRegistry;
+use bevy::ecs::resource::Resource;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// How a membrane treats one species
+///
+/// Written as `solid` or `pass`, optionally followed by a strength:
+/// `pass:0.5`, `solid:-1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WallInteraction {
+    /// Particles of the species collide with the membrane
+    pub solid: bool,
+    /// Force towards the membrane (positive attracts, negative repels)
+    pub strength: f32,
+}
+
+impl Default for WallInteraction {
+    fn default() -> Self {
+        Self {
+            solid: true,
+            strength: 0.0,
+        }
+    }
+}
+
+impl WallInteraction {
+    /// Returns true if the species collides and feels no force
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Display for WallInteraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.solid { "solid" } else { "pass" })?;
+        if self.strength.abs() > 0.0 {
+            write!(f, ":{}", self.strength)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing an invalid wall interaction string
+#[derive(Debug)]
+pub struct WallInteractionError;
+
+impl Display for WallInteractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid wall interaction. Expected solid or pass, optionally followed by :strength"
+        )
+    }
+}
+
+impl Error for WallInteractionError {}
+
+impl FromStr for WallInteraction {
+    type Err = WallInteractionError;
+
+    fn from_str(s: &str) -> Result<Self, WallInteractionError> {
+        let s = s.trim().to_lowercase();
+        let (mode, strength) = s.split_once(':').unwrap_or((s.as_str(), "0"));
+        let solid = match mode.trim() {
+            "solid" => true,
+            "pass" => false,
+            _ => return Err(WallInteractionError),
+        };
+        let strength = strength
+            .trim()
+            .parse::<f32>()
+            .map_err(|_| WallInteractionError)?;
+        if !strength.is_finite() {
+            return Err(WallInteractionError);
+        }
+        Ok(Self { solid, strength })
+    }
+}
+
+/// Named set of per-species wall interactions
+///
+/// Species without an entry collide and feel no force.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Membrane {
+    /// Name obstacles refer to, matched case-insensitively
+    pub name: String,
+    /// Range of the membrane forces overriding `config.r`
+    pub range: Option<f32>,
+    interactions: Vec<WallInteraction>,
+}
+
+impl Membrane {
+    /// Creates a membrane that is solid for every species
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            range: None,
+            interactions: Vec::new(),
+        }
+    }
+
+    /// Returns how the membrane treats a species
+    #[must_use]
+    pub fn get(&self, particle_type: ParticleType) -> WallInteraction {
+        self.interactions
+            .get(particle_type.index())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets how the membrane treats a species
+    pub fn set(&mut self, particle_type: ParticleType, interaction: WallInteraction) {
+        let index = particle_type.index();
+        if index >= self.interactions.len() {
+            self.interactions
+                .resize(index + 1, WallInteraction::default());
+        }
+        self.interactions[index] = interaction;
+    }
+
+    /// Returns true if particles of a species collide with the membrane
+    #[must_use]
+    pub fn is_solid_for(&self, particle_type: ParticleType) -> bool {
+        self.get(particle_type).solid
+    }
+
+    /// Returns true if the membrane exerts a force on any species
+    #[must_use]
+    pub fn has_forces(&self) -> bool {
+        self.interactions
+            .iter()
+            .any(|interaction| interaction.strength.abs() > 0.0)
+    }
+
+    /// Returns the species with a non-default interaction
+    pub fn custom(&self) -> impl Iterator<Item = (ParticleType, WallInteraction)> + '_ {
+        self.interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| !interaction.is_default())
+            .map(|(index, interaction)| (ParticleType::new(index), *interaction))
+    }
+}
+
+/// Membranes obstacles can refer to by name
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct Membranes {
+    membranes: Vec<Membrane>,
+}
+
+impl Membranes {
+    /// Creates an empty set of membranes
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            membranes: Vec::new(),
+        }
+    }
+
+    /// Returns the number of membranes
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.membranes.len()
+    }
+
+    /// Returns true if there are no membranes
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.membranes.is_empty()
+    }
+
+    /// Returns all membranes in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = &Membrane> {
+        self.membranes.iter()
+    }
+
+    /// Finds a membrane by name (case-insensitive)
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Membrane> {
+        self.membranes
+            .iter()
+            .find(|membrane| membrane.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Returns a membrane by name, creating it if it does not exist
+    pub fn get_or_insert(&mut self, name: &str) -> &mut Membrane {
+        let name = name.trim();
+        let index = self
+            .membranes
+            .iter()
+            .position(|membrane| membrane.name.eq_ignore_ascii_case(name))
+            .unwrap_or_else(|| {
+                self.membranes.push(Membrane::new(name));
+                self.membranes.len() - 1
+            });
+        &mut self.membranes[index]
+    }
+
+    /// Removes and returns a membrane by name
+    pub fn remove(&mut self, name: &str) -> Option<Membrane> {
+        let index = self
+            .membranes
+            .iter()
+            .position(|membrane| membrane.name.eq_ignore_ascii_case(name.trim()))?;
+        Some(self.membranes.remove(index))
+    }
+
+    /// Loads membranes from a CSV file
+    ///
+    /// CSV format:
+    /// - First row: headers (`membrane,range,Red,Blue,Green`)
+    /// - Subsequent rows: `name,range,red_rule,blue_rule,green_rule`
+    ///
+    /// Rules are written like `solid`, `pass` or `pass:0.5` (see
+    /// [`WallInteraction`]). Empty cells keep the default: an empty
+    /// range follows `config.r` and an empty rule is `solid`.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, a header names an
+    /// unknown species or a cell cannot be parsed
+    pub fn from_csv_file(
+        path: &str,
+        registry: &SpeciesRegistry,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .flexible(true)
+            .from_path(path)?;
+        let species = rdr
+            .headers()?
+            .iter()
+            .skip(2)
+            .map(|name| registry.parse(name))
+            .collect::<Result<Vec<ParticleType>, _>>()?;
+
+        let mut membranes = Self::new();
+        for result in rdr.records() {
+            let record = result?;
+            let name = record.get(0).unwrap_or_default().trim();
+            if name.is_empty() {
+                continue;
+            }
+            let membrane = membranes.get_or_insert(name);
+            let range = record.get(1).unwrap_or_default().trim();
+            membrane.range = if range.is_empty() {
+                None
+            } else {
+                Some(range.parse()?)
+            };
+            for (particle_type, rule) in species.iter().zip(record.iter().skip(2)) {
+                if !rule.trim().is_empty() {
+                    membrane.set(*particle_type, rule.parse()?);
+                }
+            }
+        }
+
+        bevy::log::info!("Loaded {} membranes from {}", membranes.len(), path);
+        Ok(membranes)
+    }
+
+    /// Saves membranes to a CSV file
+    ///
+    /// Writes the format read by [`from_csv_file`](Self::from_csv_file)
+    /// with one column per species in `registry`.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be created or written to
+    pub fn to_csv_file(
+        &self,
+        path: &str,
+        registry: &SpeciesRegistry,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = csv::Writer::from_path(path)?;
+        let mut header = vec!["membrane".to_string(), "range".to_string()];
+        header.extend(
+            registry
+                .types()
+                .map(|ptype| registry.name(ptype).to_string()),
+        );
+        wtr.write_record(&header)?;
+        for membrane in &self.membranes {
+            let mut row = vec![
+                membrane.name.clone(),
+                membrane.range.map(|r| r.to_string()).unwrap_or_default(),
+            ];
+            row.extend(
+                registry
+                    .types()
+                    .map(|ptype| membrane.get(ptype).to_string()),
+            );
+            wtr.write_record(&row)?;
+        }
+        wtr.flush()?;
+        bevy::log::info!("Saved {} membranes to {}", self.len(), path);
+        Ok(())
+    }
+}
@@ -2,14 +2,58 @@
 //!
 //! This module contains all Bevy resources used in the game.
 
+mod annealing;
+mod boundary_mode;
 mod camera_move_config;
+mod dimension;
+mod ecosystem;
+mod ecosystem_stats;
+mod evolution;
+mod field_brush;
+mod force_kernel_kind;
+mod genome_stats;
 mod input_focus;
+mod integrator;
+mod membranes;
+mod obstacles;
 mod particle_config;
 mod particle_interaction_table;
+mod particle_mesh_cache;
 mod particle_update_toggle;
+mod reaction_stats;
+mod reaction_table;
+mod repair_mode;
+mod sim_rng;
+mod simulation_health;
+mod species_properties;
+mod species_registry;
+mod startup_config;
 
+pub use annealing::Annealing;
+pub use boundary_mode::{BoundaryMode, BoundaryModeError};
 pub use camera_move_config::CameraMoveConfig;
+pub use dimension::{Dimension, DimensionError};
+pub use ecosystem::Ecosystem;
+pub use ecosystem_stats::{EcosystemStats, SpeciesCensus};
+pub use evolution::Evolution;
+pub use field_brush::FieldBrush;
+pub use force_kernel_kind::{ForceKernelKind, ForceKernelKindError};
+pub use genome_stats::{GenomeStats, SpeciesGenomes};
 pub use input_focus::InputFocus;
-pub use particle_config::ParticleConfig;
-pub use particle_interaction_table::ParticleInteractionTable;
+pub use integrator::{Integrator, IntegratorError};
+pub use membranes::{Membrane, Membranes, WallInteraction, WallInteractionError};
+pub use obstacles::{Obstacle, ObstacleParseError, ObstacleShape, Obstacles};
+pub use particle_config::{MAX_SEED, ParticleConfig};
+pub use particle_interaction_table::{InteractionProfile, ParticleInteractionTable};
+pub use particle_mesh_cache::ParticleMeshCache;
 pub use particle_update_toggle::ParticleUpdateToggle;
+pub use reaction_stats::ReactionStats;
+pub use reaction_table::{ReactionParseError, ReactionRule, ReactionTable};
+pub use repair_mode::{RepairMode, RepairModeError};
+pub use sim_rng::SimRng;
+pub use simulation_health::SimulationHealth;
+pub use species_properties::{
+    DEFAULT_MASS, DEFAULT_SIZE, SpeciesProperties, SpeciesProperty, SpeciesPropertyError,
+};
+pub use species_registry::{Species, SpeciesRegistry};
+pub use startup_config::{DEFAULT_MATRIX_PATH, DEFAULT_REACTIONS_PATH, StartupConfig};
@@ -0,0 +1,366 @@
+//! Static obstacles
+//!
+//! Holds the geometry particles collide with besides the map boundary:
+//! segments, circles, rectangles and polygons in the xy plane. In 3D
+//! mode they extend infinitely along z like walls.
+
+use bevy::ecs::resource::Resource;
+use bevy::math::{Rot2, Vec2};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// Geometry of an obstacle
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObstacleShape {
+    /// Thin wall between two points, solid from both sides
+    Segment {
+        /// First end point
+        a: Vec2,
+        /// Second end point
+        b: Vec2,
+    },
+    /// Solid disc
+    Circle {
+        /// Center of the disc
+        center: Vec2,
+        /// Radius of the disc
+        radius: f32,
+    },
+    /// Solid rectangle, axis-aligned when `angle` is zero
+    Rectangle {
+        /// Center of the rectangle
+        center: Vec2,
+        /// Half of the width and height
+        half_size: Vec2,
+        /// Counter-clockwise rotation in radians
+        angle: f32,
+    },
+    /// Solid polygon, closed from the last point back to the first
+    Polygon {
+        /// Corner points in order
+        points: Vec<Vec2>,
+    },
+}
+
+impl ObstacleShape {
+    /// Returns the name of this shape kind
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Segment { .. } => "segment",
+            Self::Circle { .. } => "circle",
+            Self::Rectangle { .. } => "rectangle",
+            Self::Polygon { .. } => "polygon",
+        }
+    }
+
+    /// Returns true if the shape encloses an area
+    ///
+    /// Particles found inside a solid shape are pushed out to its
+    /// boundary.
+    #[must_use]
+    pub const fn is_solid(&self) -> bool {
+        !matches!(self, Self::Segment { .. })
+    }
+
+    /// Returns the straight edges of the shape
+    ///
+    /// Circles have no straight edges.
+    #[must_use]
+    pub fn edges(&self) -> Vec<(Vec2, Vec2)> {
+        match self {
+            Self::Segment { a, b } => vec![(*a, *b)],
+            Self::Circle { .. } => Vec::new(),
+            Self::Rectangle { .. } | Self::Polygon { .. } => {
+                let corners = self.corners();
+                let next = corners.iter().cycle().skip(1);
+                corners.iter().copied().zip(next.copied()).collect()
+            }
+        }
+    }
+
+    /// Returns the corner points of rectangles and polygons
+    #[must_use]
+    pub fn corners(&self) -> Vec<Vec2> {
+        match self {
+            Self::Segment { a, b } => vec![*a, *b],
+            Self::Circle { .. } => Vec::new(),
+            Self::Rectangle {
+                center,
+                half_size,
+                angle,
+            } => {
+                let rotation = Rot2::radians(*angle);
+                [
+                    Vec2::new(-half_size.x, -half_size.y),
+                    Vec2::new(half_size.x, -half_size.y),
+                    Vec2::new(half_size.x, half_size.y),
+                    Vec2::new(-half_size.x, half_size.y),
+                ]
+                .into_iter()
+                .map(|corner| *center + rotation * corner)
+                .collect()
+            }
+            Self::Polygon { points } => points.clone(),
+        }
+    }
+
+    /// Returns the bounding box as `(min, max)`
+    #[must_use]
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        match self {
+            Self::Circle { center, radius } => (
+                *center - Vec2::splat(*radius),
+                *center + Vec2::splat(*radius),
+            ),
+            _ => self.corners().iter().fold(
+                (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+                |(min, max), point| (min.min(*point), max.max(*point)),
+            ),
+        }
+    }
+
+    /// Returns true if `point` lies inside a solid shape
+    ///
+    /// Polygons use the even-odd rule, so concave polygons work too.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        match self {
+            Self::Segment { .. } => false,
+            Self::Circle { center, radius } => point.distance_squared(*center) < radius * radius,
+            Self::Rectangle { .. } | Self::Polygon { .. } => {
+                self.edges().iter().fold(false, |inside, (a, b)| {
+                    let crosses = (a.y > point.y) != (b.y > point.y)
+                        && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x;
+                    inside != crosses
+                })
+            }
+        }
+    }
+}
+
+/// Static obstacle
+#[derive(Debug, Clone, PartialEq)]
+pub struct Obstacle {
+    /// Geometry of the obstacle
+    pub shape: ObstacleShape,
+    /// Fraction of the normal velocity kept on impact (1 is a perfect bounce)
+    pub restitution: f32,
+    /// Fraction of the tangential velocity removed on impact
+    pub friction: f32,
+    /// Name of the [`Membrane`] that decides per species whether the
+    /// obstacle is solid and how it attracts; `None` is solid for all
+    ///
+    /// [`Membrane`]: crate::resources::Membrane
+    pub membrane: Option<String>,
+}
+
+impl Obstacle {
+    /// Creates an obstacle with a perfect, frictionless bounce
+    #[must_use]
+    pub const fn new(shape: ObstacleShape) -> Self {
+        Self {
+            shape,
+            restitution: 1.0,
+            friction: 0.0,
+            membrane: None,
+        }
+    }
+}
+
+/// Error returned when an obstacle file line cannot be parsed
+#[derive(Debug)]
+pub struct ObstacleParseError {
+    /// Description of the problem
+    pub message: String,
+}
+
+impl Display for ObstacleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid obstacle: {}", self.message)
+    }
+}
+
+impl Error for ObstacleParseError {}
+
+/// Static obstacles particles collide with
+///
+/// Obstacles are addressed by their index in insertion order.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct Obstacles {
+    obstacles: Vec<Obstacle>,
+}
+
+impl Obstacles {
+    /// Creates an empty obstacle set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            obstacles: Vec::new(),
+        }
+    }
+
+    /// Returns the number of obstacles
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.obstacles.len()
+    }
+
+    /// Returns true if there are no obstacles
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.obstacles.is_empty()
+    }
+
+    /// Returns all obstacles in insertion order
+    #[must_use]
+    pub fn as_slice(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
+
+    /// Returns the obstacle at `index` for modification
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Obstacle> {
+        self.obstacles.get_mut(index)
+    }
+
+    /// Adds an obstacle and returns its index
+    pub fn add(&mut self, obstacle: Obstacle) -> usize {
+        self.obstacles.push(obstacle);
+        self.obstacles.len() - 1
+    }
+
+    /// Removes and returns the obstacle at `index`
+    pub fn remove(&mut self, index: usize) -> Option<Obstacle> {
+        (index < self.obstacles.len()).then(|| self.obstacles.remove(index))
+    }
+
+    /// Removes all obstacles
+    pub fn clear(&mut self) {
+        self.obstacles.clear();
+    }
+
+    /// Loads obstacles from a CSV file
+    ///
+    /// Each row holds one obstacle: the shape name, restitution, friction
+    /// and membrane name (empty for none), followed by the shape values:
+    /// - `segment,e,f,m,ax,ay,bx,by`
+    /// - `circle,e,f,m,x,y,radius`
+    /// - `rectangle,e,f,m,x,y,half_width,half_height,angle_degrees`
+    /// - `polygon,e,f,m,x1,y1,x2,y2,x3,y3,...`
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or a row is invalid
+    pub fn from_csv_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_path(path)?;
+
+        let mut obstacles = Self::new();
+        for result in rdr.records() {
+            let record = result?;
+            let kind = record.get(0).unwrap_or_default().trim().to_lowercase();
+            if record.len() < 4 {
+                return Err(
+                    invalid(format!("{kind} needs restitution, friction and membrane")).into(),
+                );
+            }
+            let restitution = record[1].trim().parse::<f32>()?;
+            let friction = record[2].trim().parse::<f32>()?;
+            let membrane = Some(record[3].trim())
+                .filter(|name| !name.is_empty())
+                .map(ToString::to_string);
+            let values = record
+                .iter()
+                .skip(4)
+                .map(|value| value.trim().parse::<f32>())
+                .collect::<Result<Vec<f32>, _>>()?;
+            let shape = match (kind.as_str(), values.as_slice()) {
+                ("segment", [ax, ay, bx, by]) => ObstacleShape::Segment {
+                    a: Vec2::new(*ax, *ay),
+                    b: Vec2::new(*bx, *by),
+                },
+                ("circle", [x, y, radius]) => ObstacleShape::Circle {
+                    center: Vec2::new(*x, *y),
+                    radius: *radius,
+                },
+                ("rectangle", [x, y, half_width, half_height, angle]) => ObstacleShape::Rectangle {
+                    center: Vec2::new(*x, *y),
+                    half_size: Vec2::new(*half_width, *half_height),
+                    angle: angle.to_radians(),
+                },
+                ("polygon", points) if points.len() >= 6 && points.len() % 2 == 0 => {
+                    ObstacleShape::Polygon {
+                        points: points
+                            .chunks_exact(2)
+                            .map(|point| Vec2::new(point[0], point[1]))
+                            .collect(),
+                    }
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "{kind} with {} values is not a known shape",
+                        values.len()
+                    ))
+                    .into());
+                }
+            };
+            obstacles.add(Obstacle {
+                shape,
+                restitution,
+                friction,
+                membrane,
+            });
+        }
+
+        bevy::log::info!("Loaded {} obstacles from {}", obstacles.len(), path);
+        Ok(obstacles)
+    }
+
+    /// Saves obstacles to a CSV file
+    ///
+    /// Writes the format read by [`from_csv_file`](Self::from_csv_file).
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be created or written to
+    pub fn to_csv_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = csv::WriterBuilder::new().flexible(true).from_path(path)?;
+        for obstacle in &self.obstacles {
+            let values = match &obstacle.shape {
+                ObstacleShape::Segment { a, b } => vec![a.x, a.y, b.x, b.y],
+                ObstacleShape::Circle { center, radius } => vec![center.x, center.y, *radius],
+                ObstacleShape::Rectangle {
+                    center,
+                    half_size,
+                    angle,
+                } => vec![
+                    center.x,
+                    center.y,
+                    half_size.x,
+                    half_size.y,
+                    angle.to_degrees(),
+                ],
+                ObstacleShape::Polygon { points } => {
+                    points.iter().flat_map(|point| [point.x, point.y]).collect()
+                }
+            };
+            let mut row = vec![
+                obstacle.shape.as_str().to_string(),
+                obstacle.restitution.to_string(),
+                obstacle.friction.to_string(),
+                obstacle.membrane.clone().unwrap_or_default(),
+            ];
+            row.extend(values.iter().map(ToString::to_string));
+            wtr.write_record(&row)?;
+        }
+        wtr.flush()?;
+        bevy::log::info!("Saved {} obstacles to {}", self.len(), path);
+        Ok(())
+    }
+}
+
+/// Creates a parse error with a message
+const fn invalid(message: String) -> ObstacleParseError {
+    ObstacleParseError { message }
+}
@@ -3,43 +3,255 @@
 //! Contains all tunable parameters for the particle system.
 //! These can be modified at runtime via console commands.
 
+use crate::resources::{BoundaryMode, Dimension, ForceKernelKind, Integrator, RepairMode};
 use bevy::ecs::resource::Resource;
+use bevy::math::Vec3;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Largest seed a config can hold
+///
+/// TOML integers are signed 64-bit, so larger seeds could not be saved
+/// to a config file.
+pub const MAX_SEED: u64 = i64::MAX.unsigned_abs();
 
 /// Configuration for particle simulation
 ///
 /// Contains all tunable parameters for the particle system.
 /// These can be modified at runtime via console commands.
-#[derive(Debug, Resource, Clone)]
+#[derive(Debug, Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ParticleConfig {
     /// Initial number of particles to spawn
     pub init_particle_num: usize,
+    /// Spatial dimension (2D map or 3D box)
+    pub dimension: Dimension,
     /// Width of the simulation map boundary
     pub map_width: f32,
     /// Height of the simulation map boundary
     pub map_height: f32,
+    /// Depth of the simulation map boundary (3D only)
+    pub map_depth: f32,
+    /// Boundary handling along the x axis
+    pub boundary_x: BoundaryMode,
+    /// Boundary handling along the y axis
+    pub boundary_y: BoundaryMode,
+    /// Boundary handling along the z axis (3D only)
+    pub boundary_z: BoundaryMode,
     /// Interaction distance
     pub r: f32,
     /// Force magnitude for collision repulsion
     pub repel_force: f32,
+    /// Collision distance as a fraction of `r`
+    pub collision_ratio: f32,
+    /// Distance-force profile used for particle interactions
+    pub kernel: ForceKernelKind,
     /// Time step for physics updates
+    ///
+    /// Simulated time advanced per physics tick (before `time_scale`).
     pub dt: f32,
     /// The half life period of velocity
     pub dt_half: f32,
+    /// Temperature of the Langevin heat bath (0 disables thermal noise)
+    ///
+    /// Velocities receive Gaussian noise that balances the damping set by
+    /// `dt_half`, so the kinetic temperature `m <v²> / d` relaxes towards
+    /// this value.
+    pub temperature: f32,
+    /// Time integration scheme
+    pub integrator: Integrator,
+    /// Upper bound on particle speed (`f32::INFINITY` disables the cap)
+    pub max_speed: f32,
+    /// Repair applied to particles that become non-finite
+    pub repair_mode: RepairMode,
+    /// Physics ticks per second of real time
+    pub tick_rate: f64,
+    /// Number of substeps each physics tick is split into
+    pub substeps: u32,
+    /// Multiplier applied to `dt` to speed up or slow down the simulation
+    pub time_scale: f32,
+    /// Seed for all random operations (spawning, random interactions)
+    pub seed: u64,
 }
 
 impl Default for ParticleConfig {
     fn default() -> Self {
         Self {
             init_particle_num: 2000,
+            dimension: Dimension::Two,
             map_width: 2000.0,
             map_height: 2000.0,
+            map_depth: 2000.0,
+            boundary_x: BoundaryMode::Reflect,
+            boundary_y: BoundaryMode::Reflect,
+            boundary_z: BoundaryMode::Reflect,
 
             r: 300.0,
 
             repel_force: 1.0,
+            collision_ratio: 0.35,
+            kernel: ForceKernelKind::Classic,
 
             dt: 1.0,
             dt_half: 1.0,
+            temperature: 0.0,
+            integrator: Integrator::SemiImplicitEuler,
+            max_speed: f32::INFINITY,
+            repair_mode: RepairMode::Quarantine,
+            tick_rate: 60.0,
+            substeps: 1,
+            time_scale: 1.0,
+
+            seed: 0,
+        }
+    }
+}
+
+impl ParticleConfig {
+    /// Returns the offset from `from` to `to` honoring the boundary modes
+    ///
+    /// Periodic axes use the minimum-image convention, so the offset
+    /// crosses the seam whenever that is the shorter way around.
+    #[must_use]
+    pub fn displacement(&self, from: Vec3, to: Vec3) -> Vec3 {
+        let delta = to - from;
+        Vec3::new(
+            self.boundary_x.delta(delta.x, self.map_width),
+            self.boundary_y.delta(delta.y, self.map_height),
+            self.boundary_z.delta(delta.z, self.depth()),
+        )
+    }
+
+    /// Returns the extent of the map along z
+    ///
+    /// This is `map_depth` in 3D and zero in 2D, where the map is flat.
+    #[must_use]
+    pub const fn depth(&self) -> f32 {
+        if self.dimension.is_3d() {
+            self.map_depth
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns a uniformly distributed position inside the map
+    ///
+    /// Draws x, then y, then (in 3D only) z, so 2D layouts do not depend
+    /// on `map_depth`.
+    pub fn random_position<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        let x = rng.random_range(-self.map_width / 2.0..self.map_width / 2.0);
+        let y = rng.random_range(-self.map_height / 2.0..self.map_height / 2.0);
+        let z = if self.dimension.is_3d() {
+            rng.random_range(-self.map_depth / 2.0..self.map_depth / 2.0)
+        } else {
+            0.0
+        };
+        Vec3::new(x, y, z)
+    }
+
+    /// Returns the simulated time advanced by one substep
+    #[must_use]
+    pub fn substep_dt(&self) -> f32 {
+        #[allow(clippy::cast_precision_loss)]
+        let substeps = self.substeps.max(1) as f32;
+        self.dt * self.time_scale / substeps
+    }
+
+    /// Returns the number of spatial dimensions particles move in
+    #[must_use]
+    pub const fn degrees_of_freedom(&self) -> u32 {
+        if self.dimension.is_3d() { 3 } else { 2 }
+    }
+
+    /// Wraps a position into the map along the periodic axes
+    ///
+    /// Other axes are left unchanged. Multi-stage integrators use this
+    /// for their intermediate positions, which must stay in the map but
+    /// must not have their velocities reflected or clamped.
+    #[must_use]
+    pub fn wrap_position(&self, mut position: Vec3) -> Vec3 {
+        let mut velocity = Vec3::ZERO;
+        let axes = [
+            (self.boundary_x, self.map_width),
+            (self.boundary_y, self.map_height),
+            (self.boundary_z, self.depth()),
+        ];
+        for (axis, (mode, extent)) in axes.into_iter().enumerate() {
+            if mode.is_wrap() {
+                mode.apply(axis, &mut position, &mut velocity, extent / 2.0);
+            }
         }
+        position
+    }
+
+    /// Checks that all values are within their valid ranges
+    ///
+    /// Used both for config files and for the `set` console command, so
+    /// the two accept the same values. Only `max_speed` may be infinite,
+    /// meaning no speed cap.
+    ///
+    /// # Errors
+    /// Returns a message naming the first invalid field
+    pub fn validate(&self) -> Result<(), String> {
+        let positive = |value: f32| value.is_finite() && value > 0.0;
+        let checks = [
+            (
+                positive(self.map_width),
+                "map_width must be positive and finite",
+            ),
+            (
+                positive(self.map_height),
+                "map_height must be positive and finite",
+            ),
+            (
+                positive(self.map_depth),
+                "map_depth must be positive and finite",
+            ),
+            (positive(self.r), "r must be positive and finite"),
+            (
+                self.repel_force.is_finite() && self.repel_force >= 0.0,
+                "repel_force must be finite and not negative",
+            ),
+            (
+                self.collision_ratio > 0.0 && self.collision_ratio < 1.0,
+                "collision_ratio must be between 0 and 1",
+            ),
+            (positive(self.dt), "dt must be positive and finite"),
+            (
+                positive(self.dt_half),
+                "dt_half must be positive and finite",
+            ),
+            (
+                self.temperature.is_finite() && self.temperature >= 0.0,
+                "temperature must be finite and not negative",
+            ),
+            (self.max_speed > 0.0, "max_speed must be positive"),
+            (
+                self.tick_rate.is_finite() && self.tick_rate > 0.0,
+                "tick_rate must be positive and finite",
+            ),
+            (self.substeps > 0, "substeps must be at least 1"),
+            (
+                positive(self.time_scale),
+                "time_scale must be positive and finite",
+            ),
+        ];
+        if let Some((_, message)) = checks.into_iter().find(|(valid, _)| !valid) {
+            return Err(message.to_string());
+        }
+        if self.seed > MAX_SEED {
+            return Err(format!("seed must be at most {MAX_SEED}"));
+        }
+        Ok(())
+    }
+
+    /// Applies the boundary modes to a particle's position and velocity
+    pub fn apply_boundary(&self, position: &mut Vec3, velocity: &mut Vec3) {
+        self.boundary_x
+            .apply(0, position, velocity, self.map_width / 2.0);
+        self.boundary_y
+            .apply(1, position, velocity, self.map_height / 2.0);
+        self.boundary_z
+            .apply(2, position, velocity, self.depth() / 2.0);
     }
 }
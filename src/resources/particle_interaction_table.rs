@@ -1,12 +1,85 @@
 //! Particle interaction table
 //!
-//! Stores interaction forces between all pairs of particle types.
+//! Stores interaction forces between all pairs of particle types,
+//! optionally with a per-pair interaction radius, collision ratio and
+//! force kernel. Its CSV files also carry the species properties.
 
 use crate::components::ParticleType;
+use crate::resources::{ForceKernelKind, SpeciesProperty, SpeciesRegistry};
 use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
 use std::fmt::Write;
 use std::str::FromStr;
 
+/// Interaction parameters for one (target, source) pair
+///
+/// Fields set to `None` fall back to the global values in
+/// [`ParticleConfig`](crate::resources::ParticleConfig).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct InteractionProfile {
+    /// Interaction force (positive attracts, negative repels)
+    pub strength: f32,
+    /// Interaction distance overriding `config.r`
+    pub r: Option<f32>,
+    /// Collision distance as a fraction of `r`, overriding `config.collision_ratio`
+    pub collision_ratio: Option<f32>,
+    /// Force kernel overriding `config.kernel`
+    pub kernel: Option<ForceKernelKind>,
+}
+
+impl InteractionProfile {
+    /// Checks the per-pair overrides
+    ///
+    /// # Errors
+    /// Returns an error message if the radius is not positive or the
+    /// collision ratio is not strictly between 0 and 1
+    pub fn validate(&self) -> Result<(), String> {
+        if self.r.is_some_and(|r| r.is_nan() || r <= 0.0) {
+            return Err("radius must be positive".to_string());
+        }
+        if self
+            .collision_ratio
+            .is_some_and(|b| b.is_nan() || b <= 0.0 || b >= 1.0)
+        {
+            return Err("collision_ratio must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Section of an interaction CSV file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CsvSection {
+    Strength,
+    Radius,
+    CollisionRatio,
+    Kernel,
+    Species,
+}
+
+impl CsvSection {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Strength => "strength",
+            Self::Radius => "radius",
+            Self::CollisionRatio => "collision_ratio",
+            Self::Kernel => "kernel",
+            Self::Species => "species",
+        }
+    }
+
+    fn from_header(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "strength" => Some(Self::Strength),
+            "radius" => Some(Self::Radius),
+            "collision_ratio" => Some(Self::CollisionRatio),
+            "kernel" => Some(Self::Kernel),
+            "species" => Some(Self::Species),
+            _ => None,
+        }
+    }
+}
+
 /// Particle interaction table
 ///
 /// Stores interaction forces between all pairs of particle types.
@@ -14,76 +87,247 @@ use std::str::FromStr;
 /// that a source particle exerts on a target particle.
 ///
 /// Positive values cause attraction, negative values cause repulsion.
+///
+/// Each pair can additionally override the interaction radius, the
+/// collision ratio and the force kernel (see [`InteractionProfile`]).
+///
+/// The table holds one row and column per species of the
+/// [`SpeciesRegistry`]. Pairs outside the table read as zero strength
+/// with no overrides, and setting such a pair grows the table.
 #[derive(Debug, Resource, Clone, Default)]
 pub struct ParticleInteractionTable {
-    interactions: [[f32; ParticleType::COUNT]; ParticleType::COUNT],
+    size: usize,
+    interactions: Vec<f32>,
+    radii: Vec<Option<f32>>,
+    collision_ratios: Vec<Option<f32>>,
+    kernels: Vec<Option<ForceKernelKind>>,
 }
 
 impl ParticleInteractionTable {
-    /// Creates a new interaction table with all zeros
+    /// Creates a new, empty interaction table
     #[must_use]
     pub const fn new() -> Self {
         Self {
-            interactions: [[0.0; ParticleType::COUNT]; ParticleType::COUNT],
+            size: 0,
+            interactions: Vec::new(),
+            radii: Vec::new(),
+            collision_ratios: Vec::new(),
+            kernels: Vec::new(),
+        }
+    }
+
+    /// Creates an interaction table for `size` species with all zeros
+    #[must_use]
+    pub fn with_size(size: usize) -> Self {
+        let mut table = Self::new();
+        table.resize(size);
+        table
+    }
+
+    /// Returns the number of species the table holds
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns true if the table holds no species
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Resizes the table to `size` species
+    ///
+    /// Entries of pairs that exist before and after keep their values;
+    /// new pairs start at zero strength without overrides.
+    pub fn resize(&mut self, size: usize) {
+        if size == self.size {
+            return;
+        }
+        let old = self.size;
+        self.interactions = resize_matrix(&self.interactions, old, size, 0.0);
+        self.radii = resize_matrix(&self.radii, old, size, None);
+        self.collision_ratios = resize_matrix(&self.collision_ratios, old, size, None);
+        self.kernels = resize_matrix(&self.kernels, old, size, None);
+        self.size = size;
+    }
+
+    /// Returns the flat index of a pair, if it is inside the table
+    const fn slot(&self, target: ParticleType, source: ParticleType) -> Option<usize> {
+        let (t, s) = (target.index(), source.index());
+        if t < self.size && s < self.size {
+            Some(t * self.size + s)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the flat index of a pair, growing the table to fit it
+    fn slot_mut(&mut self, target: ParticleType, source: ParticleType) -> usize {
+        let needed = target.index().max(source.index()) + 1;
+        if needed > self.size {
+            self.resize(needed);
         }
+        target.index() * self.size + source.index()
     }
 
     /// Gets the interaction force between two particle types
     ///
     /// Returns the force that a source particle exerts on a target particle.
     #[must_use]
-    pub const fn get_interaction(&self, target: ParticleType, source: ParticleType) -> f32 {
-        self.interactions[target as usize][source as usize]
+    pub fn get_interaction(&self, target: ParticleType, source: ParticleType) -> f32 {
+        self.slot(target, source)
+            .map_or(0.0, |slot| self.interactions[slot])
     }
 
     /// Sets the interaction force between two particle types
     ///
     /// Sets the force that a source particle exerts on a target particle.
-    pub const fn set_interaction(
+    pub fn set_interaction(
         &mut self,
         target: ParticleType,
         source: ParticleType,
         acceleration: f32,
     ) {
-        self.interactions[target as usize][source as usize] = acceleration;
+        let slot = self.slot_mut(target, source);
+        self.interactions[slot] = acceleration;
+    }
+
+    /// Gets the full interaction profile between two particle types
+    #[must_use]
+    pub fn get_profile(&self, target: ParticleType, source: ParticleType) -> InteractionProfile {
+        self.slot(target, source)
+            .map_or_else(InteractionProfile::default, |slot| InteractionProfile {
+                strength: self.interactions[slot],
+                r: self.radii[slot],
+                collision_ratio: self.collision_ratios[slot],
+                kernel: self.kernels[slot],
+            })
+    }
+
+    /// Sets the full interaction profile between two particle types
+    pub fn set_profile(
+        &mut self,
+        target: ParticleType,
+        source: ParticleType,
+        profile: InteractionProfile,
+    ) {
+        let slot = self.slot_mut(target, source);
+        self.interactions[slot] = profile.strength;
+        self.radii[slot] = profile.r;
+        self.collision_ratios[slot] = profile.collision_ratio;
+        self.kernels[slot] = profile.kernel;
+    }
+
+    /// Sets or clears the interaction radius of a pair
+    pub fn set_radius(&mut self, target: ParticleType, source: ParticleType, r: Option<f32>) {
+        let slot = self.slot_mut(target, source);
+        self.radii[slot] = r;
+    }
+
+    /// Sets or clears the collision ratio of a pair
+    pub fn set_collision_ratio(
+        &mut self,
+        target: ParticleType,
+        source: ParticleType,
+        collision_ratio: Option<f32>,
+    ) {
+        let slot = self.slot_mut(target, source);
+        self.collision_ratios[slot] = collision_ratio;
+    }
+
+    /// Sets or clears the force kernel of a pair
+    pub fn set_kernel(
+        &mut self,
+        target: ParticleType,
+        source: ParticleType,
+        kernel: Option<ForceKernelKind>,
+    ) {
+        let slot = self.slot_mut(target, source);
+        self.kernels[slot] = kernel;
+    }
+
+    /// Returns the largest interaction radius of any pair
+    ///
+    /// Pairs without their own radius use `default_r`. The spatial grid
+    /// sizes its cells by this value so that no neighbor is missed.
+    #[must_use]
+    pub fn max_radius(&self, default_r: f32) -> f32 {
+        self.radii
+            .iter()
+            .flatten()
+            .fold(default_r, |max, &r| max.max(r))
     }
 
     /// Loads interaction table from a CSV file
     ///
     /// CSV format:
-    /// - First row: headers (,target,Red,Blue,Green)
+    /// - First row: headers (,Red,Blue,Green), the first cell is ignored
     /// - Subsequent rows: `source_type,red_val,blue_val,green_val`
     ///
+    /// The species names of the first header row define the species:
+    /// `registry` is rebuilt with these names in header order (names it
+    /// already knows keep their color), and the table gets one row and
+    /// column per species.
+    ///
+    /// After the strength matrix, the file may contain optional sections
+    /// with the same layout. A section starts with a header row whose
+    /// first cell names it (`radius`, `collision_ratio` or `kernel`);
+    /// empty cells keep the global default for that pair. Files with
+    /// only the strength matrix load exactly as before.
+    ///
+    /// A `species` section sets the properties of the species instead:
+    /// its header names the properties (`species,mass,size`) and each row
+    /// starts with a species name. Empty cells restore the default.
+    ///
     /// # Errors
     /// Returns an error if:
     /// - The file cannot be opened
     /// - The CSV format is invalid
     /// - The data cannot be parsed correctly
+    /// - A pair radius is not positive or a pair collision ratio is not
+    ///   between 0 and 1
+    /// - A species property is not a positive number
     ///
     /// # Returns
     /// A new [`ParticleInteractionTable`] with loaded values
-    pub fn from_csv_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let mut table = Self::new();
-
+    pub fn from_csv_file(
+        path: &str,
+        registry: &mut SpeciesRegistry,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         let file = std::fs::File::open(path)?;
         let mut rdr = csv::ReaderBuilder::new()
             .has_headers(false)
+            .flexible(true)
             .from_reader(file);
 
+        let mut table = Self::new();
+        let mut species = registry.clone();
         let mut headers: Vec<String> = Vec::new();
+        let mut section = CsvSection::Strength;
         let mut row_idx = 0;
 
         for result in rdr.records() {
             let record = result?;
 
             if headers.is_empty() {
-                // First row: header row (,target,Red,Blue,Green)
+                // First row: header row (,Red,Blue,Green) defines the species
                 headers = record.iter().map(|s: &str| s.to_string()).collect();
+                species = registry.with_names(&headers[1..]);
+                table.resize(species.len());
                 bevy::log::info!("CSV Headers: {:?}", headers);
                 bevy::log::info!("Header length: {}", headers.len());
                 continue;
             }
 
+            // A row naming a section starts a new matrix with its own header
+            if let Some(next) = record.get(0).and_then(CsvSection::from_header) {
+                section = next;
+                headers = record.iter().map(|s: &str| s.to_string()).collect();
+                bevy::log::info!("CSV section: {}", section.as_str());
+                continue;
+            }
+
             // Parse subsequent rows
             // Format: source,target_val0,target_val1,target_val2
             bevy::log::debug!(
@@ -101,39 +345,69 @@ impl ParticleInteractionTable {
 
             // First column is the source particle type
             let source_str = record.get(0).ok_or("Missing source column")?;
-            let source_type = ParticleType::from_str(source_str)?;
+            let source_type = species.parse(source_str)?;
 
-            // Remaining columns are the target values
-            for (col_idx, target_str) in headers.iter().skip(1).enumerate() {
-                if col_idx >= ParticleType::COUNT {
-                    break;
+            // Species rows hold one value per property instead of per target
+            if section == CsvSection::Species {
+                for (col_idx, property_str) in headers.iter().skip(1).enumerate() {
+                    let property: SpeciesProperty = property_str.trim().parse()?;
+                    let value_str = record.get(col_idx + 1).ok_or("Missing value")?.trim();
+                    if let Some(entry) = species.get_mut(source_type) {
+                        entry.properties.set(property, parse_optional(value_str)?);
+                        entry
+                            .properties
+                            .validate()
+                            .map_err(|e| format!("{source_str}: {e}"))?;
+                    }
                 }
+                row_idx += 1;
+                continue;
+            }
 
-                let value_str = record.get(col_idx + 1).ok_or("Missing value")?;
-                let value: f32 = value_str.parse()?;
-
-                let target_type = ParticleType::from_str(target_str)?;
-
-                let target_idx = target_type as usize;
-                let source_idx = source_type as usize;
+            // Remaining columns are the target values
+            for (col_idx, target_str) in headers.iter().skip(1).enumerate() {
+                let value_str = record.get(col_idx + 1).ok_or("Missing value")?.trim();
+                let target_type = species.parse(target_str)?;
 
-                table.interactions[target_idx][source_idx] = value;
+                let slot = table.slot_mut(target_type, source_type);
+                match section {
+                    CsvSection::Strength => {
+                        let value: f32 = value_str.parse()?;
+                        table.interactions[slot] = value;
+                    }
+                    CsvSection::Radius => {
+                        table.radii[slot] = parse_optional(value_str)?;
+                    }
+                    CsvSection::CollisionRatio => {
+                        table.collision_ratios[slot] = parse_optional(value_str)?;
+                    }
+                    CsvSection::Kernel => {
+                        table.kernels[slot] = parse_optional(value_str)?;
+                    }
+                    CsvSection::Species => {}
+                }
+                table
+                    .get_profile(target_type, source_type)
+                    .validate()
+                    .map_err(|e| format!("{target_str} <- {source_str}: {e}"))?;
 
                 bevy::log::debug!(
-                    "Loaded: {}[{}] <- {}[{}] = {:.1}",
+                    "Loaded {}: {}[{}] <- {}[{}] = {}",
+                    section.as_str(),
                     target_str,
-                    target_idx,
+                    target_type.index(),
                     source_str,
-                    source_idx,
-                    value
+                    source_type.index(),
+                    value_str
                 );
             }
 
             row_idx += 1;
         }
 
+        *registry = species;
         bevy::log::info!("\nLoaded interaction table from {}:", path);
-        table.print_table();
+        table.print_table(registry);
 
         Ok(table)
     }
@@ -141,54 +415,138 @@ impl ParticleInteractionTable {
     /// Saves interaction table to a CSV file
     ///
     /// Writes the current interaction values to a CSV file that
-    /// can be loaded later with [`from_csv_file`].
+    /// can be loaded later with [`from_csv_file`](Self::from_csv_file).
+    /// Species names are taken from `registry`.
+    /// The `radius`, `collision_ratio` and `kernel` sections are only
+    /// written when at least one pair overrides them, and the `species`
+    /// section only lists species with non-default properties.
     ///
     /// # Errors
     /// Returns an error if:
     /// - The file cannot be created or written to
     /// - The data cannot be serialized to CSV
-    pub fn to_csv_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut wtr = csv::Writer::from_path(path)?;
+    pub fn to_csv_file(
+        &self,
+        path: &str,
+        registry: &SpeciesRegistry,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = csv::WriterBuilder::new().flexible(true).from_path(path)?;
+
+        self.write_csv_section(&mut wtr, registry, CsvSection::Strength, |slot| {
+            Some(self.interactions[slot].to_string())
+        })?;
+        if self.radii.iter().any(Option::is_some) {
+            self.write_csv_section(&mut wtr, registry, CsvSection::Radius, |slot| {
+                self.radii[slot].map(|r| r.to_string())
+            })?;
+        }
+        if self.collision_ratios.iter().any(Option::is_some) {
+            self.write_csv_section(&mut wtr, registry, CsvSection::CollisionRatio, |slot| {
+                self.collision_ratios[slot].map(|b| b.to_string())
+            })?;
+        }
+        if self.kernels.iter().any(Option::is_some) {
+            self.write_csv_section(&mut wtr, registry, CsvSection::Kernel, |slot| {
+                self.kernels[slot].map(|k| k.as_str().to_string())
+            })?;
+        }
+        Self::write_species_section(&mut wtr, registry)?;
+
+        wtr.flush()?;
+        bevy::log::info!("Saved interaction table to {}", path);
+        Ok(())
+    }
+
+    /// Writes one matrix section (header row and one row per source type)
+    ///
+    /// `value` receives the flat index of each (target, source) pair.
+    fn write_csv_section<W: std::io::Write>(
+        &self,
+        wtr: &mut csv::Writer<W>,
+        registry: &SpeciesRegistry,
+        section: CsvSection,
+        value: impl Fn(usize) -> Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let types: Vec<ParticleType> = (0..self.size).map(ParticleType::new).collect();
 
-        // Write header row: ,target,Red,Blue,Green
-        let mut header: Vec<String> = vec![String::new(), String::from("target")];
-        for particle_type in ParticleType::all_types() {
-            header.push(particle_type.as_str().to_string());
+        // Write header row: section,Red,Blue,Green (empty for the strength matrix)
+        let label = match section {
+            CsvSection::Strength => String::new(),
+            _ => section.as_str().to_string(),
+        };
+        let mut header: Vec<String> = vec![label];
+        for particle_type in &types {
+            header.push(registry.name(*particle_type).to_string());
         }
         wtr.write_record(&header)?;
 
         // Write data rows: source,value0,value1,value2
-        for source in ParticleType::all_types() {
-            let mut row: Vec<String> = vec![source.as_str().to_string()];
-            for target in ParticleType::all_types() {
-                let value = self.get_interaction(target, source);
-                row.push(value.to_string());
+        for source in &types {
+            let mut row: Vec<String> = vec![registry.name(*source).to_string()];
+            for target in &types {
+                row.push(
+                    self.slot(*target, *source)
+                        .and_then(&value)
+                        .unwrap_or_default(),
+                );
             }
             wtr.write_record(&row)?;
         }
+        Ok(())
+    }
 
-        wtr.flush()?;
-        bevy::log::info!("Saved interaction table to {}", path);
+    /// Writes the properties of species that differ from the defaults
+    fn write_species_section<W: std::io::Write>(
+        wtr: &mut csv::Writer<W>,
+        registry: &SpeciesRegistry,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let changed: Vec<ParticleType> = registry
+            .types()
+            .filter(|particle_type| !registry.properties(*particle_type).is_default())
+            .collect();
+        if changed.is_empty() {
+            return Ok(());
+        }
+
+        // Write header row: species,mass,dt_half,...
+        let mut header: Vec<String> = vec![CsvSection::Species.as_str().to_string()];
+        header.extend(SpeciesProperty::all().iter().map(ToString::to_string));
+        wtr.write_record(&header)?;
+
+        // Write data rows: name,mass,dt_half,...
+        for particle_type in changed {
+            let properties = registry.properties(particle_type);
+            let mut row: Vec<String> = vec![registry.name(particle_type).to_string()];
+            row.extend(SpeciesProperty::all().iter().map(|property| {
+                properties
+                    .get(*property)
+                    .map(|value| value.to_string())
+                    .unwrap_or_default()
+            }));
+            wtr.write_record(&row)?;
+        }
         Ok(())
     }
 
     /// Prints the interaction table to the console
     ///
     /// Outputs a formatted table showing all interaction forces
-    /// between particle types.
-    pub fn print_table(&self) {
+    /// between particle types, labeled with the names from `registry`.
+    pub fn print_table(&self, registry: &SpeciesRegistry) {
+        let types: Vec<ParticleType> = (0..self.size).map(ParticleType::new).collect();
+
         // Print header with source particle types
         bevy::log::debug!(
             "       {}",
-            ParticleType::all_types()
+            types
                 .iter()
-                .map(|t| format!("{:>8}", t.as_str()))
+                .map(|t| format!("{:>8}", registry.name(*t)))
                 .collect::<Vec<_>>()
                 .join(" ")
         );
         bevy::log::debug!(
             "       {}",
-            ParticleType::all_types()
+            types
                 .iter()
                 .map(|_| format!("{:>8}", "--------"))
                 .collect::<Vec<_>>()
@@ -196,10 +554,10 @@ impl ParticleInteractionTable {
         );
 
         // Print each row with target particle type and interaction values
-        for target in ParticleType::all_types() {
-            let mut row = format!("{:<6} |", target.as_str());
-            for source in ParticleType::all_types() {
-                let value = self.get_interaction(target, source);
+        for target in &types {
+            let mut row = format!("{:<6} |", registry.name(*target));
+            for source in &types {
+                let value = self.get_interaction(*target, *source);
                 let _ = write!(row, "{value:>8.1}");
             }
             bevy::log::debug!("{}", row);
@@ -207,16 +565,89 @@ impl ParticleInteractionTable {
         bevy::log::debug!("");
     }
 
-    /// Returns a reference to the underlying interaction matrix
+    /// Returns the underlying interaction matrix
+    ///
+    /// The matrix is stored row-major: the force a source exerts on a
+    /// target is at `target * len() + source`.
     #[must_use]
-    pub const fn as_matrix(&self) -> &[[f32; ParticleType::COUNT]; ParticleType::COUNT] {
+    pub fn as_matrix(&self) -> &[f32] {
         &self.interactions
     }
 
-    /// Returns a mutable reference to the underlying interaction matrix
-    pub const fn as_matrix_mut(
-        &mut self,
-    ) -> &mut [[f32; ParticleType::COUNT]; ParticleType::COUNT] {
+    /// Returns the underlying interaction matrix for modification
+    ///
+    /// Uses the same row-major layout as [`as_matrix`](Self::as_matrix).
+    pub fn as_matrix_mut(&mut self) -> &mut [f32] {
         &mut self.interactions
     }
 }
+
+/// Copies a row-major `old * old` matrix into a new `size * size` matrix
+///
+/// Entries outside the overlapping corner are set to `fill`.
+fn resize_matrix<T: Copy>(values: &[T], old: usize, size: usize, fill: T) -> Vec<T> {
+    let keep = old.min(size);
+    let mut resized = vec![fill; size * size];
+    for row in 0..keep {
+        resized[row * size..row * size + keep]
+            .copy_from_slice(&values[row * old..row * old + keep]);
+    }
+    resized
+}
+
+/// Parses an optional CSV cell, treating an empty cell as `None`
+fn parse_optional<T>(value: &str) -> Result<Option<T>, Box<dyn std::error::Error>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + 'static,
+{
+    if value.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(value.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Loads a CSV file with the given contents
+    fn load(name: &str, contents: &str) -> Result<ParticleInteractionTable, String> {
+        let path = std::env::temp_dir().join(format!("particle_life_{name}.csv"));
+        std::fs::write(&path, contents).unwrap();
+        let mut registry = SpeciesRegistry::default();
+        let result = ParticleInteractionTable::from_csv_file(path.to_str().unwrap(), &mut registry)
+            .map_err(|e| e.to_string());
+        std::fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn rejects_non_positive_species_properties() {
+        let matrix = ",Red,Blue\nRed,1.0,0.0\nBlue,0.0,1.0\n";
+
+        assert!(
+            load(
+                "valid_species",
+                &format!("{matrix}species,mass,size\nRed,2.0,\n")
+            )
+            .is_ok()
+        );
+        assert_eq!(
+            load(
+                "zero_mass",
+                &format!("{matrix}species,mass,size\nRed,0.0,\n")
+            )
+            .err(),
+            Some("Red: mass must be a positive number".to_string())
+        );
+        assert!(
+            load(
+                "negative_size",
+                &format!("{matrix}species,mass,size\nBlue,,-1\n")
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Shared particle meshes and materials
+//!
+//! Particles of the same species look the same, so they share one mesh
+//! per size and one material per color instead of adding new assets for
+//! every spawned, revived or converted particle.
+
+use bevy::asset::{Assets, Handle};
+use bevy::color::{Color, ColorToComponents};
+use bevy::ecs::resource::Resource;
+use bevy::math::primitives::{Circle, Sphere};
+use bevy::mesh::Mesh;
+use bevy::pbr::StandardMaterial;
+use bevy::sprite_render::ColorMaterial;
+use std::collections::HashMap;
+
+/// Cache of particle meshes and materials
+///
+/// Meshes are keyed by render size, materials by color, so every species
+/// maps to one mesh and one material handle. Entries are kept until the
+/// app exits; their number is bounded by the distinct species sizes and
+/// colors ever used.
+#[derive(Resource, Debug, Default)]
+pub struct ParticleMeshCache {
+    circles: HashMap<u32, Handle<Mesh>>,
+    spheres: HashMap<u32, Handle<Mesh>>,
+    materials_2d: HashMap<[u32; 4], Handle<ColorMaterial>>,
+    materials_3d: HashMap<[u32; 4], Handle<StandardMaterial>>,
+}
+
+impl ParticleMeshCache {
+    /// Returns the 2D circle mesh of radius `size`
+    pub fn circle(&mut self, meshes: &mut Assets<Mesh>, size: f32) -> Handle<Mesh> {
+        self.circles
+            .entry(size.to_bits())
+            .or_insert_with(|| meshes.add(Circle::new(size)))
+            .clone()
+    }
+
+    /// Returns the 3D sphere mesh of radius `size`
+    pub fn sphere(&mut self, meshes: &mut Assets<Mesh>, size: f32) -> Handle<Mesh> {
+        self.spheres
+            .entry(size.to_bits())
+            .or_insert_with(|| meshes.add(Sphere::new(size)))
+            .clone()
+    }
+
+    /// Returns the 2D material of `color`
+    pub fn material_2d(
+        &mut self,
+        materials: &mut Assets<ColorMaterial>,
+        color: Color,
+    ) -> Handle<ColorMaterial> {
+        self.materials_2d
+            .entry(color_key(color))
+            .or_insert_with(|| materials.add(ColorMaterial::from_color(color)))
+            .clone()
+    }
+
+    /// Returns the unlit 3D material of `color`
+    pub fn material_3d(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+        color: Color,
+    ) -> Handle<StandardMaterial> {
+        self.materials_3d
+            .entry(color_key(color))
+            .or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: color,
+                    unlit: true,
+                    ..StandardMaterial::default()
+                })
+            })
+            .clone()
+    }
+}
+
+/// Returns the bits of a color's sRGB components, usable as a map key
+fn color_key(color: Color) -> [u32; 4] {
+    color.to_srgba().to_f32_array().map(f32::to_bits)
+}
@@ -16,6 +16,12 @@ impl ParticleUpdateToggle {
         Self { enabled: true }
     }
 
+    /// Creates a toggle in the given state
+    #[must_use]
+    pub const fn with_enabled(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
     /// Returns whether particle updates are enabled
     #[must_use]
     pub const fn is_enabled(&self) -> bool {
@@ -0,0 +1,32 @@
+//! Reaction firing counts
+//!
+//! Counts how often each rule of the
+//! [`ReactionTable`](crate::resources::ReactionTable) fired. Reported by
+//! the `reaction list` console command.
+
+use bevy::ecs::resource::Resource;
+
+/// Reaction firing counts, indexed like the rules of the reaction table
+///
+/// Both counts restart whenever the reaction table changes.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReactionStats {
+    /// Number of times each rule fired since the table last changed
+    pub fired: Vec<u64>,
+    /// Number of times each rule fired in the most recent step
+    pub last_step: Vec<u64>,
+}
+
+impl ReactionStats {
+    /// Returns the total count of a rule, zero for unknown rules
+    #[must_use]
+    pub fn fired(&self, rule: usize) -> u64 {
+        self.fired.get(rule).copied().unwrap_or(0)
+    }
+
+    /// Returns the count of a rule in the most recent step
+    #[must_use]
+    pub fn last_step(&self, rule: usize) -> u64 {
+        self.last_step.get(rule).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,281 @@
+//! Reaction rules
+//!
+//! Lets particles change their species: on contact with a partner
+//! (`A + B -> A + A`), with a catalyst that stays unchanged
+//! (`A + C -> B + C`) or spontaneously (`A -> B`).
+
+use crate::components::ParticleType;
+use crate::resources::SpeciesRegistry;
+use bevy::ecs::resource::Resource;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// One species conversion rule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReactionRule {
+    /// Species of the particle that reacts
+    pub reactant: ParticleType,
+    /// Species of the neighbor needed for the reaction; `None` for
+    /// spontaneous decay
+    pub partner: Option<ParticleType>,
+    /// Species the reactant becomes
+    pub product: ParticleType,
+    /// Species the partner becomes; equal to `partner` for catalysts
+    pub partner_product: Option<ParticleType>,
+    /// Largest reactant–partner distance, overriding the contact distance
+    /// `config.collision_ratio * config.r`
+    pub distance: Option<f32>,
+    /// Chance to fire per step and reactant (for each partner in range)
+    pub probability: f32,
+}
+
+impl ReactionRule {
+    /// Creates a spontaneous decay rule `reactant -> product`
+    #[must_use]
+    pub const fn decay(reactant: ParticleType, product: ParticleType, probability: f32) -> Self {
+        Self {
+            reactant,
+            partner: None,
+            product,
+            partner_product: None,
+            distance: None,
+            probability,
+        }
+    }
+
+    /// Returns true if the rule needs no partner
+    #[must_use]
+    pub const fn is_spontaneous(&self) -> bool {
+        self.partner.is_none()
+    }
+
+    /// Returns true if the partner takes part without being converted
+    #[must_use]
+    pub fn is_catalyzed(&self) -> bool {
+        self.partner.is_some() && self.partner == self.partner_product
+    }
+
+    /// Parses a rule from an equation like `A + B -> A + A` or `A -> B`
+    ///
+    /// Species are matched by name through `registry`. Both sides must
+    /// have the same number of species, one or two.
+    ///
+    /// # Errors
+    /// Returns an error if the equation is malformed, names an unknown
+    /// species, or the probability is outside `[0, 1]`
+    pub fn parse(
+        equation: &str,
+        distance: Option<f32>,
+        probability: f32,
+        registry: &SpeciesRegistry,
+    ) -> Result<Self, ReactionParseError> {
+        let (left, right) = equation
+            .split_once("->")
+            .ok_or_else(|| invalid(format!("{equation} has no ->")))?;
+        let species = |side: &str| {
+            side.split('+')
+                .map(|name| registry.parse(name).map_err(|e| invalid(e.to_string())))
+                .collect::<Result<Vec<ParticleType>, _>>()
+        };
+        let (left, right) = (species(left)?, species(right)?);
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(invalid("probability must be between 0 and 1".to_string()));
+        }
+        if distance.is_some_and(|distance| distance <= 0.0) {
+            return Err(invalid("distance must be positive".to_string()));
+        }
+        match (left.as_slice(), right.as_slice()) {
+            ([reactant], [product]) => Ok(Self::decay(*reactant, *product, probability)),
+            ([reactant, partner], [product, partner_product]) => Ok(Self {
+                reactant: *reactant,
+                partner: Some(*partner),
+                product: *product,
+                partner_product: Some(*partner_product),
+                distance,
+                probability,
+            }),
+            _ => Err(invalid(format!(
+                "{equation} needs one or two species on both sides"
+            ))),
+        }
+    }
+
+    /// Formats the rule as an equation using species names
+    #[must_use]
+    pub fn equation(&self, registry: &SpeciesRegistry) -> String {
+        match (self.partner, self.partner_product) {
+            (Some(partner), Some(partner_product)) => format!(
+                "{} + {} -> {} + {}",
+                registry.name(self.reactant),
+                registry.name(partner),
+                registry.name(self.product),
+                registry.name(partner_product)
+            ),
+            _ => format!(
+                "{} -> {}",
+                registry.name(self.reactant),
+                registry.name(self.product)
+            ),
+        }
+    }
+}
+
+/// Error returned when a reaction rule cannot be parsed
+#[derive(Debug)]
+pub struct ReactionParseError {
+    /// Description of the problem
+    pub message: String,
+}
+
+impl Display for ReactionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid reaction: {}", self.message)
+    }
+}
+
+impl Error for ReactionParseError {}
+
+/// Reaction rules evaluated every step
+///
+/// Rules are addressed by their index in insertion order. When several
+/// rules could fire for a particle in the same step, the first one in
+/// neighbor order wins, and a particle takes part in at most one
+/// reaction per step.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ReactionTable {
+    rules: Vec<ReactionRule>,
+}
+
+impl ReactionTable {
+    /// Creates an empty reaction table
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Returns the number of rules
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns true if there are no rules
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns all rules in insertion order
+    #[must_use]
+    pub fn rules(&self) -> &[ReactionRule] {
+        &self.rules
+    }
+
+    /// Adds a rule and returns its index
+    pub fn add(&mut self, rule: ReactionRule) -> usize {
+        self.rules.push(rule);
+        self.rules.len() - 1
+    }
+
+    /// Removes and returns the rule at `index`
+    pub fn remove(&mut self, index: usize) -> Option<ReactionRule> {
+        (index < self.rules.len()).then(|| self.rules.remove(index))
+    }
+
+    /// Removes all rules
+    pub fn clear(&mut self) {
+        self.rules.clear();
+    }
+
+    /// Returns the largest partner distance of all rules
+    ///
+    /// Rules without a distance of their own use `contact_distance`.
+    #[must_use]
+    pub fn max_distance(&self, contact_distance: f32) -> f32 {
+        self.rules
+            .iter()
+            .filter(|rule| !rule.is_spontaneous())
+            .map(|rule| rule.distance.unwrap_or(contact_distance))
+            .fold(0.0, f32::max)
+    }
+
+    /// Loads reaction rules from a CSV file
+    ///
+    /// CSV format:
+    /// - First row: headers (`reaction,distance,probability`)
+    /// - Subsequent rows: `A + B -> A + A,20,0.01` or `A -> B,,0.001`
+    ///
+    /// An empty distance uses the contact distance.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or a row is invalid
+    pub fn from_csv_file(
+        path: &str,
+        registry: &SpeciesRegistry,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .flexible(true)
+            .from_path(path)?;
+
+        let mut table = Self::new();
+        for result in rdr.records() {
+            let record = result?;
+            let equation = record.get(0).unwrap_or_default().trim();
+            if equation.is_empty() {
+                continue;
+            }
+            let distance = record.get(1).unwrap_or_default().trim();
+            let distance = if distance.is_empty() {
+                None
+            } else {
+                Some(distance.parse()?)
+            };
+            let probability = record
+                .get(2)
+                .ok_or_else(|| invalid(format!("{equation} has no probability")))?
+                .trim()
+                .parse()?;
+            table.add(ReactionRule::parse(
+                equation,
+                distance,
+                probability,
+                registry,
+            )?);
+        }
+
+        bevy::log::info!("Loaded {} reactions from {}", table.len(), path);
+        Ok(table)
+    }
+
+    /// Saves reaction rules to a CSV file
+    ///
+    /// Writes the format read by [`from_csv_file`](Self::from_csv_file).
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be created or written to
+    pub fn to_csv_file(
+        &self,
+        path: &str,
+        registry: &SpeciesRegistry,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = csv::Writer::from_path(path)?;
+        wtr.write_record(["reaction", "distance", "probability"])?;
+        for rule in &self.rules {
+            wtr.write_record([
+                rule.equation(registry),
+                rule.distance.map(|d| d.to_string()).unwrap_or_default(),
+                rule.probability.to_string(),
+            ])?;
+        }
+        wtr.flush()?;
+        bevy::log::info!("Saved {} reactions to {}", self.len(), path);
+        Ok(())
+    }
+}
+
+/// Creates a parse error with a message
+const fn invalid(message: String) -> ReactionParseError {
+    ReactionParseError { message }
+}
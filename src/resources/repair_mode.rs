@@ -0,0 +1,66 @@
+//! Repair modes for broken particles
+//!
+//! Defines what happens to a particle whose position or velocity became
+//! non-finite (NaN or infinite) during a step.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Repair applied to particles with a non-finite position or velocity
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RepairMode {
+    /// Put the particle back where it was at the start of the step and stop it
+    #[default]
+    #[serde(rename = "quarantine")]
+    Quarantine,
+    /// Move the particle to a random position inside the map and stop it
+    #[serde(rename = "respawn")]
+    Respawn,
+}
+
+impl RepairMode {
+    /// Returns string representation of this repair mode
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Quarantine => "quarantine",
+            Self::Respawn => "respawn",
+        }
+    }
+}
+
+impl Display for RepairMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an invalid repair mode string
+#[derive(Debug)]
+pub struct RepairModeError;
+
+impl Display for RepairModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid repair mode. Expected one of: quarantine, respawn"
+        )
+    }
+}
+
+impl Error for RepairModeError {}
+
+impl FromStr for RepairMode {
+    type Err = RepairModeError;
+
+    fn from_str(s: &str) -> Result<Self, RepairModeError> {
+        match s.to_lowercase().as_str() {
+            "quarantine" => Ok(Self::Quarantine),
+            "respawn" => Ok(Self::Respawn),
+            _ => Err(RepairModeError),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! Numerical health counters of the simulation
+//!
+//! Counts how often the simulation had to step in to keep the state
+//! finite. Reported by the `print health` console command.
+
+use bevy::ecs::resource::Resource;
+
+/// Numerical health counters of the simulation
+///
+/// All counters are totals since the simulation started, except
+/// `last_step_repairs`.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationHealth {
+    /// Number of simulation steps checked
+    pub steps: u64,
+    /// Number of particle pairs found at the same position
+    pub coincident_pairs: u64,
+    /// Number of times a velocity was limited to `max_speed`
+    pub speed_caps: u64,
+    /// Number of particles repaired after becoming non-finite
+    pub repairs: u64,
+    /// Number of particles repaired in the most recent step
+    pub last_step_repairs: u64,
+}
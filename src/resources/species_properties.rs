@@ -0,0 +1,193 @@
+//! Per-species physical properties
+//!
+//! Lets a species override the global damping, speed cap and collision
+//! distance, and gives it its own mass and render size.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Default mass of a species
+pub const DEFAULT_MASS: f32 = 1.0;
+
+/// Default render radius of a particle
+pub const DEFAULT_SIZE: f32 = 5.0;
+
+/// Physical properties of one species
+///
+/// Optional values fall back to the matching [`ParticleConfig`] setting
+/// when unset.
+///
+/// [`ParticleConfig`]: crate::resources::ParticleConfig
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesProperties {
+    /// Mass, accelerations are forces divided by it
+    pub mass: f32,
+    /// Velocity half-life, overriding `config.dt_half`
+    pub dt_half: Option<f32>,
+    /// Speed cap, overriding `config.max_speed`
+    pub max_speed: Option<f32>,
+    /// Collision radius
+    ///
+    /// Two particles collide below the sum of their radii. Species
+    /// without one use half of `config.collision_ratio * r`.
+    pub collision_radius: Option<f32>,
+    /// Render radius
+    pub size: f32,
+}
+
+impl Default for SpeciesProperties {
+    fn default() -> Self {
+        Self {
+            mass: DEFAULT_MASS,
+            dt_half: None,
+            max_speed: None,
+            collision_radius: None,
+            size: DEFAULT_SIZE,
+        }
+    }
+}
+
+impl SpeciesProperties {
+    /// Returns true if no property differs from its default
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns the value of a property, or `None` if an optional one is unset
+    #[must_use]
+    pub const fn get(&self, property: SpeciesProperty) -> Option<f32> {
+        match property {
+            SpeciesProperty::Mass => Some(self.mass),
+            SpeciesProperty::DtHalf => self.dt_half,
+            SpeciesProperty::MaxSpeed => self.max_speed,
+            SpeciesProperty::CollisionRadius => self.collision_radius,
+            SpeciesProperty::Size => Some(self.size),
+        }
+    }
+
+    /// Checks that every set property is positive
+    ///
+    /// Only `max_speed` may be infinite, meaning no speed cap. Shared by
+    /// the CSV loader, config files, snapshots and the `species` console
+    /// command.
+    ///
+    /// # Errors
+    /// Returns a message naming the first invalid property
+    pub fn validate(&self) -> Result<(), String> {
+        SpeciesProperty::all()
+            .into_iter()
+            .find(|property| {
+                self.get(*property)
+                    .is_some_and(|value| !property.accepts(value))
+            })
+            .map_or(Ok(()), |property| {
+                Err(format!("{property} must be a positive number"))
+            })
+    }
+
+    /// Sets a property, `None` restores its default
+    pub fn set(&mut self, property: SpeciesProperty, value: Option<f32>) {
+        match property {
+            SpeciesProperty::Mass => self.mass = value.unwrap_or(DEFAULT_MASS),
+            SpeciesProperty::DtHalf => self.dt_half = value,
+            SpeciesProperty::MaxSpeed => self.max_speed = value,
+            SpeciesProperty::CollisionRadius => self.collision_radius = value,
+            SpeciesProperty::Size => self.size = value.unwrap_or(DEFAULT_SIZE),
+        }
+    }
+}
+
+/// Name of a field of [`SpeciesProperties`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeciesProperty {
+    /// [`SpeciesProperties::mass`]
+    Mass,
+    /// [`SpeciesProperties::dt_half`]
+    DtHalf,
+    /// [`SpeciesProperties::max_speed`]
+    MaxSpeed,
+    /// [`SpeciesProperties::collision_radius`]
+    CollisionRadius,
+    /// [`SpeciesProperties::size`]
+    Size,
+}
+
+impl SpeciesProperty {
+    /// Returns all properties in display order
+    #[must_use]
+    pub const fn all() -> [Self; 5] {
+        [
+            Self::Mass,
+            Self::DtHalf,
+            Self::MaxSpeed,
+            Self::CollisionRadius,
+            Self::Size,
+        ]
+    }
+
+    /// Returns string representation of this property
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mass => "mass",
+            Self::DtHalf => "dt_half",
+            Self::MaxSpeed => "max_speed",
+            Self::CollisionRadius => "collision_radius",
+            Self::Size => "size",
+        }
+    }
+
+    /// Returns true if `value` is a valid value of this property
+    ///
+    /// Values must be positive; only `max_speed` may be infinite.
+    #[must_use]
+    pub fn accepts(&self, value: f32) -> bool {
+        value > 0.0 && (value.is_finite() || *self == Self::MaxSpeed)
+    }
+
+    /// Returns true if the property can be unset to follow the config
+    #[must_use]
+    pub const fn is_optional(&self) -> bool {
+        matches!(self, Self::DtHalf | Self::MaxSpeed | Self::CollisionRadius)
+    }
+}
+
+impl Display for SpeciesProperty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an invalid species property string
+#[derive(Debug)]
+pub struct SpeciesPropertyError;
+
+impl Display for SpeciesPropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid species property. Expected one of: mass, dt_half, max_speed, collision_radius, size"
+        )
+    }
+}
+
+impl Error for SpeciesPropertyError {}
+
+impl FromStr for SpeciesProperty {
+    type Err = SpeciesPropertyError;
+
+    fn from_str(s: &str) -> Result<Self, SpeciesPropertyError> {
+        match s.to_lowercase().as_str() {
+            "mass" => Ok(Self::Mass),
+            "dt_half" | "half_life" | "friction" => Ok(Self::DtHalf),
+            "max_speed" => Ok(Self::MaxSpeed),
+            "collision_radius" => Ok(Self::CollisionRadius),
+            "size" => Ok(Self::Size),
+            _ => Err(SpeciesPropertyError),
+        }
+    }
+}
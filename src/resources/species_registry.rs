@@ -0,0 +1,232 @@
+//! Registry of particle species
+//!
+//! Defines how many particle types exist and the name, color and physical
+//! properties of each.
+//! [`ParticleType`] components index into this registry.
+
+use crate::components::{ParticleType, ParticleTypeError};
+use crate::resources::SpeciesProperties;
+use bevy::color::Color;
+use bevy::color::Srgba;
+use bevy::color::palettes::tailwind::{
+    AMBER_500, BLUE_500, CYAN_500, EMERALD_500, FUCHSIA_500, GREEN_500, INDIGO_500, LIME_500,
+    ORANGE_500, PINK_500, PURPLE_500, RED_500, ROSE_500, SKY_500, TEAL_500, VIOLET_500, YELLOW_500,
+};
+use bevy::ecs::resource::Resource;
+
+/// Names and colors of the built-in species, in registry order
+const PALETTE: [(&str, Srgba); 17] = [
+    ("Amber", AMBER_500),
+    ("Blue", BLUE_500),
+    ("Cyan", CYAN_500),
+    ("Emerald", EMERALD_500),
+    ("Fuchsia", FUCHSIA_500),
+    ("Green", GREEN_500),
+    ("Indigo", INDIGO_500),
+    ("Lime", LIME_500),
+    ("Orange", ORANGE_500),
+    ("Pink", PINK_500),
+    ("Purple", PURPLE_500),
+    ("Red", RED_500),
+    ("Rose", ROSE_500),
+    ("Sky", SKY_500),
+    ("Teal", TEAL_500),
+    ("Violet", VIOLET_500),
+    ("Yellow", YELLOW_500),
+];
+
+/// One particle species
+#[derive(Debug, Clone, PartialEq)]
+pub struct Species {
+    /// Display name, matched case-insensitively by console commands and CSV files
+    pub name: String,
+    /// Render color
+    pub color: Color,
+    /// Mass, damping, speed cap, collision radius and render size
+    pub properties: SpeciesProperties,
+}
+
+impl Species {
+    /// Creates a species with a name, color and default properties
+    #[must_use]
+    pub fn new(name: impl Into<String>, color: Color) -> Self {
+        Self {
+            name: name.into(),
+            color,
+            properties: SpeciesProperties::default(),
+        }
+    }
+}
+
+/// Registry of particle species
+///
+/// Holds any number of species. The [`ParticleType`] of a particle is
+/// the index of its species here, and the
+/// [`ParticleInteractionTable`](crate::resources::ParticleInteractionTable)
+/// is sized to match.
+///
+/// The default registry contains the 17 built-in species (Amber to
+/// Yellow).
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SpeciesRegistry {
+    species: Vec<Species>,
+}
+
+impl Default for SpeciesRegistry {
+    fn default() -> Self {
+        Self::generate(PALETTE.len())
+    }
+}
+
+impl SpeciesRegistry {
+    /// Creates an empty registry
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            species: Vec::new(),
+        }
+    }
+
+    /// Creates a registry with `count` species
+    ///
+    /// The first 17 are the built-in species; further species are named
+    /// `S18`, `S19`, ... and get evenly spread hues.
+    #[must_use]
+    pub fn generate(count: usize) -> Self {
+        Self {
+            species: (0..count)
+                .map(|index| Species::new(default_name(index), default_color(index)))
+                .collect(),
+        }
+    }
+
+    /// Creates a registry from species names, in order
+    ///
+    /// Names already present in `self` keep their color and properties;
+    /// new names get the default color for their position.
+    #[must_use]
+    pub fn with_names<S: AsRef<str>>(&self, names: &[S]) -> Self {
+        Self {
+            species: names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    let name = name.as_ref().trim();
+                    self.find(name)
+                        .and_then(|existing| self.get(existing))
+                        .map_or_else(
+                            || Species::new(name, default_color(index)),
+                            |existing| Species {
+                                name: name.to_string(),
+                                ..existing.clone()
+                            },
+                        )
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the number of species
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.species.len()
+    }
+
+    /// Returns true if no species are registered
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.species.is_empty()
+    }
+
+    /// Returns all particle types in registry order
+    pub fn types(&self) -> impl Iterator<Item = ParticleType> + use<> {
+        (0..self.len()).map(ParticleType::new)
+    }
+
+    /// Returns the species of a particle type
+    #[must_use]
+    pub fn get(&self, particle_type: ParticleType) -> Option<&Species> {
+        self.species.get(particle_type.index())
+    }
+
+    /// Returns the species of a particle type for modification
+    pub fn get_mut(&mut self, particle_type: ParticleType) -> Option<&mut Species> {
+        self.species.get_mut(particle_type.index())
+    }
+
+    /// Returns the name of a particle type, or `"?"` if it is not registered
+    #[must_use]
+    pub fn name(&self, particle_type: ParticleType) -> &str {
+        self.get(particle_type)
+            .map_or("?", |species| species.name.as_str())
+    }
+
+    /// Returns the color of a particle type, or white if it is not registered
+    #[must_use]
+    pub fn color(&self, particle_type: ParticleType) -> Color {
+        self.get(particle_type)
+            .map_or(Color::WHITE, |species| species.color)
+    }
+
+    /// Returns the properties of a particle type, or the defaults if it is
+    /// not registered
+    #[must_use]
+    pub fn properties(&self, particle_type: ParticleType) -> SpeciesProperties {
+        self.get(particle_type)
+            .map_or_else(SpeciesProperties::default, |species| species.properties)
+    }
+
+    /// Finds a particle type by name (case-insensitive)
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<ParticleType> {
+        self.species
+            .iter()
+            .position(|species| species.name.eq_ignore_ascii_case(name.trim()))
+            .map(ParticleType::new)
+    }
+
+    /// Parses a particle type from its name
+    ///
+    /// # Errors
+    /// Returns an error if no species has this name
+    pub fn parse(&self, name: &str) -> Result<ParticleType, ParticleTypeError> {
+        self.find(name).ok_or_else(|| ParticleTypeError {
+            name: name.to_string(),
+        })
+    }
+
+    /// Adds a species and returns its particle type
+    ///
+    /// Without a color the default color for its position is used.
+    pub fn add(&mut self, name: impl Into<String>, color: Option<Color>) -> ParticleType {
+        let index = self.len();
+        self.species.push(Species::new(
+            name,
+            color.unwrap_or_else(|| default_color(index)),
+        ));
+        ParticleType::new(index)
+    }
+}
+
+/// Returns the default name of the species at `index`
+fn default_name(index: usize) -> String {
+    PALETTE.get(index).map_or_else(
+        || format!("S{}", index + 1),
+        |(name, _)| (*name).to_string(),
+    )
+}
+
+/// Returns the default color of the species at `index`
+///
+/// Built-in species use the tailwind palette; later ones step around
+/// the hue circle by the golden angle so neighbors stay distinct.
+fn default_color(index: usize) -> Color {
+    PALETTE.get(index).map_or_else(
+        || {
+            #[allow(clippy::cast_precision_loss)]
+            let hue = (index as f32 * 137.508) % 360.0;
+            Color::hsl(hue, 0.7, 0.55)
+        },
+        |(_, color)| Color::Srgba(*color),
+    )
+}
@@ -0,0 +1,44 @@
+//! Startup settings
+//!
+//! Settings of the config file that only matter when the app starts or
+//! that name files, rather than tune the physics.
+
+use bevy::ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+/// Default interaction matrix file
+pub const DEFAULT_MATRIX_PATH: &str = "particle_interactions.csv";
+
+/// Default reaction rules file
+pub const DEFAULT_REACTIONS_PATH: &str = "reactions.csv";
+
+/// Startup settings
+///
+/// `matrix` is read at startup and by `reset_interaction`, and is the
+/// default file of `save_interaction`. `reactions` is read at startup if
+/// it exists and is the default file of `reaction save` and `reaction
+/// load`. A `snapshot` replaces the particles spawned at startup once the
+/// app is set up.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StartupConfig {
+    /// Interaction matrix CSV file, whose header defines the species
+    pub matrix: String,
+    /// Reaction rules CSV file, skipped if it does not exist
+    pub reactions: String,
+    /// Start with physics updates paused (toggle with T)
+    pub paused: bool,
+    /// Snapshot file to load at startup
+    pub snapshot: Option<String>,
+}
+
+impl Default for StartupConfig {
+    fn default() -> Self {
+        Self {
+            matrix: DEFAULT_MATRIX_PATH.to_string(),
+            reactions: DEFAULT_REACTIONS_PATH.to_string(),
+            paused: false,
+            snapshot: None,
+        }
+    }
+}
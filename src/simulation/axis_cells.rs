@@ -0,0 +1,115 @@
+//! Layout of the spatial cells along one axis
+
+use crate::resources::BoundaryMode;
+
+/// Largest number of cells along one axis
+///
+/// Keeps a 3D grid at about two million cells however small the
+/// interaction radius or large the map is.
+const MAX_CELLS_PER_AXIS: usize = 128;
+
+/// Layout of the spatial cells along one axis
+///
+/// Open axes use cells of size `r` anchored at the origin and cover the
+/// map extent. Positions outside the map are clamped into the outermost
+/// cells, which never separates two particles that are within `r` of
+/// each other. Periodic axes split the map into a whole number of cells
+/// (each at least `r` wide) so that neighbor lookups can wrap around
+/// the seam.
+///
+/// Cells grow beyond `r` when the axis would otherwise need more than
+/// [`MAX_CELLS_PER_AXIS`] of them. A radius or extent that leaves no
+/// usable cell size (zero, negative, infinite or NaN) gives a single cell.
+#[derive(Debug, Clone, Default)]
+pub struct AxisCells {
+    origin: f32,
+    size: f32,
+    offset: i32,
+    len: i32,
+    wrap: bool,
+}
+
+impl AxisCells {
+    /// Creates the cell layout for one axis of the map
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(mode: BoundaryMode, extent: f32, r: f32) -> Self {
+        let extent = extent.max(0.0);
+        #[allow(clippy::cast_precision_loss)]
+        let max_cells = MAX_CELLS_PER_AXIS as f32;
+        let r = r.max(extent / max_cells);
+        if !(r > 0.0 && r.is_finite()) {
+            return Self {
+                origin: 0.0,
+                size: 1.0,
+                offset: 0,
+                len: 1,
+                wrap: false,
+            };
+        }
+        if mode.is_wrap() && extent > 0.0 {
+            let count = (extent / r).floor().clamp(1.0, max_cells);
+            Self {
+                origin: -extent / 2.0,
+                size: extent / count,
+                offset: 0,
+                len: count as i32,
+                wrap: true,
+            }
+        } else {
+            let half = extent / 2.0;
+            let first = (-half / r).floor() as i32;
+            let last = (half / r).floor() as i32;
+            Self {
+                origin: 0.0,
+                size: r,
+                offset: first,
+                len: last.saturating_sub(first).saturating_add(1).max(1),
+                wrap: false,
+            }
+        }
+    }
+
+    /// Returns the number of cells along this axis
+    #[allow(clippy::cast_sign_loss)]
+    pub const fn count(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns the cell index containing `value`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn cell(&self, value: f32) -> usize {
+        let cell = ((value - self.origin) / self.size).floor() as i32;
+        let cell = cell.saturating_sub(self.offset);
+        let cell = if self.wrap {
+            cell.rem_euclid(self.len)
+        } else {
+            cell.clamp(0, self.len - 1)
+        };
+        cell as usize
+    }
+
+    /// Returns the cell indices adjacent to `cell`, including itself
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    pub fn neighbors(&self, cell: usize) -> impl Iterator<Item = usize> {
+        let cell = cell as i32;
+        let len = self.len;
+        let wrap = self.wrap;
+        // With fewer than three periodic cells the wrapped neighbors
+        // would repeat, so only visit each distinct cell once.
+        let span = if wrap && len < 3 { 0..=len - 1 } else { -1..=1 };
+        span.filter_map(move |offset| {
+            let neighbor = cell + offset;
+            if wrap {
+                Some(neighbor.rem_euclid(len) as usize)
+            } else if (0..len).contains(&neighbor) {
+                Some(neighbor as usize)
+            } else {
+                None
+            }
+        })
+    }
+}
@@ -0,0 +1,296 @@
+//! Distance-force profiles for particle interactions
+//!
+//! A [`ForceKernel`] turns the distance between two particles into a
+//! scalar force along the line between them. All kernels share the same
+//! structure: a repulsive core below the collision distance
+//! `collision_ratio * r`, an interaction region scaled by the pair
+//! strength up to `r`, and no force beyond `r`.
+
+use crate::resources::{ForceKernelKind, InteractionProfile, ParticleConfig};
+use std::f32::consts::TAU;
+
+/// Distance-force profile for particle interactions
+///
+/// Positive values pull the target towards the source, negative values
+/// push it away.
+pub trait ForceKernel {
+    /// Returns the scalar force at `distance`
+    ///
+    /// `r` is the interaction distance and `strength` the entry of the
+    /// interaction table for this pair of particle types.
+    fn force(&self, distance: f32, r: f32, strength: f32) -> f32;
+
+    /// Returns the kernel to use for a pair with its own profile
+    ///
+    /// The radius and strength of a profile are always honored by the
+    /// caller. Kernels that can also follow a per-pair collision ratio
+    /// or kernel choice override this; by default the kernel is reused
+    /// unchanged.
+    #[must_use]
+    fn for_profile(&self, _profile: &InteractionProfile) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        self.clone()
+    }
+}
+
+/// Original piecewise linear kernel
+///
+/// Linear repulsion ramp below the collision distance `d1 = r * b`, then
+/// a triangle with its corner at `d2 = r * (1 - b) / 2` that falls back
+/// to zero at `r`, where `b` is the collision ratio.
+///
+/// For `b < 1/3` the force rises from zero at `d1` to `strength` at `d2`.
+/// For larger ratios, including the default 0.35, `d2` lies below `d1`:
+/// the rising edge is never reached, and the force jumps to
+/// `strength * (r - d1) / (r - d2)` at `d1` and falls linearly from
+/// there. This matches the original simulation and is kept as is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassicKernel {
+    /// Collision distance as a fraction of `r`
+    pub collision_ratio: f32,
+    /// Force magnitude for collision repulsion
+    pub repel_force: f32,
+}
+
+impl ForceKernel for ClassicKernel {
+    fn force(&self, distance: f32, r: f32, strength: f32) -> f32 {
+        let b = self.collision_ratio;
+        let d1 = r * b;
+        let d2 = r * (1.0 - b) / 2.0;
+        let d3 = r;
+
+        if distance < d1 {
+            (distance - d1) / d1 * self.repel_force
+        } else if distance >= d3 {
+            0.0
+        } else if distance >= d2 {
+            strength * (d3 - distance) / (d3 - d2)
+        } else {
+            strength * (distance - d1) / (d2 - d1)
+        }
+    }
+}
+
+/// Smooth kernel
+///
+/// Quadratic repulsion below the collision distance and a raised-cosine
+/// bump in the interaction region, so the force and its slope are
+/// continuous everywhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothKernel {
+    /// Collision distance as a fraction of `r`
+    pub collision_ratio: f32,
+    /// Force magnitude for collision repulsion
+    pub repel_force: f32,
+}
+
+impl ForceKernel for SmoothKernel {
+    fn force(&self, distance: f32, r: f32, strength: f32) -> f32 {
+        let d1 = r * self.collision_ratio;
+
+        if distance < d1 {
+            let overlap = 1.0 - distance / d1;
+            -self.repel_force * overlap * overlap
+        } else if distance >= r {
+            0.0
+        } else {
+            let t = (distance - d1) / (r - d1);
+            strength * 0.5 * (1.0 - (TAU * t).cos())
+        }
+    }
+}
+
+/// Lennard-Jones-like kernel
+///
+/// Uses `s = collision distance / distance`. The core pushes with
+/// `repel_force * (s^12 - s^6)`, capped at `repel_force`. Beyond the
+/// collision distance the tail `4 * (s^6 - s^12)` peaks at 1, is scaled
+/// by `strength` and shifted so it reaches zero exactly at `r`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LennardJonesKernel {
+    /// Collision distance as a fraction of `r`
+    pub collision_ratio: f32,
+    /// Force magnitude for collision repulsion
+    pub repel_force: f32,
+}
+
+impl LennardJonesKernel {
+    fn tail(s: f32) -> f32 {
+        let s6 = s.powi(6);
+        4.0 * s6.mul_add(-s6, s6)
+    }
+}
+
+impl ForceKernel for LennardJonesKernel {
+    fn force(&self, distance: f32, r: f32, strength: f32) -> f32 {
+        let d1 = r * self.collision_ratio;
+
+        if distance >= r {
+            0.0
+        } else if distance < d1 {
+            let s6 = (d1 / distance).powi(6);
+            -self.repel_force * s6.mul_add(s6, -s6).min(1.0)
+        } else {
+            strength * (Self::tail(d1 / distance) - Self::tail(d1 / r))
+        }
+    }
+}
+
+/// Gaussian kernel
+///
+/// Linear repulsion ramp below the collision distance and a Gaussian
+/// bump centered halfway between collision distance and `r`, with a
+/// standard deviation of one sixth of that region. Unlike the
+/// [`ClassicKernel`] triangle, the peak always lies in that region.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GaussianKernel {
+    /// Collision distance as a fraction of `r`
+    pub collision_ratio: f32,
+    /// Force magnitude for collision repulsion
+    pub repel_force: f32,
+}
+
+impl ForceKernel for GaussianKernel {
+    fn force(&self, distance: f32, r: f32, strength: f32) -> f32 {
+        let d1 = r * self.collision_ratio;
+
+        if distance < d1 {
+            (distance - d1) / d1 * self.repel_force
+        } else if distance >= r {
+            0.0
+        } else {
+            let center = f32::midpoint(d1, r);
+            let sigma = (r - d1) / 6.0;
+            let z = (distance - center) / sigma;
+            strength * (-0.5 * z * z).exp()
+        }
+    }
+}
+
+/// Kernel selected at runtime from a [`ParticleConfig`]
+///
+/// Dispatches to the kernel named by `config.kernel` with the
+/// configured collision ratio and repel force.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfiguredKernel {
+    kind: ForceKernelKind,
+    collision_ratio: f32,
+    repel_force: f32,
+}
+
+impl ConfiguredKernel {
+    /// Creates the kernel selected by the configuration
+    #[must_use]
+    pub const fn from_config(config: &ParticleConfig) -> Self {
+        Self {
+            kind: config.kernel,
+            collision_ratio: config.collision_ratio,
+            repel_force: config.repel_force,
+        }
+    }
+}
+
+impl ForceKernel for ConfiguredKernel {
+    fn for_profile(&self, profile: &InteractionProfile) -> Self {
+        Self {
+            kind: profile.kernel.unwrap_or(self.kind),
+            collision_ratio: profile.collision_ratio.unwrap_or(self.collision_ratio),
+            repel_force: self.repel_force,
+        }
+    }
+
+    fn force(&self, distance: f32, r: f32, strength: f32) -> f32 {
+        let (collision_ratio, repel_force) = (self.collision_ratio, self.repel_force);
+        match self.kind {
+            ForceKernelKind::Classic => ClassicKernel {
+                collision_ratio,
+                repel_force,
+            }
+            .force(distance, r, strength),
+            ForceKernelKind::Smooth => SmoothKernel {
+                collision_ratio,
+                repel_force,
+            }
+            .force(distance, r, strength),
+            ForceKernelKind::LennardJones => LennardJonesKernel {
+                collision_ratio,
+                repel_force,
+            }
+            .force(distance, r, strength),
+            ForceKernelKind::Gaussian => GaussianKernel {
+                collision_ratio,
+                repel_force,
+            }
+            .force(distance, r, strength),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Force of the original per-frame update, before kernels existed
+    fn original_force(distance: f32, r: f32, strength: f32, repel_force: f32) -> f32 {
+        let b = 0.35;
+        let d1 = r * b;
+        let d2 = r * (1.0 - b) / 2.0;
+        let d3 = r;
+        if distance < d1 {
+            (distance - d1) / d1 * repel_force
+        } else if distance >= d3 {
+            0.0
+        } else if distance >= d2 {
+            strength * (d3 - distance) / (d3 - d2)
+        } else {
+            strength * (distance - d1) / (d2 - d1)
+        }
+    }
+
+    #[test]
+    fn classic_kernel_matches_original_force() {
+        let kernel = ClassicKernel {
+            collision_ratio: 0.35,
+            repel_force: 1.5,
+        };
+        for step in 0..=400 {
+            #[allow(clippy::cast_precision_loss)]
+            let distance = step as f32;
+            for strength in [-0.8, 0.0, 1.0] {
+                assert_eq!(
+                    kernel.force(distance, 300.0, strength).to_bits(),
+                    original_force(distance, 300.0, strength, 1.5).to_bits(),
+                    "distance {distance}, strength {strength}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn classic_kernel_values_are_pinned() {
+        let kernel = ClassicKernel {
+            collision_ratio: 0.35,
+            repel_force: 1.0,
+        };
+        // d1 = 105 and d2 = 97.5, so the force jumps at the collision
+        // distance and falls linearly to zero at r.
+        let cases = [
+            (0.0, -1.0),
+            (52.5, -0.5),
+            (104.9, -0.000_952_4),
+            (105.0, 0.962_963),
+            (200.0, 0.493_827),
+            (299.0, 0.004_938),
+            (300.0, 0.0),
+        ];
+        for (distance, expected) in cases {
+            let force = kernel.force(distance, 300.0, 1.0);
+            assert!(
+                (force - expected).abs() < 1e-5,
+                "distance {distance}: {force} != {expected}"
+            );
+        }
+    }
+}
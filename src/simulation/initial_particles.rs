@@ -0,0 +1,35 @@
+//! Initial particle layout
+//!
+//! Used by the `spawn_particle` system, which runs in the app and in
+//! headless runs alike, so both start from the same particles for the
+//! same seed and configuration.
+
+use crate::components::ParticleType;
+use crate::resources::{ParticleConfig, SimRng};
+use bevy::math::Vec3;
+use rand::Rng;
+
+/// Returns the types and positions of the initial particles
+///
+/// Draws `config.init_particle_num` particles with random positions
+/// within the map boundaries (a box in 3D mode) and random types among
+/// the first `species` types. The generator is reseeded from
+/// `config.seed` first, so the same seed and configuration always
+/// produce the same layout. Without species no particles are returned.
+#[must_use]
+pub fn initial_particles(
+    config: &ParticleConfig,
+    species: usize,
+    rng: &mut SimRng,
+) -> Vec<(ParticleType, Vec3)> {
+    rng.reseed(config.seed);
+    if species == 0 {
+        return Vec::new();
+    }
+    (0..config.init_particle_num)
+        .map(|_| {
+            let position = config.random_position(rng);
+            (ParticleType::new(rng.random_range(0..species)), position)
+        })
+        .collect()
+}
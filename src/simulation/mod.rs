@@ -0,0 +1,19 @@
+//! Simulation module
+//!
+//! This module contains the Bevy-free simulation core. It can be driven
+//! from plain Rust code (analysis tools, tests) without an `App`.
+
+mod axis_cells;
+mod force_kernel;
+mod initial_particles;
+mod obstacle_grid;
+mod particle_simulation;
+mod spatial_grid;
+
+pub use force_kernel::{
+    ClassicKernel, ConfiguredKernel, ForceKernel, GaussianKernel, LennardJonesKernel, SmoothKernel,
+};
+pub use initial_particles::initial_particles;
+pub use obstacle_grid::{ObstacleGrid, ObstacleHit, SKIN, bounce};
+pub use particle_simulation::{Simulation, kinetic_temperature};
+pub use spatial_grid::SpatialGrid;
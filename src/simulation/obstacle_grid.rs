@@ -0,0 +1,403 @@
+//! Uniform grid over static obstacles
+//!
+//! Splits every obstacle into straight edges and circles and buckets
+//! them into cells, so a particle only tests the obstacles near its path.
+
+use crate::resources::{Obstacle, ObstacleShape};
+use bevy::math::Vec2;
+
+/// Target cell size of the obstacle grid
+const CELL_SIZE: f32 = 128.0;
+
+/// Largest number of cells along one axis
+const MAX_CELLS_PER_AXIS: usize = 256;
+
+/// Distance particles keep from an obstacle surface after a collision
+pub const SKIN: f32 = 0.01;
+
+/// Boundary piece of an obstacle
+#[derive(Debug, Clone, Copy)]
+enum Primitive {
+    Edge { a: Vec2, b: Vec2 },
+    Circle { center: Vec2, radius: f32 },
+}
+
+impl Primitive {
+    fn bounds(self) -> (Vec2, Vec2) {
+        match self {
+            Self::Edge { a, b } => (a.min(b), a.max(b)),
+            Self::Circle { center, radius } => {
+                (center - Vec2::splat(radius), center + Vec2::splat(radius))
+            }
+        }
+    }
+
+    /// Returns where the path `from + t * delta` first enters the
+    /// primitive as `(t, normal)` with `t` in `[0, 1]`
+    ///
+    /// The normal faces the side the path comes from.
+    fn intersect(self, from: Vec2, delta: Vec2) -> Option<(f32, Vec2)> {
+        match self {
+            Self::Edge { a, b } => {
+                let edge = b - a;
+                let denominator = delta.perp_dot(edge);
+                if denominator.abs() <= f32::EPSILON {
+                    return None;
+                }
+                let t = (a - from).perp_dot(edge) / denominator;
+                let s = (a - from).perp_dot(delta) / denominator;
+                if !(0.0..=1.0).contains(&t) || !(0.0..=1.0).contains(&s) {
+                    return None;
+                }
+                let normal = edge.perp().normalize_or_zero();
+                let normal = if normal.dot(delta) > 0.0 {
+                    -normal
+                } else {
+                    normal
+                };
+                Some((t, normal))
+            }
+            Self::Circle { center, radius } => {
+                let offset = from - center;
+                let a = delta.length_squared();
+                let half_b = offset.dot(delta);
+                let c = radius.mul_add(-radius, offset.length_squared());
+                // Paths starting inside are handled by the push-out.
+                if a <= f32::EPSILON || c < 0.0 {
+                    return None;
+                }
+                let discriminant = half_b.mul_add(half_b, -a * c);
+                if discriminant < 0.0 {
+                    return None;
+                }
+                let t = (-half_b - discriminant.sqrt()) / a;
+                if !(0.0..=1.0).contains(&t) {
+                    return None;
+                }
+                let normal = (from + delta * t - center).normalize_or_zero();
+                Some((t, normal))
+            }
+        }
+    }
+
+    /// Returns the point on the primitive closest to `point`
+    fn closest_point(self, point: Vec2) -> Vec2 {
+        match self {
+            Self::Edge { a, b } => {
+                let edge = b - a;
+                let t = (point - a).dot(edge) / edge.length_squared().max(f32::EPSILON);
+                a + edge * t.clamp(0.0, 1.0)
+            }
+            Self::Circle { center, radius } => {
+                center + (point - center).normalize_or(Vec2::X) * radius
+            }
+        }
+    }
+}
+
+/// First obstacle surface on a particle path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObstacleHit {
+    /// Fraction of the path travelled before the hit, in `[0, 1]`
+    pub t: f32,
+    /// Point of impact
+    pub point: Vec2,
+    /// Surface normal facing the incoming particle
+    pub normal: Vec2,
+    /// Index of the obstacle that was hit
+    pub obstacle: usize,
+}
+
+/// Cell ranges of a set of bounding boxes
+///
+/// The items of cell `c` are `entries[cell_start[c]..cell_start[c + 1]]`.
+#[derive(Debug, Clone, Default)]
+struct CellIndex {
+    cell_start: Vec<usize>,
+    entries: Vec<usize>,
+}
+
+/// Uniform grid over static obstacles
+///
+/// Built once per change of the obstacle set with
+/// [`ObstacleGrid::rebuild`]. Edges and circles are indexed by the cells
+/// their bounding box touches, and solid obstacles additionally by the
+/// cells of their whole area for the inside test.
+#[derive(Debug, Clone, Default)]
+pub struct ObstacleGrid {
+    obstacles: Vec<Obstacle>,
+    primitives: Vec<(Primitive, usize)>,
+    origin: Vec2,
+    cell_size: f32,
+    cells_x: usize,
+    cells_y: usize,
+    boundaries: CellIndex,
+    areas: CellIndex,
+}
+
+impl ObstacleGrid {
+    /// Creates an empty grid
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the grid holds no obstacles
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.obstacles.is_empty()
+    }
+
+    /// Returns the obstacles the grid was built from
+    #[must_use]
+    pub fn obstacles(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
+
+    /// Rebuilds the grid for a new set of obstacles
+    pub fn rebuild(&mut self, obstacles: &[Obstacle]) {
+        self.obstacles = obstacles.to_vec();
+        self.primitives.clear();
+        for (index, obstacle) in obstacles.iter().enumerate() {
+            if let ObstacleShape::Circle { center, radius } = obstacle.shape {
+                self.primitives
+                    .push((Primitive::Circle { center, radius }, index));
+            }
+            self.primitives.extend(
+                obstacle
+                    .shape
+                    .edges()
+                    .into_iter()
+                    .map(|(a, b)| (Primitive::Edge { a, b }, index)),
+            );
+        }
+
+        let (min, max) = obstacles
+            .iter()
+            .map(|obstacle| obstacle.shape.bounds())
+            .fold(
+                (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+                |(min, max), (low, high)| (min.min(low), max.max(high)),
+            );
+        if !min.is_finite() || !max.is_finite() {
+            *self = Self::default();
+            return;
+        }
+        let extent = (max - min).max(Vec2::splat(CELL_SIZE));
+        #[allow(clippy::cast_precision_loss)]
+        let max_cells = MAX_CELLS_PER_AXIS as f32;
+        self.cell_size = CELL_SIZE.max(extent.max_element() / max_cells);
+        self.origin = min;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        {
+            self.cells_x = (extent.x / self.cell_size).ceil().max(1.0) as usize;
+            self.cells_y = (extent.y / self.cell_size).ceil().max(1.0) as usize;
+        }
+
+        let boundaries: Vec<(Vec2, Vec2)> = self
+            .primitives
+            .iter()
+            .map(|(primitive, _)| primitive.bounds())
+            .collect();
+        self.boundaries = self.index(&boundaries);
+        let areas: Vec<(Vec2, Vec2)> = obstacles
+            .iter()
+            .map(|obstacle| {
+                if obstacle.shape.is_solid() {
+                    obstacle.shape.bounds()
+                } else {
+                    // Never matches a cell
+                    (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY))
+                }
+            })
+            .collect();
+        self.areas = self.index(&areas);
+    }
+
+    /// Buckets bounding boxes into cells with a counting sort
+    fn index(&self, boxes: &[(Vec2, Vec2)]) -> CellIndex {
+        let cell_count = self.cells_x * self.cells_y;
+        let mut cell_start = vec![0; cell_count + 1];
+        for (min, max) in boxes {
+            for cell in self.cells_in(*min, *max) {
+                cell_start[cell] += 1;
+            }
+        }
+        let mut total = 0;
+        for start in &mut cell_start {
+            let count = *start;
+            *start = total;
+            total += count;
+        }
+        let mut entries = vec![0; total];
+        let mut cursor = cell_start[..cell_count].to_vec();
+        for (item, (min, max)) in boxes.iter().enumerate() {
+            for cell in self.cells_in(*min, *max) {
+                entries[cursor[cell]] = item;
+                cursor[cell] += 1;
+            }
+        }
+        CellIndex {
+            cell_start,
+            entries,
+        }
+    }
+
+    /// Returns the flat indices of the cells overlapping a box
+    fn cells_in(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = usize> + use<> {
+        let cells_x = self.cells_x;
+        let range = self.cell_range(min, max);
+        range.into_iter().flat_map(move |(x0, x1, y0, y1)| {
+            (y0..=y1).flat_map(move |y| (x0..=x1).map(move |x| y * cells_x + x))
+        })
+    }
+
+    /// Returns the inclusive cell range `(x0, x1, y0, y1)` covering a box,
+    /// or `None` if the box misses the grid
+    fn cell_range(&self, min: Vec2, max: Vec2) -> Option<(usize, usize, usize, usize)> {
+        if self.cells_x == 0 || !(min.cmple(max).all()) {
+            return None;
+        }
+        let low = ((min - self.origin) / self.cell_size).floor();
+        let high = ((max - self.origin) / self.cell_size).floor();
+        #[allow(clippy::cast_precision_loss)]
+        let limit = Vec2::new((self.cells_x - 1) as f32, (self.cells_y - 1) as f32);
+        if high.x < 0.0 || high.y < 0.0 || low.x > limit.x || low.y > limit.y {
+            return None;
+        }
+        let low = low.clamp(Vec2::ZERO, limit);
+        let high = high.clamp(Vec2::ZERO, limit);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some((
+            low.x as usize,
+            high.x as usize,
+            low.y as usize,
+            high.y as usize,
+        ))
+    }
+
+    /// Returns the items of `index` in the cells overlapping a box
+    ///
+    /// Items spanning several cells are yielded once per cell.
+    fn candidates<'a>(
+        &'a self,
+        index: &'a CellIndex,
+        min: Vec2,
+        max: Vec2,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.cells_in(min, max).flat_map(move |cell| {
+            index.entries[index.cell_start[cell]..index.cell_start[cell + 1]]
+                .iter()
+                .copied()
+        })
+    }
+
+    /// Returns the first obstacle surface on the path from `from` to `to`
+    ///
+    /// Only obstacles whose index `solid` accepts are considered.
+    #[must_use]
+    pub fn first_hit(
+        &self,
+        from: Vec2,
+        to: Vec2,
+        solid: impl Fn(usize) -> bool,
+    ) -> Option<ObstacleHit> {
+        let delta = to - from;
+        self.candidates(&self.boundaries, from.min(to), from.max(to))
+            .filter_map(|item| {
+                let (primitive, obstacle) = self.primitives[item];
+                if !solid(obstacle) {
+                    return None;
+                }
+                primitive
+                    .intersect(from, delta)
+                    .map(|(t, normal)| ObstacleHit {
+                        t,
+                        point: from + delta * t,
+                        normal,
+                        obstacle,
+                    })
+            })
+            .min_by(|a, b| a.t.total_cmp(&b.t))
+    }
+
+    /// Returns the way out of a solid obstacle containing `point`
+    ///
+    /// The result holds the closest boundary point, the outward normal
+    /// and the obstacle index. `None` if no solid obstacle accepted by
+    /// `solid` contains the point.
+    #[must_use]
+    pub fn push_out(&self, point: Vec2, solid: impl Fn(usize) -> bool) -> Option<ObstacleHit> {
+        let obstacle = self
+            .candidates(&self.areas, point, point)
+            .find(|&index| solid(index) && self.obstacles[index].shape.contains(point))?;
+        let closest = self
+            .primitives
+            .iter()
+            .filter(|(_, owner)| *owner == obstacle)
+            .map(|(primitive, _)| primitive.closest_point(point))
+            .min_by(|a, b| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })?;
+        let normal = (closest - point).normalize_or(Vec2::X);
+        Some(ObstacleHit {
+            t: 0.0,
+            point: closest,
+            normal,
+            obstacle,
+        })
+    }
+
+    /// Returns the closest boundary point of every obstacle within
+    /// `range` of `point`
+    ///
+    /// Only obstacles whose index `include` accepts are considered. Each
+    /// obstacle appears once, as `(index, closest point)`.
+    #[must_use]
+    pub fn closest_points(
+        &self,
+        point: Vec2,
+        range: f32,
+        include: impl Fn(usize) -> bool,
+    ) -> Vec<(usize, Vec2)> {
+        let mut closest: Vec<(usize, Vec2, f32)> = Vec::new();
+        let reach = Vec2::splat(range);
+        for item in self.candidates(&self.boundaries, point - reach, point + reach) {
+            let (primitive, obstacle) = self.primitives[item];
+            if !include(obstacle) {
+                continue;
+            }
+            let candidate = primitive.closest_point(point);
+            let distance = candidate.distance_squared(point);
+            if distance > range * range {
+                continue;
+            }
+            match closest.iter_mut().find(|(index, _, _)| *index == obstacle) {
+                Some(entry) if distance < entry.2 => *entry = (obstacle, candidate, distance),
+                Some(_) => {}
+                None => closest.push((obstacle, candidate, distance)),
+            }
+        }
+        closest
+            .into_iter()
+            .map(|(obstacle, point, _)| (obstacle, point))
+            .collect()
+    }
+}
+
+/// Applies an impact to a velocity
+///
+/// The normal component is reversed and scaled by `restitution`, the
+/// tangential component loses `friction` of its magnitude. Velocities
+/// already leaving the surface are unchanged.
+#[must_use]
+pub fn bounce(velocity: Vec2, normal: Vec2, restitution: f32, friction: f32) -> Vec2 {
+    let normal_speed = velocity.dot(normal);
+    if normal_speed >= 0.0 {
+        return velocity;
+    }
+    let normal_part = normal * normal_speed;
+    let tangent_part = velocity - normal_part;
+    tangent_part * (1.0 - friction) - normal_part * restitution
+}
@@ -0,0 +1,1826 @@
+//! Bevy-free particle life simulation
+//!
+//! Holds the full physics state in plain vectors and advances it with
+//! [`Simulation::step`]. The `update_particle` system is a thin adapter
+//! that copies ECS components in and out of this type, which the app
+//! keeps as a resource so snapshots can save and restore its state.
+
+use crate::components::{ForceField, Genome, ParticleType};
+use crate::resources::{
+    DEFAULT_MASS, Dimension, Ecosystem, EcosystemStats, Evolution, GenomeStats, Integrator,
+    InteractionProfile, Membrane, Membranes, Obstacle, ParticleConfig, ParticleInteractionTable,
+    ReactionStats, ReactionTable, RepairMode, SimRng, SimulationHealth, SpeciesGenomes,
+    SpeciesProperties,
+};
+use crate::simulation::{ConfiguredKernel, ForceKernel, ObstacleGrid, SKIN, SpatialGrid, bounce};
+use bevy::ecs::resource::Resource;
+use bevy::math::Vec3;
+use bevy::tasks::{ComputeTaskPool, ParallelSliceMut, TaskPool};
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::f32::consts::TAU;
+
+/// Smallest number of particles handed to one task in the force phase
+const MIN_CHUNK_SIZE: usize = 256;
+
+/// Number of midpoint samples used to integrate a pair potential
+const POTENTIAL_SAMPLES: u16 = 32;
+
+/// Distance below which two particles count as coincident
+const MIN_DISTANCE: f32 = 1e-4;
+
+/// Largest collision ratio derived from species collision radii
+const MAX_COLLISION_RATIO: f32 = 0.99;
+
+/// Largest number of obstacle bounces resolved for one particle per step
+const MAX_BOUNCES: usize = 4;
+
+/// Mixed into the seed so predation draws differ from reaction draws
+const FORAGE_SALT: u64 = 0xF0A6_E5A1_7C3D_9B21;
+
+/// Integration values of one species for the current time step
+///
+/// Resolved from its [`SpeciesProperties`] and the [`ParticleConfig`]
+/// whenever forces are computed.
+///
+/// `noise` and `half_noise` are the per-axis standard deviations of the
+/// thermal kick that accompanies a decay by `decay` and `half_decay`.
+/// They follow the exact Ornstein-Uhlenbeck update
+/// `v' = decay * v + sqrt(T / m * (1 - decay²)) * ξ`, which keeps the
+/// velocity variance at `T / m` in equilibrium for any step size.
+#[derive(Debug, Clone, Copy)]
+struct Motion {
+    inverse_mass: f32,
+    max_speed: f32,
+    rate: f32,
+    decay: f32,
+    half_decay: f32,
+    noise: f32,
+    half_noise: f32,
+}
+
+impl Motion {
+    fn new(properties: &SpeciesProperties, config: &ParticleConfig) -> Self {
+        let dt_half = properties.dt_half.unwrap_or(config.dt_half);
+        let inverse_mass = properties.mass.recip();
+        let decay = 0.5f32.powf(config.dt / dt_half);
+        let half_decay = 0.5f32.powf(config.dt / (2.0 * dt_half));
+        let variance = config.temperature.max(0.0) * inverse_mass;
+        Self {
+            inverse_mass,
+            max_speed: properties.max_speed.unwrap_or(config.max_speed),
+            rate: std::f32::consts::LN_2 / dt_half,
+            decay,
+            half_decay,
+            noise: (variance * decay.mul_add(-decay, 1.0)).sqrt(),
+            half_noise: (variance * half_decay.mul_add(-half_decay, 1.0)).sqrt(),
+        }
+    }
+
+    /// Returns the resolved values of a particle type
+    ///
+    /// Types without resolved values use the default properties.
+    fn of(motions: &[Self], particle_type: ParticleType, config: &ParticleConfig) -> Self {
+        motions
+            .get(particle_type.index())
+            .copied()
+            .unwrap_or_else(|| Self::new(&SpeciesProperties::default(), config))
+    }
+
+    /// Adds a thermal kick with standard deviation `sigma` per axis
+    ///
+    /// Draws nothing when `sigma` is zero, so runs without a temperature
+    /// keep their random sequence.
+    fn kick(sigma: f32, velocity: &mut Vec3, rng: &mut SimRng, dimension: Dimension) {
+        if sigma > 0.0 {
+            let x = rng.gaussian();
+            let y = rng.gaussian();
+            let z = if dimension.is_3d() {
+                rng.gaussian()
+            } else {
+                0.0
+            };
+            *velocity += Vec3::new(x, y, z) * sigma;
+        }
+    }
+
+    /// Limits a velocity to `max_speed`
+    ///
+    /// Returns true if the velocity had to be limited.
+    fn limit_speed(&self, velocity: &mut Vec3) -> bool {
+        if velocity.length_squared() > self.max_speed * self.max_speed {
+            *velocity = velocity.clamp_length_max(self.max_speed);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Reaction found for a particle during the force pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PendingReaction {
+    /// Index of the reacting particle
+    index: usize,
+    /// Index of the rule in the reaction table
+    rule: usize,
+    /// Index of the partner particle, `None` for spontaneous rules
+    partner: Option<usize>,
+}
+
+/// Feeding and predation found for a particle during the force pass
+#[derive(Debug, Clone, Copy, Default)]
+struct Forage {
+    /// Energy per second gained from food particles in range
+    gain: f32,
+    /// Index of the prey eaten this step
+    prey: Option<usize>,
+}
+
+/// Bevy-free particle life simulation
+///
+/// Stores positions, velocities and types of all particles together with
+/// the [`ParticleInteractionTable`] and [`ParticleConfig`] that drive them.
+/// Particles are addressed by their index in insertion order.
+///
+/// In ecosystem mode particles are born and die during a step: dead
+/// particles are removed without changing the order of the others and
+/// newborns are appended, so [`Simulation::origins`] maps every particle
+/// back to the index it was added with.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Simulation {
+    /// Configuration used by [`Simulation::step`]
+    pub config: ParticleConfig,
+    /// Interaction forces between particle types
+    pub interaction_table: ParticleInteractionTable,
+    /// Physical properties of each particle type, indexed by
+    /// [`ParticleType::index`]; types past the end use the defaults
+    pub species: Vec<SpeciesProperties>,
+    /// External force fields acting on the particles
+    pub fields: Vec<ForceField>,
+    /// Species conversion rules, set with [`Simulation::set_reactions`]
+    reactions: ReactionTable,
+    /// Energy and population settings, set with [`Simulation::set_ecosystem`]
+    ecosystem: Ecosystem,
+    /// Genome settings, set with [`Simulation::set_evolution`]
+    evolution: Evolution,
+    positions: Vec<Vec3>,
+    velocities: Vec<Vec3>,
+    types: Vec<ParticleType>,
+    energies: Vec<f32>,
+    origins: Vec<Option<usize>>,
+    genomes: Vec<Genome>,
+    next_lineage: u64,
+    accelerations: Vec<Vec3>,
+    motions: Vec<Motion>,
+    grid: SpatialGrid,
+    obstacle_grid: ObstacleGrid,
+    obstacle_membranes: Vec<Option<Membrane>>,
+    membrane_range: f32,
+    start_positions: Vec<Vec3>,
+    start_velocities: Vec<Vec3>,
+    sum_positions: Vec<Vec3>,
+    sum_velocities: Vec<Vec3>,
+    rng: SimRng,
+    health: SimulationHealth,
+    find_reactions: bool,
+    pending_reactions: Vec<PendingReaction>,
+    reacted: Vec<bool>,
+    reaction_stats: ReactionStats,
+    find_forage: bool,
+    forage: Vec<Forage>,
+    alive: Vec<bool>,
+    ecosystem_stats: EcosystemStats,
+}
+
+impl Simulation {
+    /// Creates an empty simulation with the given configuration and interactions
+    #[must_use]
+    pub fn new(config: ParticleConfig, interaction_table: ParticleInteractionTable) -> Self {
+        let rng = SimRng::new(config.seed);
+        Self {
+            config,
+            interaction_table,
+            species: Vec::new(),
+            fields: Vec::new(),
+            reactions: ReactionTable::new(),
+            ecosystem: Ecosystem::new(),
+            evolution: Evolution::new(),
+            positions: Vec::new(),
+            velocities: Vec::new(),
+            types: Vec::new(),
+            energies: Vec::new(),
+            origins: Vec::new(),
+            genomes: Vec::new(),
+            next_lineage: 0,
+            accelerations: Vec::new(),
+            motions: Vec::new(),
+            grid: SpatialGrid::new(),
+            obstacle_grid: ObstacleGrid::new(),
+            obstacle_membranes: Vec::new(),
+            membrane_range: 0.0,
+            start_positions: Vec::new(),
+            start_velocities: Vec::new(),
+            sum_positions: Vec::new(),
+            sum_velocities: Vec::new(),
+            rng,
+            health: SimulationHealth::default(),
+            find_reactions: false,
+            pending_reactions: Vec::new(),
+            reacted: Vec::new(),
+            reaction_stats: ReactionStats::default(),
+            find_forage: false,
+            forage: Vec::new(),
+            alive: Vec::new(),
+            ecosystem_stats: EcosystemStats::default(),
+        }
+    }
+
+    /// Restarts the generator used to respawn broken particles
+    pub const fn reseed(&mut self, seed: u64) {
+        self.rng.reseed(seed);
+    }
+
+    /// Returns the seed of the generator used to respawn broken particles
+    #[must_use]
+    pub const fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    /// Returns the generator used for thermal noise, repairs, mutations
+    /// and birth offsets
+    #[must_use]
+    pub const fn rng(&self) -> &SimRng {
+        &self.rng
+    }
+
+    /// Returns the last lineage number handed out in evolution mode
+    #[must_use]
+    pub const fn next_lineage(&self) -> u64 {
+        self.next_lineage
+    }
+
+    /// Continues a saved run
+    ///
+    /// Restores the generator, the step count that selects the reaction
+    /// and feeding streams, and the lineage counter. The other health
+    /// counters restart from zero.
+    pub fn resume(&mut self, rng: SimRng, steps: u64, next_lineage: u64) {
+        self.rng = rng;
+        self.health = SimulationHealth {
+            steps,
+            ..SimulationHealth::default()
+        };
+        self.next_lineage = next_lineage;
+    }
+
+    /// Adds a particle and returns its index
+    ///
+    /// The particle starts with the ecosystem's initial energy and an
+    /// empty genome.
+    pub fn add_particle(
+        &mut self,
+        particle_type: ParticleType,
+        position: Vec3,
+        velocity: Vec3,
+    ) -> usize {
+        self.types.push(particle_type);
+        self.positions.push(position);
+        self.velocities.push(velocity);
+        self.energies.push(self.ecosystem.initial_energy);
+        self.origins.push(Some(self.types.len() - 1));
+        self.genomes.push(Genome::default());
+        self.types.len() - 1
+    }
+
+    /// Removes all particles while keeping the allocated storage
+    pub fn clear(&mut self) {
+        self.types.clear();
+        self.positions.clear();
+        self.velocities.clear();
+        self.energies.clear();
+        self.origins.clear();
+        self.genomes.clear();
+    }
+
+    /// Returns the number of particles
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Returns true if the simulation has no particles
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Returns the particle types
+    #[must_use]
+    pub fn types(&self) -> &[ParticleType] {
+        &self.types
+    }
+
+    /// Returns the particle positions
+    #[must_use]
+    pub fn positions(&self) -> &[Vec3] {
+        &self.positions
+    }
+
+    /// Returns the particle positions for modification
+    pub fn positions_mut(&mut self) -> &mut [Vec3] {
+        &mut self.positions
+    }
+
+    /// Returns the particle velocities
+    #[must_use]
+    pub fn velocities(&self) -> &[Vec3] {
+        &self.velocities
+    }
+
+    /// Returns the particle velocities for modification
+    pub fn velocities_mut(&mut self) -> &mut [Vec3] {
+        &mut self.velocities
+    }
+
+    /// Returns the particle energies
+    #[must_use]
+    pub fn energies(&self) -> &[f32] {
+        &self.energies
+    }
+
+    /// Returns the particle energies for modification
+    pub fn energies_mut(&mut self) -> &mut [f32] {
+        &mut self.energies
+    }
+
+    /// Returns the particle genomes
+    ///
+    /// Empty genomes are seeded from the interaction table at the start of
+    /// the next step in evolution mode.
+    #[must_use]
+    pub fn genomes(&self) -> &[Genome] {
+        &self.genomes
+    }
+
+    /// Returns the particle genomes for modification
+    pub fn genomes_mut(&mut self) -> &mut [Genome] {
+        &mut self.genomes
+    }
+
+    /// Returns the index each particle was added with
+    ///
+    /// Particles born during a step have no origin.
+    #[must_use]
+    pub fn origins(&self) -> &[Option<usize>] {
+        &self.origins
+    }
+
+    /// Returns the accelerations computed during the last step
+    #[must_use]
+    pub fn accelerations(&self) -> &[Vec3] {
+        &self.accelerations
+    }
+
+    /// Returns the spatial grid built during the last step
+    #[must_use]
+    pub const fn grid(&self) -> &SpatialGrid {
+        &self.grid
+    }
+
+    /// Returns the obstacles particles collide with
+    #[must_use]
+    pub fn obstacles(&self) -> &[Obstacle] {
+        self.obstacle_grid.obstacles()
+    }
+
+    /// Replaces the obstacles particles collide with
+    ///
+    /// Obstacle membrane names are looked up in `membranes`; unknown
+    /// names leave the obstacle solid for every species. Rebuilds the
+    /// obstacle grid, so call it only when the obstacles or membranes
+    /// change.
+    pub fn set_obstacles(&mut self, obstacles: &[Obstacle], membranes: &Membranes) {
+        self.obstacle_grid.rebuild(obstacles);
+        self.obstacle_membranes.clear();
+        self.obstacle_membranes
+            .extend(obstacles.iter().map(|obstacle| {
+                obstacle
+                    .membrane
+                    .as_deref()
+                    .and_then(|name| membranes.get(name))
+                    .cloned()
+            }));
+    }
+
+    /// Returns the reaction rules
+    #[must_use]
+    pub const fn reactions(&self) -> &ReactionTable {
+        &self.reactions
+    }
+
+    /// Replaces the reaction rules and restarts their firing counts
+    pub fn set_reactions(&mut self, reactions: &ReactionTable) {
+        self.reactions.clone_from(reactions);
+        self.reaction_stats.fired.clear();
+        self.reaction_stats.fired.resize(reactions.len(), 0);
+        self.reaction_stats.last_step.clear();
+        self.reaction_stats.last_step.resize(reactions.len(), 0);
+    }
+
+    /// Returns how often each reaction rule fired
+    #[must_use]
+    pub const fn reaction_stats(&self) -> &ReactionStats {
+        &self.reaction_stats
+    }
+
+    /// Returns the ecosystem settings
+    #[must_use]
+    pub const fn ecosystem(&self) -> &Ecosystem {
+        &self.ecosystem
+    }
+
+    /// Replaces the ecosystem settings
+    pub fn set_ecosystem(&mut self, ecosystem: &Ecosystem) {
+        self.ecosystem.clone_from(ecosystem);
+    }
+
+    /// Returns the per-species ecosystem metrics
+    #[must_use]
+    pub const fn ecosystem_stats(&self) -> &EcosystemStats {
+        &self.ecosystem_stats
+    }
+
+    /// Returns the evolution settings
+    #[must_use]
+    pub const fn evolution(&self) -> &Evolution {
+        &self.evolution
+    }
+
+    /// Replaces the evolution settings
+    pub const fn set_evolution(&mut self, evolution: Evolution) {
+        self.evolution = evolution;
+    }
+
+    /// Returns the genome metrics of every species
+    ///
+    /// Computed from the current genomes; particles with an empty genome
+    /// are skipped.
+    #[must_use]
+    pub fn genome_stats(&self) -> GenomeStats {
+        let mut stats = GenomeStats::default();
+        let mut lineages: Vec<BTreeMap<u64, (usize, Vec<f32>)>> = Vec::new();
+        let mut sums: Vec<(Vec<f32>, Vec<f32>)> = Vec::new();
+        for (particle_type, genome) in self.types.iter().zip(&self.genomes) {
+            if genome.is_empty() {
+                continue;
+            }
+            let index = particle_type.index();
+            if index >= stats.species.len() {
+                stats.species.resize(index + 1, SpeciesGenomes::default());
+                lineages.resize(index + 1, BTreeMap::new());
+                sums.resize(index + 1, (Vec::new(), Vec::new()));
+            }
+            let census = &mut stats.species[index];
+            census.population += 1;
+            census.max_generation = census.max_generation.max(genome.generation);
+
+            let (sum, sum_squares) = &mut sums[index];
+            let (count, lineage_sum) = lineages[index].entry(genome.lineage).or_default();
+            *count += 1;
+            for values in [&mut *sum, &mut *sum_squares, &mut *lineage_sum] {
+                if values.len() < genome.strengths.len() {
+                    values.resize(genome.strengths.len(), 0.0);
+                }
+            }
+            for (source, strength) in genome.strengths.iter().enumerate() {
+                sum[source] += strength;
+                sum_squares[source] += strength * strength;
+                lineage_sum[source] += strength;
+            }
+        }
+
+        for ((census, lineages), (sum, sum_squares)) in
+            stats.species.iter_mut().zip(&lineages).zip(&sums)
+        {
+            if census.population == 0 {
+                continue;
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let population = census.population as f32;
+            census.lineages = lineages.len();
+            if !sum.is_empty() {
+                #[allow(clippy::cast_precision_loss)]
+                let sources = sum.len() as f32;
+                census.diversity = sum
+                    .iter()
+                    .zip(sum_squares)
+                    .map(|(sum, sum_squares)| {
+                        let mean = sum / population;
+                        mean.mul_add(-mean, sum_squares / population)
+                            .max(0.0)
+                            .sqrt()
+                    })
+                    .sum::<f32>()
+                    / sources;
+            }
+            // Ties go to the oldest lineage.
+            if let Some((lineage, (count, lineage_sum))) = lineages
+                .iter()
+                .max_by(|(a, (a_count, _)), (b, (b_count, _))| a_count.cmp(b_count).then(b.cmp(a)))
+            {
+                #[allow(clippy::cast_precision_loss)]
+                let count_f32 = *count as f32;
+                census.dominant_lineage = *lineage;
+                census.dominant_count = *count;
+                census.dominant_genome = lineage_sum.iter().map(|sum| sum / count_f32).collect();
+            }
+        }
+        stats
+    }
+
+    /// Returns the numerical health counters
+    #[must_use]
+    pub const fn health(&self) -> SimulationHealth {
+        self.health
+    }
+
+    /// Returns the force a source particle exerts on a target particle
+    ///
+    /// `offset` points from the target to the source. The magnitude comes
+    /// from the kernel selected by `config.kernel`.
+    #[must_use]
+    pub fn pair_force(&self, target: ParticleType, source: ParticleType, offset: Vec3) -> Vec3 {
+        self.pair_force_with(
+            &ConfiguredKernel::from_config(&self.config),
+            target,
+            source,
+            offset,
+        )
+    }
+
+    /// Returns the force a source particle exerts on a target particle
+    /// using the given kernel
+    ///
+    /// The pair's [`InteractionProfile`] supplies the strength and, if
+    /// set, its own radius, collision ratio and kernel. Without a collision
+    /// ratio of its own, the collision radii of both species apply. A zero
+    /// offset has no direction and yields no force.
+    #[must_use]
+    pub fn pair_force_with<K: ForceKernel + Clone>(
+        &self,
+        kernel: &K,
+        target: ParticleType,
+        source: ParticleType,
+        offset: Vec3,
+    ) -> Vec3 {
+        self.pair_force_of(kernel, target, source, offset, None)
+    }
+
+    /// Returns the force of a pair, optionally with the strength of its
+    /// profile replaced by a genome value
+    fn pair_force_of<K: ForceKernel + Clone>(
+        &self,
+        kernel: &K,
+        target: ParticleType,
+        source: ParticleType,
+        offset: Vec3,
+        strength: Option<f32>,
+    ) -> Vec3 {
+        let (mut profile, r) = self.resolved_profile(target, source);
+        if let Some(strength) = strength {
+            profile.strength = strength;
+        }
+        let distance = offset.length();
+        if distance >= r || distance <= 0.0 {
+            return Vec3::ZERO;
+        }
+        let direction = offset / distance;
+        let force = if profile.collision_ratio.is_some() || profile.kernel.is_some() {
+            kernel
+                .for_profile(&profile)
+                .force(distance, r, profile.strength)
+        } else {
+            kernel.force(distance, r, profile.strength)
+        };
+        direction * force
+    }
+
+    /// Returns the potential energy of a target particle in the field of a
+    /// source particle at `distance`
+    ///
+    /// The potential is `-∫ force` from `distance` to the pair's radius,
+    /// integrated numerically, so it is zero beyond the radius.
+    #[must_use]
+    pub fn pair_potential_with<K: ForceKernel + Clone>(
+        &self,
+        kernel: &K,
+        target: ParticleType,
+        source: ParticleType,
+        distance: f32,
+    ) -> f32 {
+        let (profile, r) = self.resolved_profile(target, source);
+        if distance >= r {
+            return 0.0;
+        }
+        let kernel = kernel.for_profile(&profile);
+        let width = (r - distance) / f32::from(POTENTIAL_SAMPLES);
+        let integral: f32 = (0..POTENTIAL_SAMPLES)
+            .map(|sample| {
+                let s = (f32::from(sample) + 0.5).mul_add(width, distance);
+                kernel.force(s, r, profile.strength)
+            })
+            .sum();
+        -integral * width
+    }
+
+    /// Returns the interaction profile of a pair and its radius
+    ///
+    /// If the profile has no collision ratio but either species has a
+    /// collision radius, the ratio is derived from the sum of both radii.
+    fn resolved_profile(
+        &self,
+        target: ParticleType,
+        source: ParticleType,
+    ) -> (InteractionProfile, f32) {
+        let mut profile = self.interaction_table.get_profile(target, source);
+        let r = profile.r.unwrap_or(self.config.r);
+        if profile.collision_ratio.is_none() {
+            let radius = |particle_type: ParticleType| {
+                self.species
+                    .get(particle_type.index())
+                    .and_then(|properties| properties.collision_radius)
+            };
+            if radius(target).is_none() && radius(source).is_none() {
+                return (profile, r);
+            }
+            let fallback = self.config.collision_ratio * r / 2.0;
+            let distance = radius(target).unwrap_or(fallback) + radius(source).unwrap_or(fallback);
+            profile.collision_ratio = Some((distance / r).min(MAX_COLLISION_RATIO));
+        }
+        (profile, r)
+    }
+
+    /// Returns the mass and velocity of every particle
+    fn masses_and_velocities(&self) -> impl Iterator<Item = (f32, Vec3)> {
+        self.velocities
+            .iter()
+            .zip(&self.types)
+            .map(|(velocity, particle_type)| {
+                let mass = self
+                    .species
+                    .get(particle_type.index())
+                    .map_or(DEFAULT_MASS, |properties| properties.mass);
+                (mass, *velocity)
+            })
+    }
+
+    /// Returns the total kinetic energy `Σ m |v|² / 2`
+    #[must_use]
+    pub fn kinetic_energy(&self) -> f64 {
+        self.masses_and_velocities()
+            .map(|(mass, velocity)| 0.5 * f64::from(mass) * f64::from(velocity.length_squared()))
+            .sum()
+    }
+
+    /// Returns the measured kinetic temperature
+    ///
+    /// See [`kinetic_temperature`].
+    #[must_use]
+    pub fn kinetic_temperature(&self) -> f64 {
+        kinetic_temperature(
+            self.masses_and_velocities(),
+            self.config.degrees_of_freedom(),
+        )
+    }
+
+    /// Returns the total pair potential energy for the configured kernel
+    ///
+    /// Each ordered pair contributes half of its potential, which makes
+    /// asymmetric interaction tables use the mean of both directions.
+    /// Rebuilds the spatial grid for the current positions.
+    pub fn potential_energy(&mut self) -> f64 {
+        let kernel = ConfiguredKernel::from_config(&self.config);
+        let cell_size = self.interaction_table.max_radius(self.config.r);
+        self.grid.rebuild(&self.config, cell_size, &self.positions);
+
+        let mut energy = 0.0;
+        for (index, position) in self.positions.iter().enumerate() {
+            for neighbors in self.grid.neighbors(*position) {
+                for &other in neighbors {
+                    if other == index {
+                        continue;
+                    }
+                    let distance = self
+                        .config
+                        .displacement(*position, self.positions[other])
+                        .length();
+                    let potential = self.pair_potential_with(
+                        &kernel,
+                        self.types[index],
+                        self.types[other],
+                        distance,
+                    );
+                    energy += 0.5 * f64::from(potential);
+                }
+            }
+        }
+        energy
+    }
+
+    /// Returns the total energy (kinetic plus potential)
+    pub fn total_energy(&mut self) -> f64 {
+        self.kinetic_energy() + self.potential_energy()
+    }
+
+    /// Advances the simulation by one time step of `config.dt`
+    ///
+    /// 1. Spatial partitioning for efficient neighbor queries
+    /// 2. Calculation of interaction forces between particles (parallel)
+    /// 3. Velocity integration with `config.integrator`, thermal noise at
+    ///    `config.temperature` and boundary handling
+    /// 4. Species conversion by the reaction rules found in step 2
+    /// 5. Energy, deaths and births in ecosystem mode
+    ///
+    /// All accelerations of a force evaluation are computed from the same
+    /// positions, so the result does not depend on particle order or on
+    /// the number of threads.
+    pub fn step(&mut self) {
+        let kernel = ConfiguredKernel::from_config(&self.config);
+        self.step_with(&kernel);
+    }
+
+    /// Advances the simulation by one time step using the given kernel
+    ///
+    /// Particles are then kept out of obstacles, and those that end the
+    /// step with a non-finite position or velocity are repaired according
+    /// to `config.repair_mode`. Finally the reactions found in the first
+    /// force pass of the step convert the species of their particles, and
+    /// in ecosystem mode particles feed, die and split.
+    pub fn step_with<K: ForceKernel + Clone + Sync>(&mut self, kernel: &K) {
+        self.start_positions.clone_from(&self.positions);
+        self.pending_reactions.clear();
+        self.find_reactions = !self.reactions.is_empty();
+        self.forage.clear();
+        self.find_forage = self.ecosystem.forages();
+        if self.evolution.enabled {
+            self.seed_genomes();
+        }
+        match self.config.integrator {
+            Integrator::SemiImplicitEuler => self.step_euler(kernel),
+            Integrator::VelocityVerlet => self.step_verlet(kernel),
+            Integrator::Rk4 => self.step_rk4(kernel),
+        }
+        self.collide_obstacles();
+        self.repair_non_finite();
+        self.apply_reactions();
+        if self.ecosystem.enabled {
+            self.update_ecosystem();
+        }
+        if self.evolution.enabled
+            && self
+                .evolution
+                .respawn_interval
+                .is_some_and(|interval| (self.health.steps + 1).is_multiple_of(interval.max(1)))
+        {
+            self.respawn_genomes();
+        }
+        self.health.steps += 1;
+    }
+
+    /// Converts the species of the particles that reacted this step
+    ///
+    /// Reactions are applied in particle order. A particle takes part in
+    /// at most one reaction per step, so a reaction whose reactant or
+    /// partner already reacted is dropped.
+    fn apply_reactions(&mut self) {
+        let stats = &mut self.reaction_stats;
+        stats.fired.resize(self.reactions.len(), 0);
+        stats.last_step.clear();
+        stats.last_step.resize(self.reactions.len(), 0);
+        if self.pending_reactions.is_empty() {
+            return;
+        }
+
+        self.reacted.clear();
+        self.reacted.resize(self.types.len(), false);
+        for reaction in &self.pending_reactions {
+            let partner_reacted = reaction.partner.is_some_and(|other| self.reacted[other]);
+            if self.reacted[reaction.index] || partner_reacted {
+                continue;
+            }
+            let rule = &self.reactions.rules()[reaction.rule];
+            Self::convert(
+                &mut self.types[reaction.index],
+                &mut self.genomes[reaction.index],
+                rule.product,
+            );
+            self.reacted[reaction.index] = true;
+            if let (Some(other), Some(product)) = (reaction.partner, rule.partner_product) {
+                Self::convert(&mut self.types[other], &mut self.genomes[other], product);
+                self.reacted[other] = true;
+            }
+            stats.fired[reaction.rule] += 1;
+            stats.last_step[reaction.rule] += 1;
+        }
+        self.pending_reactions.clear();
+    }
+
+    /// Changes the species of a particle
+    ///
+    /// A genome belongs to its species' row, so a particle that changes
+    /// species loses its genome and is seeded again as a founder.
+    fn convert(particle_type: &mut ParticleType, genome: &mut Genome, product: ParticleType) {
+        if *particle_type != product {
+            *particle_type = product;
+            *genome = Genome::default();
+        }
+    }
+
+    /// Seeds empty genomes from the interaction table
+    ///
+    /// Every particle without a genome becomes the founder of a new
+    /// lineage, with its species' row of the table as strengths.
+    fn seed_genomes(&mut self) {
+        let sources = self.species.len().max(self.interaction_table.len());
+        for (particle_type, genome) in self.types.iter().zip(&mut self.genomes) {
+            if genome.is_empty() && sources > 0 {
+                let strengths = (0..sources)
+                    .map(|source| {
+                        self.interaction_table
+                            .get_interaction(*particle_type, ParticleType::new(source))
+                    })
+                    .collect();
+                self.next_lineage += 1;
+                *genome = Genome::founder(strengths, self.next_lineage);
+            }
+        }
+    }
+
+    /// Replaces a fraction of the particles by mutated copies of others
+    ///
+    /// Picks `respawn_fraction` of the particles with the seeded
+    /// generator. Each one starts over as a newborn of a random particle
+    /// of its species: it takes the donor's genome, mutated, appears next
+    /// to the donor at rest and gets the initial energy of the ecosystem.
+    /// Picks whose donor has another species are skipped.
+    fn respawn_genomes(&mut self) {
+        if self.types.len() < 2 {
+            return;
+        }
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let count = (self.types.len() as f32 * self.evolution.respawn_fraction)
+            .round()
+            .max(1.0) as usize;
+        let spacing = self.config.collision_ratio * self.config.r / 2.0;
+        for _ in 0..count {
+            let target = self.rng.random_range(0..self.types.len());
+            let donor = self.rng.random_range(0..self.types.len());
+            if target == donor || self.types[target] != self.types[donor] {
+                continue;
+            }
+            self.genomes[target] = self.genomes[donor].child(
+                self.evolution.mutation,
+                self.evolution.limit,
+                &mut self.rng,
+            );
+            let mut position = self.positions[donor] + self.birth_offset(spacing);
+            let mut velocity = Vec3::ZERO;
+            self.config.apply_boundary(&mut position, &mut velocity);
+            self.positions[target] = position;
+            self.velocities[target] = velocity;
+            self.energies[target] = self.ecosystem.initial_energy;
+        }
+    }
+
+    /// Returns a random offset of length `spacing` for a newborn particle
+    ///
+    /// The offset stays on the `z = 0` plane in 2D.
+    fn birth_offset(&mut self, spacing: f32) -> Vec3 {
+        let direction = Vec3::new(
+            self.rng.gaussian(),
+            self.rng.gaussian(),
+            if self.config.dimension.is_3d() {
+                self.rng.gaussian()
+            } else {
+                0.0
+            },
+        );
+        direction.normalize_or_zero() * spacing
+    }
+
+    /// Updates energies, removes dead particles and adds newborns
+    ///
+    /// 1. Every particle loses `drain` and gains its feeding rate, both
+    ///    per second of the step
+    /// 2. Predators eat the prey found in the force pass, in particle
+    ///    order; each predator eats at most once per step and a prey can
+    ///    only be eaten once
+    /// 3. Particles without energy starve
+    /// 4. Particles at `split_energy` split in two, in particle order,
+    ///    unless the total or species population cap is reached. The
+    ///    child is placed next to the parent and both keep half the
+    ///    energy. In evolution mode the child inherits a mutated copy of
+    ///    the parent's genome.
+    /// 5. Dead particles are removed and the species metrics recounted
+    #[allow(clippy::too_many_lines)]
+    fn update_ecosystem(&mut self) {
+        let dt = self.config.dt;
+        let count = self.types.len();
+        let forage = std::mem::take(&mut self.forage);
+
+        for (index, energy) in self.energies.iter_mut().enumerate() {
+            let gain = forage.get(index).map_or(0.0, |forage| forage.gain);
+            *energy += (gain - self.ecosystem.drain) * dt;
+        }
+
+        self.alive.clear();
+        self.alive.resize(count, true);
+        let mut ate = vec![false; count];
+        for (index, forage) in forage.iter().enumerate() {
+            let Some(prey) = forage.prey else {
+                continue;
+            };
+            if ate[index] || !self.alive[index] || !self.alive[prey] {
+                continue;
+            }
+            self.energies[index] += self.ecosystem.efficiency * self.energies[prey].max(0.0);
+            self.alive[prey] = false;
+            ate[index] = true;
+            self.ecosystem_stats.get_mut(self.types[prey]).eaten += 1;
+        }
+
+        for (index, energy) in self.energies.iter().enumerate() {
+            if self.alive[index] && (*energy <= 0.0 || energy.is_nan()) {
+                self.alive[index] = false;
+                self.ecosystem_stats.get_mut(self.types[index]).starvations += 1;
+            }
+        }
+
+        let mut populations = Vec::new();
+        for (particle_type, alive) in self.types.iter().zip(&self.alive) {
+            if *alive {
+                let index = particle_type.index();
+                if index >= populations.len() {
+                    populations.resize(index + 1, 0);
+                }
+                populations[index] += 1;
+            }
+        }
+        let mut total: usize = populations.iter().sum();
+        let spacing = self.config.collision_ratio * self.config.r / 2.0;
+        for parent in 0..count {
+            let particle_type = self.types[parent];
+            if !self.alive[parent] || self.energies[parent] < self.ecosystem.split_energy {
+                continue;
+            }
+            let population = populations[particle_type.index()];
+            if self
+                .ecosystem
+                .max_population
+                .is_some_and(|max| total >= max)
+                || self
+                    .ecosystem
+                    .cap(particle_type)
+                    .is_some_and(|cap| population >= cap)
+            {
+                continue;
+            }
+            let energy = self.energies[parent] / 2.0;
+            self.energies[parent] = energy;
+            let mut position = self.positions[parent] + self.birth_offset(spacing);
+            let mut velocity = self.velocities[parent];
+            self.config.apply_boundary(&mut position, &mut velocity);
+            let genome = if self.evolution.enabled {
+                self.genomes[parent].child(
+                    self.evolution.mutation,
+                    self.evolution.limit,
+                    &mut self.rng,
+                )
+            } else {
+                self.genomes[parent].clone()
+            };
+
+            self.types.push(particle_type);
+            self.positions.push(position);
+            self.velocities.push(velocity);
+            self.energies.push(energy);
+            self.origins.push(None);
+            self.genomes.push(genome);
+            self.accelerations.push(Vec3::ZERO);
+            self.alive.push(true);
+            populations[particle_type.index()] += 1;
+            total += 1;
+            self.ecosystem_stats.get_mut(particle_type).births += 1;
+        }
+
+        if self.alive.contains(&false) {
+            retain_alive(&mut self.types, &self.alive);
+            retain_alive(&mut self.positions, &self.alive);
+            retain_alive(&mut self.velocities, &self.alive);
+            retain_alive(&mut self.energies, &self.alive);
+            retain_alive(&mut self.origins, &self.alive);
+            retain_alive(&mut self.genomes, &self.alive);
+            retain_alive(&mut self.accelerations, &self.alive);
+        }
+
+        for census in &mut self.ecosystem_stats.species {
+            census.population = 0;
+            census.energy = 0.0;
+        }
+        for (particle_type, energy) in self.types.iter().zip(&self.energies) {
+            let census = self.ecosystem_stats.get_mut(*particle_type);
+            census.population += 1;
+            census.energy += energy;
+        }
+    }
+
+    /// Stops particles from passing through obstacles
+    ///
+    /// Sweeps every particle along its displacement of this step, so fast
+    /// particles cannot tunnel through thin walls. On a hit the particle
+    /// stops just in front of the surface, its velocity bounces with the
+    /// obstacle's restitution and friction, and the rest of the
+    /// displacement is reflected the same way. Particles starting inside a
+    /// solid obstacle are pushed out to its boundary first. Obstacles with
+    /// a membrane are skipped for the species it lets pass.
+    ///
+    /// Only x and y are affected; obstacles are walls along z.
+    fn collide_obstacles(&mut self) {
+        if self.obstacle_grid.is_empty() {
+            return;
+        }
+        let grid = &self.obstacle_grid;
+        let membranes = &self.obstacle_membranes;
+        for (index, (position, velocity)) in self
+            .positions
+            .iter_mut()
+            .zip(&mut self.velocities)
+            .enumerate()
+        {
+            let start = self.start_positions[index];
+            if !start.is_finite() || !position.is_finite() {
+                continue;
+            }
+            // Follow the shortest path so wrapping does not sweep the map.
+            let end = start + self.config.displacement(start, *position);
+            let mut from = start.truncate();
+            let mut to = end.truncate();
+            let mut planar_velocity = velocity.truncate();
+            let mut collided = false;
+            let particle_type = self.types[index];
+            let solid = |obstacle: usize| {
+                membranes[obstacle]
+                    .as_ref()
+                    .is_none_or(|membrane| membrane.is_solid_for(particle_type))
+            };
+
+            if let Some(exit) = grid.push_out(from, solid) {
+                let obstacle = &grid.obstacles()[exit.obstacle];
+                let exit_point = exit.point + exit.normal * SKIN;
+                to = exit_point
+                    + bounce(
+                        to - from,
+                        exit.normal,
+                        obstacle.restitution,
+                        obstacle.friction,
+                    );
+                from = exit_point;
+                planar_velocity = bounce(
+                    planar_velocity,
+                    exit.normal,
+                    obstacle.restitution,
+                    obstacle.friction,
+                );
+                collided = true;
+            }
+
+            let mut bounces = 0;
+            while let Some(hit) = grid.first_hit(from, to, solid) {
+                collided = true;
+                if bounces == MAX_BOUNCES {
+                    // Stay at the last safe point rather than tunnel.
+                    to = from;
+                    break;
+                }
+                bounces += 1;
+                let obstacle = &grid.obstacles()[hit.obstacle];
+                let rest = bounce(
+                    to - hit.point,
+                    hit.normal,
+                    obstacle.restitution,
+                    obstacle.friction,
+                );
+                from = hit.point + hit.normal * SKIN;
+                to = from + rest;
+                planar_velocity = bounce(
+                    planar_velocity,
+                    hit.normal,
+                    obstacle.restitution,
+                    obstacle.friction,
+                );
+            }
+
+            if collided {
+                *position = to.extend(position.z);
+                *velocity = planar_velocity.extend(velocity.z);
+                self.config.apply_boundary(position, velocity);
+            }
+        }
+    }
+
+    /// Repairs particles whose position or velocity is no longer finite
+    fn repair_non_finite(&mut self) {
+        let mut repairs = 0;
+        for (index, (position, velocity)) in self
+            .positions
+            .iter_mut()
+            .zip(&mut self.velocities)
+            .enumerate()
+        {
+            if position.is_finite() && velocity.is_finite() {
+                continue;
+            }
+            *position = match self.config.repair_mode {
+                RepairMode::Quarantine => {
+                    let start = self.start_positions[index];
+                    if start.is_finite() { start } else { Vec3::ZERO }
+                }
+                RepairMode::Respawn => self.config.random_position(&mut self.rng),
+            };
+            *velocity = Vec3::ZERO;
+            repairs += 1;
+        }
+        self.health.repairs += repairs;
+        self.health.last_step_repairs = repairs;
+    }
+
+    /// Computes the acceleration of every particle from its neighbors
+    ///
+    /// Rebuilds the spatial grid and stores the result, which is then
+    /// available through [`Simulation::accelerations`].
+    ///
+    /// This phase only reads positions, so particles are split into
+    /// chunks that run in parallel on the [`ComputeTaskPool`]. Each
+    /// acceleration is summed in a fixed neighbor order, which keeps the
+    /// result identical for any number of threads.
+    pub fn compute_accelerations(&mut self) {
+        let kernel = ConfiguredKernel::from_config(&self.config);
+        self.compute_accelerations_with(&kernel);
+    }
+
+    /// Computes the acceleration of every particle using the given kernel
+    ///
+    /// Same as [`Simulation::compute_accelerations`], but evaluates a
+    /// custom [`ForceKernel`] instead of the one selected in the config.
+    pub fn compute_accelerations_with<K: ForceKernel + Clone + Sync>(&mut self, kernel: &K) {
+        let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+        self.compute_accelerations_on(kernel, pool);
+    }
+
+    /// Computes the acceleration of every particle on the given task pool
+    fn compute_accelerations_on<K: ForceKernel + Clone + Sync>(
+        &mut self,
+        kernel: &K,
+        pool: &TaskPool,
+    ) {
+        self.motions.clear();
+        self.motions.extend(
+            self.species
+                .iter()
+                .map(|properties| Motion::new(properties, &self.config)),
+        );
+        self.membrane_range = self
+            .obstacle_membranes
+            .iter()
+            .flatten()
+            .filter(|membrane| membrane.has_forces())
+            .map(|membrane| membrane.range.unwrap_or(self.config.r))
+            .fold(0.0, f32::max);
+
+        let contact_distance = self.config.collision_ratio * self.config.r;
+        let forage_distance = if self.find_forage {
+            self.ecosystem.max_distance(self.config.r, contact_distance)
+        } else {
+            0.0
+        };
+        let cell_size = self
+            .interaction_table
+            .max_radius(self.config.r)
+            .max(self.reactions.max_distance(contact_distance))
+            .max(forage_distance);
+        self.grid.rebuild(&self.config, cell_size, &self.positions);
+
+        // Reactions and meals are found once per step, in its first force pass.
+        let find_reactions = std::mem::take(&mut self.find_reactions);
+        let find_forage = std::mem::take(&mut self.find_forage);
+
+        let mut accelerations = std::mem::take(&mut self.accelerations);
+        accelerations.clear();
+        accelerations.resize(self.positions.len(), Vec3::ZERO);
+
+        let chunk_size = accelerations
+            .len()
+            .div_ceil(pool.thread_num().max(1))
+            .max(MIN_CHUNK_SIZE);
+        let this = &*self;
+        let results = accelerations.par_chunk_map_mut(pool, chunk_size, |chunk_index, chunk| {
+            let start = chunk_index * chunk_size;
+            let mut coincident = 0;
+            let mut reactions = Vec::new();
+            let mut forage = Vec::new();
+            for (offset, acceleration) in chunk.iter_mut().enumerate() {
+                let (value, count) = this.acceleration_of(kernel, start + offset);
+                *acceleration = value;
+                coincident += count;
+                if find_reactions {
+                    reactions.extend(this.reaction_of(start + offset, contact_distance));
+                }
+                if find_forage {
+                    forage.push(this.forage_of(start + offset, contact_distance));
+                }
+            }
+            (coincident, reactions, forage)
+        });
+        self.accelerations = accelerations;
+        // Every coincident pair is seen once from each side.
+        self.health.coincident_pairs += results.iter().map(|(count, ..)| count).sum::<u64>() / 2;
+        // Chunks come back in order, which keeps reactions in particle order.
+        for (_, reactions, forage) in results {
+            self.pending_reactions.extend(reactions);
+            self.forage.extend(forage);
+        }
+    }
+
+    /// Returns the acceleration of one particle from its neighbors and the
+    /// number of neighbors found at the same position
+    ///
+    /// The summed pair and membrane forces are divided by the mass of the
+    /// particle's species, then the accelerations of the external force
+    /// fields are added. In evolution mode the particle's genome supplies
+    /// the pair strengths it has entries for.
+    ///
+    /// Coincident neighbors are separated along a direction derived from
+    /// both indices instead of the undefined direction of a zero offset.
+    fn acceleration_of<K: ForceKernel + Clone>(&self, kernel: &K, index: usize) -> (Vec3, u64) {
+        let position = self.positions[index];
+        let my_type = self.types[index];
+        let genome = self
+            .evolution
+            .enabled
+            .then(|| self.genomes[index].strengths.as_slice());
+
+        let mut acceleration = Vec3::ZERO;
+        let mut coincident = 0;
+        for neighbors in self.grid.neighbors(position) {
+            for &other in neighbors {
+                if other == index {
+                    continue;
+                }
+                let mut offset = self.config.displacement(position, self.positions[other]);
+                if offset.length_squared() < MIN_DISTANCE * MIN_DISTANCE {
+                    offset = coincident_offset(self.config.dimension, index, other);
+                    coincident += 1;
+                }
+                let source = self.types[other];
+                let strength = genome.and_then(|genome| genome.get(source.index()).copied());
+                acceleration += self.pair_force_of(kernel, my_type, source, offset, strength);
+            }
+        }
+        if self.membrane_range > 0.0 {
+            acceleration += self.membrane_force(position, my_type);
+        }
+        acceleration *= Motion::of(&self.motions, my_type, &self.config).inverse_mass;
+
+        let velocity = self.velocities[index];
+        for field in &self.fields {
+            if field.applies_to(my_type) {
+                let offset = self.config.displacement(position, field.center);
+                acceleration += field.acceleration(offset, velocity);
+            }
+        }
+        (acceleration, coincident)
+    }
+
+    /// Returns the reaction a particle takes part in this step, if any
+    ///
+    /// Rolls every rule with this particle as reactant: contact rules once
+    /// per matching neighbor within their distance, in neighbor order,
+    /// then spontaneous rules. The first roll below the rule's
+    /// probability wins. Each particle draws from its own stream of the
+    /// seed for this step, so the outcome does not depend on the number
+    /// of threads.
+    fn reaction_of(&self, index: usize, contact_distance: f32) -> Option<PendingReaction> {
+        let position = self.positions[index];
+        let my_type = self.types[index];
+        let rules = self.reactions.rules();
+        let mut rng = SimRng::step_stream(self.rng.seed(), self.health.steps, index as u64);
+
+        for neighbors in self.grid.neighbors(position) {
+            for &other in neighbors {
+                if other == index {
+                    continue;
+                }
+                let distance = self
+                    .config
+                    .displacement(position, self.positions[other])
+                    .length();
+                for (rule_index, rule) in rules.iter().enumerate() {
+                    if rule.reactant == my_type
+                        && rule.partner == Some(self.types[other])
+                        && distance <= rule.distance.unwrap_or(contact_distance)
+                        && rng.random::<f32>() < rule.probability
+                    {
+                        return Some(PendingReaction {
+                            index,
+                            rule: rule_index,
+                            partner: Some(other),
+                        });
+                    }
+                }
+            }
+        }
+        rules
+            .iter()
+            .enumerate()
+            .find(|(_, rule)| {
+                rule.reactant == my_type
+                    && rule.is_spontaneous()
+                    && rng.random::<f32>() < rule.probability
+            })
+            .map(|(rule_index, _)| PendingReaction {
+                index,
+                rule: rule_index,
+                partner: None,
+            })
+    }
+
+    /// Returns the feeding rate of a particle and the prey it eats, if any
+    ///
+    /// Sums the feeding rates of all neighbors within the feeding
+    /// distance. Predation is rolled once per neighbor within
+    /// `contact_distance`, in neighbor order, and the first success picks
+    /// the prey. Draws come from the particle's own stream like
+    /// [`Simulation::reaction_of`].
+    fn forage_of(&self, index: usize, contact_distance: f32) -> Forage {
+        let position = self.positions[index];
+        let my_type = self.types[index];
+        let feeding_distance = self.ecosystem.feeding_distance.unwrap_or(self.config.r);
+        let mut rng = SimRng::step_stream(
+            self.rng.seed() ^ FORAGE_SALT,
+            self.health.steps,
+            index as u64,
+        );
+
+        let mut forage = Forage::default();
+        for neighbors in self.grid.neighbors(position) {
+            for &other in neighbors {
+                if other == index {
+                    continue;
+                }
+                let other_type = self.types[other];
+                let distance = self
+                    .config
+                    .displacement(position, self.positions[other])
+                    .length();
+                if distance <= feeding_distance {
+                    forage.gain += self.ecosystem.feeding(my_type, other_type);
+                }
+                let chance = self.ecosystem.predation(my_type, other_type);
+                if forage.prey.is_none()
+                    && chance > 0.0
+                    && distance <= contact_distance
+                    && rng.random::<f32>() < chance
+                {
+                    forage.prey = Some(other);
+                }
+            }
+        }
+        forage
+    }
+
+    /// Returns the force of all membranes near a particle
+    ///
+    /// Each membrane pulls the particle towards its closest boundary
+    /// point with `strength * (1 - distance / range)`, so negative
+    /// strengths push it away. The force lies in the xy plane.
+    fn membrane_force(&self, position: Vec3, particle_type: ParticleType) -> Vec3 {
+        let point = position.truncate();
+        let membranes = &self.obstacle_membranes;
+        let closest = self
+            .obstacle_grid
+            .closest_points(point, self.membrane_range, |obstacle| {
+                membranes[obstacle].is_some()
+            });
+
+        let mut force = Vec3::ZERO;
+        for (obstacle, closest) in closest {
+            let Some(membrane) = &membranes[obstacle] else {
+                continue;
+            };
+            let strength = membrane.get(particle_type).strength;
+            let range = membrane.range.unwrap_or(self.config.r);
+            let offset = closest - point;
+            let distance = offset.length();
+            if strength.abs() > 0.0 && distance < range {
+                let direction = offset.normalize_or_zero().extend(0.0);
+                force += direction * strength * (1.0 - distance / range);
+            }
+        }
+        force
+    }
+
+    /// Damped semi-implicit Euler step
+    ///
+    /// Decays the velocity over the full step, kicks it with the
+    /// acceleration and then drifts the position with the new velocity.
+    fn step_euler<K: ForceKernel + Clone + Sync>(&mut self, kernel: &K) {
+        self.compute_accelerations_with(kernel);
+
+        let config = &self.config;
+        let mut speed_caps = 0;
+        for (index, (position, velocity)) in self
+            .positions
+            .iter_mut()
+            .zip(&mut self.velocities)
+            .enumerate()
+        {
+            let motion = Motion::of(&self.motions, self.types[index], &self.config);
+            *velocity *= motion.decay;
+            Motion::kick(motion.noise, velocity, &mut self.rng, config.dimension);
+            *velocity += self.accelerations[index] * config.dt;
+            speed_caps += u64::from(motion.limit_speed(velocity));
+
+            *position += *velocity * config.dt;
+            config.apply_boundary(position, velocity);
+        }
+        self.health.speed_caps += speed_caps;
+    }
+
+    /// Velocity Verlet step with symmetric damping
+    ///
+    /// Damps for half a step, kicks for half a step, drifts for a full
+    /// step and applies the boundary, re-evaluates the forces at the new
+    /// positions, then kicks and damps again.
+    fn step_verlet<K: ForceKernel + Clone + Sync>(&mut self, kernel: &K) {
+        self.compute_accelerations_with(kernel);
+
+        let dt = self.config.dt;
+        let mut speed_caps = 0;
+        for (index, (position, velocity)) in self
+            .positions
+            .iter_mut()
+            .zip(&mut self.velocities)
+            .enumerate()
+        {
+            let motion = Motion::of(&self.motions, self.types[index], &self.config);
+            *velocity *= motion.half_decay;
+            Motion::kick(
+                motion.half_noise,
+                velocity,
+                &mut self.rng,
+                self.config.dimension,
+            );
+            *velocity += self.accelerations[index] * (dt / 2.0);
+            speed_caps += u64::from(motion.limit_speed(velocity));
+            *position += *velocity * dt;
+            self.config.apply_boundary(position, velocity);
+        }
+
+        self.compute_accelerations_with(kernel);
+
+        let config = &self.config;
+        for (index, velocity) in self.velocities.iter_mut().enumerate() {
+            let motion = Motion::of(&self.motions, self.types[index], &self.config);
+            *velocity += self.accelerations[index] * (dt / 2.0);
+            *velocity *= motion.half_decay;
+            Motion::kick(motion.half_noise, velocity, &mut self.rng, config.dimension);
+            speed_caps += u64::from(motion.limit_speed(velocity));
+        }
+        self.health.speed_caps += speed_caps;
+    }
+
+    /// Classic fourth-order Runge-Kutta step
+    ///
+    /// Integrates `x' = v` and `v' = a(x) - rate * v`, where `rate` is
+    /// the damping rate matching the species' velocity half-life. Expects
+    /// `start_positions` to hold the positions at the start of the step.
+    /// Stage positions are wrapped along periodic axes; the full boundary,
+    /// the thermal kick and the speed cap are applied to the combined
+    /// result.
+    fn step_rk4<K: ForceKernel + Clone + Sync>(&mut self, kernel: &K) {
+        let dt = self.config.dt;
+
+        self.start_velocities.clone_from(&self.velocities);
+        self.sum_positions.clear();
+        self.sum_positions.resize(self.positions.len(), Vec3::ZERO);
+        self.sum_velocities.clear();
+        self.sum_velocities
+            .resize(self.velocities.len(), Vec3::ZERO);
+
+        // (weight of the stage derivative, offset of the next stage)
+        let stages = [
+            (1.0 / 6.0, 0.5),
+            (1.0 / 3.0, 0.5),
+            (1.0 / 3.0, 1.0),
+            (1.0 / 6.0, 0.0),
+        ];
+        for (weight, next_offset) in stages {
+            self.compute_accelerations_with(kernel);
+
+            for (index, (sum_position, sum_velocity)) in self
+                .sum_positions
+                .iter_mut()
+                .zip(&mut self.sum_velocities)
+                .enumerate()
+            {
+                let dx = self.velocities[index];
+                let dv = self.accelerations[index]
+                    - dx * Motion::of(&self.motions, self.types[index], &self.config).rate;
+                *sum_position += dx * weight;
+                *sum_velocity += dv * weight;
+
+                self.positions[index] = self
+                    .config
+                    .wrap_position(self.start_positions[index] + dx * (dt * next_offset));
+                self.velocities[index] = self.start_velocities[index] + dv * (dt * next_offset);
+            }
+        }
+
+        let config = &self.config;
+        let mut speed_caps = 0;
+        for (index, (position, velocity)) in self
+            .positions
+            .iter_mut()
+            .zip(&mut self.velocities)
+            .enumerate()
+        {
+            *position = self.start_positions[index] + self.sum_positions[index] * dt;
+            *velocity = self.start_velocities[index] + self.sum_velocities[index] * dt;
+            let motion = Motion::of(&self.motions, self.types[index], &self.config);
+            Motion::kick(motion.noise, velocity, &mut self.rng, config.dimension);
+            speed_caps += u64::from(motion.limit_speed(velocity));
+            config.apply_boundary(position, velocity);
+        }
+        self.health.speed_caps += speed_caps;
+    }
+}
+
+/// Returns the kinetic temperature `Σ m |v|² / (d N)` of a set of particles
+///
+/// Takes the mass and velocity of every particle and the number of
+/// dimensions `d` they move in. With a unit Boltzmann constant this is
+/// the value a thermostatted system approaches at `config.temperature`.
+/// An empty set has zero temperature.
+pub fn kinetic_temperature(
+    particles: impl IntoIterator<Item = (f32, Vec3)>,
+    degrees_of_freedom: u32,
+) -> f64 {
+    let (count, sum) =
+        particles
+            .into_iter()
+            .fold((0u32, 0.0f64), |(count, sum), (mass, velocity)| {
+                (
+                    count + 1,
+                    f64::from(mass).mul_add(f64::from(velocity.length_squared()), sum),
+                )
+            });
+    if count == 0 {
+        0.0
+    } else {
+        sum / (f64::from(degrees_of_freedom) * f64::from(count))
+    }
+}
+
+/// Keeps the values whose entry in `alive` is true, in order
+fn retain_alive<T>(values: &mut Vec<T>, alive: &[bool]) {
+    let mut index = 0;
+    values.retain(|_| {
+        let keep = alive[index];
+        index += 1;
+        keep
+    });
+}
+
+/// Returns a tiny offset that separates two coincident particles
+///
+/// The direction is derived from both indices, so the two particles of a
+/// pair get opposite offsets and the result is the same on every run. In
+/// 2D the direction stays on the `z = 0` plane.
+fn coincident_offset(dimension: Dimension, target: usize, source: usize) -> Vec3 {
+    let (low, high) = (target.min(source) as u64, target.max(source) as u64);
+    let hash = low.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ high.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    #[allow(clippy::cast_precision_loss)]
+    let unit = |bits: u64| (bits & 0xFF_FFFF) as f32 / (1u32 << 24) as f32;
+    let angle = unit(hash >> 40) * TAU;
+    let z = if dimension.is_3d() {
+        2.0f32.mul_add(unit(hash >> 16), -1.0)
+    } else {
+        0.0
+    };
+    let ring = z.mul_add(-z, 1.0).sqrt();
+    let direction = Vec3::new(ring * angle.cos(), ring * angle.sin(), z) * MIN_DISTANCE;
+    if target < source {
+        direction
+    } else {
+        -direction
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::resources::{BoundaryMode, ReactionRule};
+    use bevy::tasks::TaskPoolBuilder;
+
+    /// Returns a simulation with random particles of three species and a
+    /// random interaction table
+    fn random_simulation(config: ParticleConfig, count: usize) -> Simulation {
+        let mut rng = SimRng::new(config.seed);
+        let mut table = ParticleInteractionTable::with_size(3);
+        for target in 0..3 {
+            for source in 0..3 {
+                table.set_interaction(
+                    ParticleType::new(target),
+                    ParticleType::new(source),
+                    rng.random_range(-1.0..1.0),
+                );
+            }
+        }
+        let mut simulation = Simulation::new(config, table);
+        simulation.species = vec![SpeciesProperties::default(); 3];
+        for _ in 0..count {
+            let position = simulation.config.random_position(&mut rng);
+            let particle_type = ParticleType::new(rng.random_range(0..3));
+            let velocity = Vec3::new(rng.gaussian(), rng.gaussian(), 0.0);
+            simulation.add_particle(particle_type, position, velocity);
+        }
+        simulation
+    }
+
+    #[test]
+    fn accelerations_match_brute_force() {
+        let config = ParticleConfig {
+            boundary_x: BoundaryMode::Wrap,
+            map_width: 1500.0,
+            map_height: 1200.0,
+            ..ParticleConfig::default()
+        };
+        let mut simulation = random_simulation(config, 500);
+        simulation.compute_accelerations();
+
+        for index in 0..simulation.len() {
+            let position = simulation.positions()[index];
+            let expected: Vec3 = (0..simulation.len())
+                .filter(|other| *other != index)
+                .map(|other| {
+                    let offset = simulation
+                        .config
+                        .displacement(position, simulation.positions()[other]);
+                    simulation.pair_force(
+                        simulation.types()[index],
+                        simulation.types()[other],
+                        offset,
+                    )
+                })
+                .sum();
+            let actual = simulation.accelerations()[index];
+            assert!(
+                (actual - expected).length() <= 1e-4 * expected.length().max(1.0),
+                "particle {index}: {actual} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn forces_reach_across_a_periodic_seam() {
+        let mut table = ParticleInteractionTable::with_size(1);
+        table.set_interaction(ParticleType::new(0), ParticleType::new(0), 1.0);
+        let mut config = ParticleConfig {
+            boundary_x: BoundaryMode::Wrap,
+            ..ParticleConfig::default()
+        };
+        let mut simulation = Simulation::new(config.clone(), table);
+        simulation.add_particle(
+            ParticleType::new(0),
+            Vec3::new(-925.0, 0.0, 0.0),
+            Vec3::ZERO,
+        );
+        simulation.add_particle(ParticleType::new(0), Vec3::new(925.0, 0.0, 0.0), Vec3::ZERO);
+        simulation.compute_accelerations();
+        // 150 apart through the seam: the attraction points across it.
+        assert!(simulation.accelerations()[0].x < 0.0);
+        assert!(simulation.accelerations()[1].x > 0.0);
+        assert_eq!(simulation.accelerations()[0].y, 0.0);
+
+        config.boundary_x = BoundaryMode::Reflect;
+        simulation.config = config;
+        simulation.compute_accelerations();
+        assert_eq!(simulation.accelerations()[0], Vec3::ZERO);
+    }
+
+    #[test]
+    fn euler_step_decays_and_drifts_a_free_particle() {
+        let config = ParticleConfig {
+            dt: 1.0,
+            dt_half: 1.0,
+            ..ParticleConfig::default()
+        };
+        let mut simulation = Simulation::new(config, ParticleInteractionTable::with_size(1));
+        simulation.add_particle(ParticleType::new(0), Vec3::ZERO, Vec3::new(10.0, -4.0, 0.0));
+        simulation.step();
+        // One half-life halves the velocity before the drift.
+        assert!((simulation.velocities()[0] - Vec3::new(5.0, -2.0, 0.0)).length() < 1e-5);
+        assert!((simulation.positions()[0] - Vec3::new(5.0, -2.0, 0.0)).length() < 1e-5);
+        assert_eq!(simulation.health().steps, 1);
+    }
+
+    #[test]
+    fn particles_stay_inside_the_map() {
+        for integrator in [
+            Integrator::SemiImplicitEuler,
+            Integrator::VelocityVerlet,
+            Integrator::Rk4,
+        ] {
+            let config = ParticleConfig {
+                map_width: 800.0,
+                map_height: 600.0,
+                boundary_y: BoundaryMode::Wrap,
+                integrator,
+                temperature: 5.0,
+                ..ParticleConfig::default()
+            };
+            let mut simulation = random_simulation(config, 300);
+            for _ in 0..20 {
+                simulation.step();
+            }
+            for position in simulation.positions() {
+                assert!(position.is_finite(), "{integrator}");
+                assert!(position.x.abs() <= 400.0, "{integrator}: {position}");
+                assert!(position.y.abs() <= 300.0, "{integrator}: {position}");
+                assert_eq!(position.z, 0.0, "{integrator}");
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_run() {
+        let config = ParticleConfig {
+            temperature: 2.0,
+            seed: 11,
+            ..ParticleConfig::default()
+        };
+        let mut first = random_simulation(config.clone(), 400);
+        let mut second = random_simulation(config, 400);
+        for _ in 0..10 {
+            first.step();
+            second.step();
+        }
+        assert_eq!(first.positions(), second.positions());
+        assert_eq!(first.velocities(), second.velocities());
+    }
+
+    #[test]
+    fn thread_count_does_not_change_forces_or_reactions() {
+        let config = ParticleConfig {
+            seed: 5,
+            ..ParticleConfig::default()
+        };
+        let mut reactions = ReactionTable::new();
+        reactions.add(ReactionRule {
+            reactant: ParticleType::new(0),
+            partner: Some(ParticleType::new(1)),
+            product: ParticleType::new(1),
+            partner_product: Some(ParticleType::new(1)),
+            distance: Some(60.0),
+            probability: 0.3,
+        });
+        reactions.add(ReactionRule::decay(
+            ParticleType::new(2),
+            ParticleType::new(0),
+            0.05,
+        ));
+        let kernel = ConfiguredKernel::from_config(&config);
+        let run = |threads| {
+            let mut simulation = random_simulation(config.clone(), 2000);
+            simulation.set_reactions(&reactions);
+            simulation.find_reactions = true;
+            let pool = TaskPoolBuilder::new().num_threads(threads).build();
+            simulation.compute_accelerations_on(&kernel, &pool);
+            (simulation.accelerations, simulation.pending_reactions)
+        };
+
+        let (accelerations, reactions) = run(1);
+        assert!(!reactions.is_empty());
+        for threads in [2, 4] {
+            let (other_accelerations, other_reactions) = run(threads);
+            assert_eq!(accelerations, other_accelerations);
+            assert_eq!(reactions, other_reactions);
+        }
+    }
+}
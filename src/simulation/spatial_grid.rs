@@ -0,0 +1,235 @@
+//! Uniform spatial grid for neighbor queries
+//!
+//! Particles are bucketed into cells with a counting sort, so the grid
+//! is stored as two flat arrays that are reused from step to step.
+
+use crate::resources::ParticleConfig;
+use crate::simulation::axis_cells::AxisCells;
+use bevy::math::Vec3;
+
+/// Uniform spatial grid for neighbor queries
+///
+/// In 2D the grid has a single layer along z, so a neighbor query visits
+/// 9 cells; in 3D it visits the full 27-cell neighborhood.
+///
+/// The particle indices of cell `c` are `entries[cell_start[c]..cell_start[c + 1]]`.
+/// Within a cell the indices are in ascending order, so iterating the
+/// neighbors of a particle visits them in a deterministic order.
+///
+/// [`SpatialGrid::rebuild`] updates the grid in place and only allocates
+/// when the number of particles or cells grows.
+///
+/// The grid is owned by [`Simulation`](crate::simulation::Simulation),
+/// which the app keeps as a resource, rather than being a resource of
+/// its own: it is scratch state rebuilt from the positions at every
+/// force evaluation, and keeping it next to the particle arrays lets
+/// benchmarks reuse it without a `World`.
+#[derive(Debug, Clone, Default)]
+pub struct SpatialGrid {
+    cells_x: AxisCells,
+    cells_y: AxisCells,
+    cells_z: AxisCells,
+    cell_start: Vec<usize>,
+    entries: Vec<usize>,
+    particle_cell: Vec<usize>,
+    cursor: Vec<usize>,
+}
+
+impl SpatialGrid {
+    /// Creates an empty grid
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuilds the grid for the given positions
+    ///
+    /// Cells are at least `cell_size` wide (the largest interaction
+    /// radius in use) and follow the boundary modes of the map.
+    pub fn rebuild(&mut self, config: &ParticleConfig, cell_size: f32, positions: &[Vec3]) {
+        self.cells_x = AxisCells::new(config.boundary_x, config.map_width, cell_size);
+        self.cells_y = AxisCells::new(config.boundary_y, config.map_height, cell_size);
+        self.cells_z = AxisCells::new(config.boundary_z, config.depth(), cell_size);
+        let cell_count = self.cells_x.count() * self.cells_y.count() * self.cells_z.count();
+
+        let cells = (&self.cells_x, &self.cells_y, &self.cells_z);
+        self.particle_cell.clear();
+        self.particle_cell
+            .extend(positions.iter().map(|position| flat_cell(cells, *position)));
+
+        // Counting sort: count, prefix sum, then scatter in index order.
+        self.cell_start.clear();
+        self.cell_start.resize(cell_count + 1, 0);
+        for &cell in &self.particle_cell {
+            self.cell_start[cell] += 1;
+        }
+        let mut total = 0;
+        for start in &mut self.cell_start {
+            let count = *start;
+            *start = total;
+            total += count;
+        }
+
+        self.entries.clear();
+        self.entries.resize(positions.len(), 0);
+        self.cursor.clear();
+        self.cursor
+            .extend_from_slice(&self.cell_start[..cell_count]);
+        for (index, &cell) in self.particle_cell.iter().enumerate() {
+            self.entries[self.cursor[cell]] = index;
+            self.cursor[cell] += 1;
+        }
+    }
+
+    /// Returns the number of cells in the grid
+    #[must_use]
+    pub const fn cell_count(&self) -> usize {
+        self.cell_start.len().saturating_sub(1)
+    }
+
+    /// Returns the particle indices stored in a cell
+    #[must_use]
+    pub fn cell(&self, cell: usize) -> &[usize] {
+        &self.entries[self.cell_start[cell]..self.cell_start[cell + 1]]
+    }
+
+    /// Returns the particle indices in the cells around `position`
+    ///
+    /// Yields one slice per neighboring cell (including the cell that
+    /// contains `position` itself).
+    pub fn neighbors(&self, position: Vec3) -> impl Iterator<Item = &[usize]> {
+        let cell_x = self.cells_x.cell(position.x);
+        let cell_y = self.cells_y.cell(position.y);
+        let cell_z = self.cells_z.cell(position.z);
+        let width = self.cells_x.count();
+        let height = self.cells_y.count();
+        self.cells_x.neighbors(cell_x).flat_map(move |x| {
+            self.cells_y.neighbors(cell_y).flat_map(move |y| {
+                self.cells_z
+                    .neighbors(cell_z)
+                    .map(move |z| self.cell((z * height + y) * width + x))
+            })
+        })
+    }
+}
+
+/// Returns the flat cell index containing `position`
+fn flat_cell(
+    (cells_x, cells_y, cells_z): (&AxisCells, &AxisCells, &AxisCells),
+    position: Vec3,
+) -> usize {
+    let z = cells_z.cell(position.z);
+    let y = cells_y.cell(position.y);
+    (z * cells_y.count() + y) * cells_x.count() + cells_x.cell(position.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{BoundaryMode, Dimension, SimRng};
+    use rand::Rng;
+    use std::collections::BTreeSet;
+
+    /// Returns `count` random positions, some of them outside the map
+    fn positions(config: &ParticleConfig, count: usize, seed: u64) -> Vec<Vec3> {
+        let mut rng = SimRng::new(seed);
+        (0..count)
+            .map(|_| config.random_position(&mut rng) * rng.random_range(0.5..1.2))
+            .collect()
+    }
+
+    /// Checks that every pair closer than `cell_size` is found by the grid
+    fn assert_finds_all_pairs(config: &ParticleConfig, cell_size: f32) {
+        let positions = positions(config, 600, 7);
+        let mut grid = SpatialGrid::new();
+        grid.rebuild(config, cell_size, &positions);
+
+        for (index, position) in positions.iter().enumerate() {
+            let found: BTreeSet<usize> = grid.neighbors(*position).flatten().copied().collect();
+            assert!(found.contains(&index));
+            for (other, other_position) in positions.iter().enumerate() {
+                let distance = config.displacement(*position, *other_position).length();
+                if distance < cell_size {
+                    assert!(
+                        found.contains(&other),
+                        "{index} and {other} are {distance} apart but not neighbors"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_match_brute_force_with_open_boundaries() {
+        let config = ParticleConfig::default();
+        assert_finds_all_pairs(&config, config.r);
+        assert_finds_all_pairs(&config, 77.0);
+    }
+
+    #[test]
+    fn neighbors_match_brute_force_across_periodic_seams() {
+        let config = ParticleConfig {
+            boundary_x: BoundaryMode::Wrap,
+            boundary_y: BoundaryMode::Wrap,
+            map_width: 1000.0,
+            map_height: 700.0,
+            ..ParticleConfig::default()
+        };
+        assert_finds_all_pairs(&config, 300.0);
+        // Fewer than three cells per axis must not visit a cell twice.
+        assert_finds_all_pairs(&config, 450.0);
+    }
+
+    #[test]
+    fn neighbors_match_brute_force_in_3d() {
+        let config = ParticleConfig {
+            dimension: Dimension::Three,
+            boundary_z: BoundaryMode::Wrap,
+            map_width: 900.0,
+            map_height: 900.0,
+            map_depth: 600.0,
+            ..ParticleConfig::default()
+        };
+        assert_finds_all_pairs(&config, 200.0);
+    }
+
+    #[test]
+    fn cell_count_stays_bounded() {
+        let config = ParticleConfig {
+            dimension: Dimension::Three,
+            boundary_x: BoundaryMode::Wrap,
+            map_width: 1e9,
+            map_height: 1e9,
+            map_depth: 1e9,
+            ..ParticleConfig::default()
+        };
+        let mut grid = SpatialGrid::new();
+        for cell_size in [0.001, 0.0, -5.0, f32::NAN, f32::INFINITY] {
+            grid.rebuild(&config, cell_size, &positions(&config, 50, 1));
+            assert!(grid.cell_count() <= 130 * 130 * 130, "{cell_size}");
+        }
+        assert_finds_all_pairs(&config, 0.001);
+    }
+
+    #[test]
+    fn neighbor_cells_are_distinct_and_sorted() {
+        let config = ParticleConfig {
+            boundary_x: BoundaryMode::Wrap,
+            map_width: 500.0,
+            ..ParticleConfig::default()
+        };
+        let positions = positions(&config, 300, 3);
+        let mut grid = SpatialGrid::new();
+        grid.rebuild(&config, 300.0, &positions);
+
+        for position in &positions {
+            let mut seen = BTreeSet::new();
+            for cell in grid.neighbors(*position) {
+                assert!(cell.windows(2).all(|pair| pair[0] < pair[1]));
+                for index in cell {
+                    assert!(seen.insert(*index), "particle {index} visited twice");
+                }
+            }
+        }
+    }
+}
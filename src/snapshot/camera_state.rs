@@ -0,0 +1,76 @@
+//! Saved camera state
+//!
+//! The 2D camera is described by its translation and zoom, the 3D orbit
+//! camera by its focus, distance and angles.
+
+use crate::components::OrbitCamera;
+use bevy::math::Vec3;
+use bevy::transform::components::Transform;
+use serde::{Deserialize, Serialize};
+
+/// Saved state of the 3D orbit camera
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrbitState {
+    /// Point the camera looks at
+    pub focus: [f32; 3],
+    /// Distance from the focus point
+    pub radius: f32,
+    /// Rotation around the y axis in radians
+    pub yaw: f32,
+    /// Elevation above the xz plane in radians
+    pub pitch: f32,
+}
+
+impl OrbitState {
+    /// Returns the orbit camera this state describes
+    #[must_use]
+    pub const fn to_orbit_camera(&self) -> OrbitCamera {
+        OrbitCamera {
+            focus: Vec3::from_array(self.focus),
+            radius: self.radius,
+            yaw: self.yaw,
+            pitch: self.pitch,
+        }
+    }
+}
+
+impl From<&OrbitCamera> for OrbitState {
+    fn from(orbit: &OrbitCamera) -> Self {
+        Self {
+            focus: orbit.focus.to_array(),
+            radius: orbit.radius,
+            yaw: orbit.yaw,
+            pitch: orbit.pitch,
+        }
+    }
+}
+
+/// Saved camera state
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraState {
+    /// Translation of the 2D camera
+    pub translation: [f32; 3],
+    /// Zoom of the 2D camera (its uniform transform scale)
+    pub scale: f32,
+    /// State of the 3D orbit camera, if one exists
+    pub orbit: Option<OrbitState>,
+}
+
+impl CameraState {
+    /// Creates the state of a 2D camera transform without orbit camera
+    #[must_use]
+    pub const fn new(transform: &Transform) -> Self {
+        Self {
+            translation: transform.translation.to_array(),
+            scale: transform.scale.x,
+            orbit: None,
+        }
+    }
+
+    /// Returns the 2D camera transform this state describes
+    #[must_use]
+    pub const fn transform(&self) -> Transform {
+        Transform::from_translation(Vec3::from_array(self.translation))
+            .with_scale(Vec3::splat(self.scale))
+    }
+}
@@ -0,0 +1,22 @@
+//! Snapshot module
+//!
+//! This module saves and restores the simulation state: every particle
+//! together with the configuration, species, interaction table, random
+//! number generators, step count and camera. Obstacles, membranes, force
+//! fields, reactions and the ecosystem, evolution and annealing settings
+//! are not part of a snapshot. Snapshots are versioned and come in a
+//! human-readable RON form and a compact binary form.
+
+mod camera_state;
+mod particle_state;
+mod physics_state;
+mod simulation_snapshot;
+mod snapshot_format;
+mod species_state;
+
+pub use camera_state::{CameraState, OrbitState};
+pub use particle_state::ParticleState;
+pub use physics_state::PhysicsState;
+pub use simulation_snapshot::{SNAPSHOT_VERSION, Snapshot, SnapshotError};
+pub use snapshot_format::{SnapshotFormat, SnapshotFormatError};
+pub use species_state::SpeciesState;
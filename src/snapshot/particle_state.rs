@@ -0,0 +1,55 @@
+//! Saved state of one particle
+
+use crate::components::{Genome, ParticleType};
+use bevy::math::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// Saved state of one particle
+///
+/// `energy` and `genome` are only present for particles that had them,
+/// that is in ecosystem and evolution mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticleState {
+    /// Index of the species in the registry
+    pub species: usize,
+    /// Physics position
+    pub position: [f32; 3],
+    /// Velocity
+    pub velocity: [f32; 3],
+    /// Energy in ecosystem mode
+    pub energy: Option<f32>,
+    /// Genome in evolution mode
+    pub genome: Option<Genome>,
+}
+
+impl ParticleState {
+    /// Creates the state of a particle without energy or genome
+    #[must_use]
+    pub const fn new(particle_type: ParticleType, position: Vec3, velocity: Vec3) -> Self {
+        Self {
+            species: particle_type.index(),
+            position: position.to_array(),
+            velocity: velocity.to_array(),
+            energy: None,
+            genome: None,
+        }
+    }
+
+    /// Returns the type of the particle
+    #[must_use]
+    pub const fn particle_type(&self) -> ParticleType {
+        ParticleType::new(self.species)
+    }
+
+    /// Returns the position of the particle
+    #[must_use]
+    pub const fn position(&self) -> Vec3 {
+        Vec3::from_array(self.position)
+    }
+
+    /// Returns the velocity of the particle
+    #[must_use]
+    pub const fn velocity(&self) -> Vec3 {
+        Vec3::from_array(self.velocity)
+    }
+}
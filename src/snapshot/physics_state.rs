@@ -0,0 +1,41 @@
+//! Saved state of the physics engine
+//!
+//! The [`Simulation`] keeps a few values between steps that are not
+//! stored in any component or resource. Restoring them makes a loaded
+//! snapshot continue exactly like the run it was taken from.
+
+use crate::resources::SimRng;
+use crate::simulation::Simulation;
+use serde::{Deserialize, Serialize};
+
+/// Saved state of the physics engine
+///
+/// `rng` drives thermal noise, repair respawns, mutations and birth
+/// offsets, `steps` selects the per-particle reaction and feeding
+/// streams, and `next_lineage` numbers the lineages founded next.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhysicsState {
+    /// Generator of the simulation
+    pub rng: SimRng,
+    /// Number of steps simulated so far
+    pub steps: u64,
+    /// Last lineage number handed out in evolution mode
+    pub next_lineage: u64,
+}
+
+impl PhysicsState {
+    /// Continues `simulation` from this state
+    pub fn restore(&self, simulation: &mut Simulation) {
+        simulation.resume(self.rng.clone(), self.steps, self.next_lineage);
+    }
+}
+
+impl From<&Simulation> for PhysicsState {
+    fn from(simulation: &Simulation) -> Self {
+        Self {
+            rng: simulation.rng().clone(),
+            steps: simulation.health().steps,
+            next_lineage: simulation.next_lineage(),
+        }
+    }
+}
@@ -0,0 +1,561 @@
+//! Simulation snapshot
+//!
+//! Holds the particles together with what is needed to continue the run:
+//! the configuration, species, interaction table, random number
+//! generators, step count and camera. Snapshots are written as RON text
+//! or as a compact binary file and carry a version number, so files from
+//! newer releases are rejected instead of misread.
+
+use crate::components::Genome;
+use crate::resources::{
+    InteractionProfile, ParticleConfig, ParticleInteractionTable, SimRng, SpeciesRegistry,
+};
+use crate::snapshot::{CameraState, ParticleState, PhysicsState, SnapshotFormat, SpeciesState};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// Version written to new snapshots
+///
+/// Version 2 added the [`PhysicsState`]; version 1 files still load.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// First bytes of every binary snapshot
+const MAGIC: [u8; 4] = *b"PLSB";
+
+/// Genome length marking a particle without genome in binary snapshots
+const NO_GENOME: u32 = u32::MAX;
+
+/// Error returned when a snapshot cannot be read
+#[derive(Debug)]
+pub struct SnapshotError {
+    /// Description of the problem
+    pub message: String,
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid snapshot: {}", self.message)
+    }
+}
+
+impl Error for SnapshotError {}
+
+/// Simulation snapshot
+///
+/// A snapshot is partial: obstacles, membranes, force fields, reactions
+/// and the ecosystem, evolution and annealing settings are not saved, so
+/// a loaded run uses whatever is set up when it is loaded. With the same
+/// setup it continues exactly like the run it was taken from.
+///
+/// `interactions` holds the profile of every (target, source) pair of
+/// `species` in row-major order, like the
+/// [`ParticleInteractionTable`]. Particle types index into `species`.
+///
+/// The binary form starts with the bytes `PLSB` and the version, followed
+/// by a length-prefixed RON header with everything except the particles
+/// and then the particles packed as little-endian numbers. Loading
+/// detects the form from these first bytes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Format version the snapshot was written with
+    pub version: u32,
+    /// Particle configuration
+    pub config: ParticleConfig,
+    /// Species in registry order
+    pub species: Vec<SpeciesState>,
+    /// Interaction profiles of all species pairs
+    pub interactions: Vec<InteractionProfile>,
+    /// Random number generator state
+    pub rng: SimRng,
+    /// Physics engine state, missing in version 1 snapshots
+    #[serde(default)]
+    pub physics: Option<PhysicsState>,
+    /// Camera state, if a camera was present
+    pub camera: Option<CameraState>,
+    /// All living particles
+    pub particles: Vec<ParticleState>,
+}
+
+impl Snapshot {
+    /// Creates a snapshot without particles, physics state or camera
+    #[must_use]
+    pub fn new(
+        config: &ParticleConfig,
+        registry: &SpeciesRegistry,
+        table: &ParticleInteractionTable,
+        rng: &SimRng,
+    ) -> Self {
+        let species = registry
+            .types()
+            .filter_map(|ptype| registry.get(ptype))
+            .map(SpeciesState::from)
+            .collect();
+        let interactions = registry
+            .types()
+            .flat_map(|target| registry.types().map(move |source| (target, source)))
+            .map(|(target, source)| table.get_profile(target, source))
+            .collect();
+        Self {
+            version: SNAPSHOT_VERSION,
+            config: config.clone(),
+            species,
+            interactions,
+            rng: rng.clone(),
+            physics: None,
+            camera: None,
+            particles: Vec::new(),
+        }
+    }
+
+    /// Returns the species registry of the snapshot
+    #[must_use]
+    pub fn registry(&self) -> SpeciesRegistry {
+        let mut registry = SpeciesRegistry::new();
+        for state in &self.species {
+            let species = state.to_species();
+            let ptype = registry.add(species.name.clone(), Some(species.color));
+            if let Some(entry) = registry.get_mut(ptype) {
+                *entry = species;
+            }
+        }
+        registry
+    }
+
+    /// Returns the interaction table of the snapshot
+    #[must_use]
+    pub fn interaction_table(&self) -> ParticleInteractionTable {
+        let registry = self.registry();
+        let mut table = ParticleInteractionTable::with_size(registry.len());
+        let pairs = registry
+            .types()
+            .flat_map(|target| registry.types().map(move |source| (target, source)));
+        for ((target, source), profile) in pairs.zip(&self.interactions) {
+            table.set_profile(target, source, *profile);
+        }
+        table
+    }
+
+    /// Checks the version, the values and that all indices are in range
+    ///
+    /// The configuration and species properties go through the same
+    /// checks as config files and the `set` and `species` console
+    /// commands.
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot was written by a newer version,
+    /// its configuration or a species property is out of range, its
+    /// interactions or particle types do not match its species, an
+    /// interaction profile is out of range, or its physics state was
+    /// seeded with another seed than its configuration
+    pub fn validate(&self) -> Result<(), SnapshotError> {
+        if self.version == 0 || self.version > SNAPSHOT_VERSION {
+            return Err(invalid(format!(
+                "version {} is not supported (expected 1 to {SNAPSHOT_VERSION})",
+                self.version
+            )));
+        }
+        self.config
+            .validate()
+            .map_err(|e| invalid(format!("config: {e}")))?;
+        if let Some((species, e)) = self
+            .species
+            .iter()
+            .find_map(|species| Some((species, species.properties.validate().err()?)))
+        {
+            return Err(invalid(format!("species {}: {e}", species.name)));
+        }
+        let count = self.species.len();
+        if self.interactions.len() != count * count {
+            return Err(invalid(format!(
+                "{} interactions do not match {count} species",
+                self.interactions.len()
+            )));
+        }
+        if let Some(e) = self.interactions.iter().find_map(|p| p.validate().err()) {
+            return Err(invalid(e));
+        }
+        if let Some(physics) = self
+            .physics
+            .as_ref()
+            .filter(|physics| physics.rng.seed() != self.config.seed)
+        {
+            return Err(invalid(format!(
+                "physics seed {} does not match config seed {}",
+                physics.rng.seed(),
+                self.config.seed
+            )));
+        }
+        if let Some(particle) = self.particles.iter().find(|p| p.species >= count) {
+            return Err(invalid(format!(
+                "particle species {} is out of range for {count} species",
+                particle.species
+            )));
+        }
+        Ok(())
+    }
+
+    /// Saves the snapshot to a file
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot cannot be serialized or the file
+    /// cannot be written
+    pub fn to_file(&self, path: &str, format: SnapshotFormat) -> Result<(), Box<dyn Error>> {
+        std::fs::write(path, self.to_bytes(format)?)?;
+        bevy::log::info!(
+            "Saved snapshot with {} particles to {} ({})",
+            self.particles.len(),
+            path,
+            format
+        );
+        Ok(())
+    }
+
+    /// Loads a snapshot from a file, detecting its format
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or is not a valid
+    /// snapshot
+    pub fn from_file(path: &str) -> Result<Self, Box<dyn Error>> {
+        let snapshot = Self::from_bytes(&std::fs::read(path)?)?;
+        bevy::log::info!(
+            "Loaded snapshot with {} particles from {}",
+            snapshot.particles.len(),
+            path
+        );
+        Ok(snapshot)
+    }
+
+    /// Serializes the snapshot in the given format
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot cannot be serialized
+    pub fn to_bytes(&self, format: SnapshotFormat) -> Result<Vec<u8>, Box<dyn Error>> {
+        match format {
+            SnapshotFormat::Ron => Ok(to_ron(self)?.into_bytes()),
+            SnapshotFormat::Binary => self.to_binary(),
+        }
+    }
+
+    /// Deserializes and validates a snapshot in either format
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a valid snapshot
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let snapshot = if bytes.starts_with(&MAGIC) {
+            Self::from_binary(&bytes[MAGIC.len()..])?
+        } else {
+            ron::from_str(std::str::from_utf8(bytes)?)?
+        };
+        snapshot.validate()?;
+        Ok(snapshot)
+    }
+
+    /// Writes the binary form
+    fn to_binary(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let header = to_ron(&Self {
+            version: self.version,
+            config: self.config.clone(),
+            species: self.species.clone(),
+            interactions: self.interactions.clone(),
+            rng: self.rng.clone(),
+            physics: self.physics.clone(),
+            camera: self.camera,
+            particles: Vec::new(),
+        })?;
+
+        let mut bytes = Vec::with_capacity(header.len() + self.particles.len() * 40 + 24);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.extend_from_slice(&u64::try_from(header.len())?.to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(&u64::try_from(self.particles.len())?.to_le_bytes());
+        for particle in &self.particles {
+            bytes.extend_from_slice(&u32::try_from(particle.species)?.to_le_bytes());
+            for value in particle.position.iter().chain(&particle.velocity) {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            bytes.extend_from_slice(&particle.energy.unwrap_or(f32::NAN).to_le_bytes());
+            match &particle.genome {
+                Some(genome) => {
+                    bytes.extend_from_slice(&u32::try_from(genome.strengths.len())?.to_le_bytes());
+                    bytes.extend_from_slice(&genome.lineage.to_le_bytes());
+                    bytes.extend_from_slice(&genome.generation.to_le_bytes());
+                    for strength in &genome.strengths {
+                        bytes.extend_from_slice(&strength.to_le_bytes());
+                    }
+                }
+                None => bytes.extend_from_slice(&NO_GENOME.to_le_bytes()),
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Reads the binary form after the magic bytes
+    fn from_binary(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut reader = ByteReader { bytes };
+        let version = reader.u32()?;
+        if version == 0 || version > SNAPSHOT_VERSION {
+            return Err(invalid(format!(
+                "version {version} is not supported (expected 1 to {SNAPSHOT_VERSION})"
+            ))
+            .into());
+        }
+        let header_len = usize::try_from(reader.u64()?)?;
+        let mut snapshot: Self = ron::from_str(std::str::from_utf8(reader.take(header_len)?)?)?;
+
+        let count = usize::try_from(reader.u64()?)?;
+        snapshot.particles = Vec::with_capacity(count.min(bytes.len() / 32));
+        for _ in 0..count {
+            let species = usize::try_from(reader.u32()?)?;
+            let mut values = [0.0; 6];
+            for value in &mut values {
+                *value = reader.f32()?;
+            }
+            let energy = Some(reader.f32()?).filter(|energy| !energy.is_nan());
+            let genome = match reader.u32()? {
+                NO_GENOME => None,
+                len => {
+                    let lineage = reader.u64()?;
+                    let generation = reader.u32()?;
+                    let strengths = (0..len)
+                        .map(|_| reader.f32())
+                        .collect::<Result<Vec<f32>, _>>()?;
+                    Some(Genome {
+                        strengths,
+                        lineage,
+                        generation,
+                    })
+                }
+            };
+            snapshot.particles.push(ParticleState {
+                species,
+                position: [values[0], values[1], values[2]],
+                velocity: [values[3], values[4], values[5]],
+                energy,
+                genome,
+            });
+        }
+        if !reader.bytes.is_empty() {
+            return Err(invalid(format!("{} trailing bytes", reader.bytes.len())).into());
+        }
+        Ok(snapshot)
+    }
+}
+
+/// Reads little-endian numbers from the front of a byte slice
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    /// Removes and returns the next `len` bytes
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if len > self.bytes.len() {
+            return Err(invalid("unexpected end of file".to_string()));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    /// Removes and returns the next `N` bytes as an array
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    /// Removes and returns the next `u32`
+    fn u32(&mut self) -> Result<u32, SnapshotError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    /// Removes and returns the next `u64`
+    fn u64(&mut self) -> Result<u64, SnapshotError> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    /// Removes and returns the next `f32`
+    fn f32(&mut self) -> Result<f32, SnapshotError> {
+        self.array().map(f32::from_le_bytes)
+    }
+}
+
+/// Serializes a value as pretty RON text
+fn to_ron<T: Serialize>(value: &T) -> Result<String, ron::Error> {
+    ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+}
+
+/// Creates a snapshot error with a message
+const fn invalid(message: String) -> SnapshotError {
+    SnapshotError { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::ParticleType;
+    use crate::resources::{Evolution, ReactionRule, ReactionTable, SpeciesProperties};
+    use crate::simulation::Simulation;
+    use bevy::math::Vec3;
+    use rand::Rng;
+
+    /// Sets up what snapshots do not save, the same way for every run
+    fn set_up(simulation: &mut Simulation) {
+        let mut reactions = ReactionTable::new();
+        reactions.add(ReactionRule {
+            reactant: ParticleType::new(0),
+            partner: Some(ParticleType::new(1)),
+            product: ParticleType::new(2),
+            partner_product: Some(ParticleType::new(1)),
+            distance: Some(60.0),
+            probability: 0.2,
+        });
+        reactions.add(ReactionRule::decay(
+            ParticleType::new(2),
+            ParticleType::new(0),
+            0.02,
+        ));
+        simulation.set_reactions(&reactions);
+        simulation.set_evolution(Evolution {
+            enabled: true,
+            respawn_interval: Some(3),
+            respawn_fraction: 0.05,
+            ..Evolution::new()
+        });
+        simulation.species = vec![SpeciesProperties::default(); 3];
+    }
+
+    /// Returns a hot simulation of three species that ran a few steps
+    fn running_simulation() -> Simulation {
+        let config = ParticleConfig {
+            temperature: 1.5,
+            seed: 21,
+            ..ParticleConfig::default()
+        };
+        let mut rng = SimRng::new(config.seed);
+        let mut table = ParticleInteractionTable::with_size(3);
+        for value in table.as_matrix_mut() {
+            *value = rng.random_range(-1.0..1.0);
+        }
+        let mut simulation = Simulation::new(config, table);
+        set_up(&mut simulation);
+        for _ in 0..300 {
+            let position = simulation.config.random_position(&mut rng);
+            let particle_type = ParticleType::new(rng.random_range(0..3));
+            simulation.add_particle(particle_type, position, Vec3::ZERO);
+        }
+        for _ in 0..5 {
+            simulation.step();
+        }
+        simulation
+    }
+
+    /// Captures a simulation like the `save_snapshot` command does
+    fn capture(simulation: &Simulation) -> Snapshot {
+        let registry = SpeciesRegistry::generate(3);
+        let rng = SimRng::new(simulation.config.seed);
+        let mut snapshot = Snapshot::new(
+            &simulation.config,
+            &registry,
+            &simulation.interaction_table,
+            &rng,
+        );
+        snapshot.physics = Some(PhysicsState::from(simulation));
+        snapshot.particles = (0..simulation.len())
+            .map(|index| ParticleState {
+                energy: Some(simulation.energies()[index]),
+                genome: Some(simulation.genomes()[index].clone()),
+                ..ParticleState::new(
+                    simulation.types()[index],
+                    simulation.positions()[index],
+                    simulation.velocities()[index],
+                )
+            })
+            .collect();
+        snapshot
+    }
+
+    /// Rebuilds a simulation from a snapshot like loading one does
+    fn restore(snapshot: &Snapshot) -> Simulation {
+        let mut simulation = Simulation::new(snapshot.config.clone(), snapshot.interaction_table());
+        set_up(&mut simulation);
+        for particle in &snapshot.particles {
+            let index = simulation.add_particle(
+                particle.particle_type(),
+                particle.position(),
+                particle.velocity(),
+            );
+            simulation.energies_mut()[index] = particle.energy.unwrap_or_default();
+            if let Some(genome) = &particle.genome {
+                simulation.genomes_mut()[index].clone_from(genome);
+            }
+        }
+        if let Some(physics) = &snapshot.physics {
+            physics.restore(&mut simulation);
+        }
+        simulation
+    }
+
+    #[test]
+    fn round_trips_through_both_formats() {
+        let snapshot = capture(&running_simulation());
+        for format in [SnapshotFormat::Ron, SnapshotFormat::Binary] {
+            let bytes = snapshot.to_bytes(format).unwrap();
+            let loaded = Snapshot::from_bytes(&bytes).unwrap();
+            assert_eq!(loaded, snapshot, "{format}");
+            assert_eq!(loaded.to_bytes(format).unwrap(), bytes, "{format}");
+        }
+    }
+
+    #[test]
+    fn loaded_snapshot_continues_like_the_original() {
+        let original = running_simulation();
+        for format in [SnapshotFormat::Ron, SnapshotFormat::Binary] {
+            let bytes = capture(&original).to_bytes(format).unwrap();
+            let mut loaded = restore(&Snapshot::from_bytes(&bytes).unwrap());
+            let mut uninterrupted = original.clone();
+            for _ in 0..10 {
+                uninterrupted.step();
+                loaded.step();
+            }
+            assert_eq!(loaded.positions(), uninterrupted.positions(), "{format}");
+            assert_eq!(loaded.velocities(), uninterrupted.velocities(), "{format}");
+            assert_eq!(loaded.types(), uninterrupted.types(), "{format}");
+            assert_eq!(loaded.genomes(), uninterrupted.genomes(), "{format}");
+            assert_eq!(loaded.health().steps, uninterrupted.health().steps);
+        }
+    }
+
+    #[test]
+    fn physics_seed_must_match_config() {
+        let mut snapshot = capture(&running_simulation());
+        snapshot.physics = Some(PhysicsState {
+            rng: SimRng::new(snapshot.config.seed + 1),
+            steps: 0,
+            next_lineage: 0,
+        });
+        assert!(snapshot.validate().is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_config_and_species() {
+        let snapshot = capture(&running_simulation());
+        assert!(snapshot.validate().is_ok());
+
+        for change in [
+            |snapshot: &mut Snapshot| snapshot.config.r = 0.0,
+            |snapshot: &mut Snapshot| snapshot.config.dt = 0.0,
+            |snapshot: &mut Snapshot| snapshot.config.substeps = 0,
+            |snapshot: &mut Snapshot| snapshot.species[0].properties.mass = 0.0,
+        ] {
+            let mut invalid = snapshot.clone();
+            change(&mut invalid);
+            assert!(invalid.validate().is_err());
+            let bytes = invalid.to_bytes(SnapshotFormat::Ron).unwrap();
+            assert!(Snapshot::from_bytes(&bytes).is_err());
+        }
+    }
+}
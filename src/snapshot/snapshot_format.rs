@@ -0,0 +1,77 @@
+//! Snapshot file formats
+//!
+//! Selects how a snapshot is written: readable RON text or compact
+//! binary.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+/// File format of a snapshot
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// Human-readable RON text, convenient to inspect and edit
+    #[default]
+    Ron,
+    /// Compact little-endian binary, much smaller for large particle sets
+    Binary,
+}
+
+impl SnapshotFormat {
+    /// Returns string representation of this format
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ron => "ron",
+            Self::Binary => "binary",
+        }
+    }
+
+    /// Returns the format matching the extension of `path`
+    ///
+    /// `.bin` and `.snap` files are binary; everything else is RON.
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        match Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("bin" | "snap") => Self::Binary,
+            _ => Self::Ron,
+        }
+    }
+}
+
+impl Display for SnapshotFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an invalid snapshot format string
+#[derive(Debug)]
+pub struct SnapshotFormatError;
+
+impl Display for SnapshotFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid snapshot format. Expected one of: ron, binary")
+    }
+}
+
+impl Error for SnapshotFormatError {}
+
+impl FromStr for SnapshotFormat {
+    type Err = SnapshotFormatError;
+
+    fn from_str(s: &str) -> Result<Self, SnapshotFormatError> {
+        match s.to_lowercase().as_str() {
+            "ron" => Ok(Self::Ron),
+            "binary" | "bin" => Ok(Self::Binary),
+            _ => Err(SnapshotFormatError),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Saved state of one species
+//!
+//! Colors are stored as sRGBA components so the snapshot does not depend
+//! on the serialization of Bevy's color types.
+
+use crate::resources::{Species, SpeciesProperties};
+use bevy::color::{Color, Srgba};
+use serde::{Deserialize, Serialize};
+
+/// Saved state of one species
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesState {
+    /// Display name
+    pub name: String,
+    /// Render color as sRGB red, green, blue and alpha
+    pub color: [f32; 4],
+    /// Mass, damping, speed cap, collision radius and render size
+    pub properties: SpeciesProperties,
+}
+
+impl SpeciesState {
+    /// Returns the species this state describes
+    #[must_use]
+    pub fn to_species(&self) -> Species {
+        let [red, green, blue, alpha] = self.color;
+        Species {
+            name: self.name.clone(),
+            color: Color::Srgba(Srgba::new(red, green, blue, alpha)),
+            properties: self.properties,
+        }
+    }
+}
+
+impl From<&Species> for SpeciesState {
+    fn from(species: &Species) -> Self {
+        let color = species.color.to_srgba();
+        Self {
+            name: species.name.clone(),
+            color: [color.red, color.green, color.blue, color.alpha],
+            properties: species.properties,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Temperature annealing system
+//!
+//! This system runs in `FixedUpdate` before `update_particle` and moves
+//! `config.temperature` along the active [`Annealing`] ramp.
+
+use crate::resources::{Annealing, ParticleConfig};
+use bevy::prelude::*;
+
+/// Temperature annealing system
+///
+/// Advances the active ramp by the simulated time of one physics tick
+/// (`config.dt * config.time_scale`) and writes the resulting
+/// temperature into the config. Does nothing once the ramp has ended,
+/// so the config is not marked as changed every tick.
+pub fn anneal_temperature(mut annealing: ResMut<Annealing>, mut config: ResMut<ParticleConfig>) {
+    if !annealing.is_active() {
+        return;
+    }
+    config.temperature = annealing.advance(config.dt * config.time_scale);
+    if !annealing.is_active() {
+        bevy::log::info!(
+            "Annealing finished at temperature {:.3}",
+            config.temperature
+        );
+    }
+}
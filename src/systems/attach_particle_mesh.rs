@@ -0,0 +1,42 @@
+//! Attach render components to new particles
+//!
+//! Spawning a particle only adds its simulation components. This
+//! observer gives every entity that becomes a particle a transform, a
+//! previous position for interpolation and the mesh and material of its
+//! species, so the simulation itself never touches render assets.
+
+use crate::bundles::{Particle, ParticleAssets};
+use crate::components::{ParticleMarker, ParticleType, Position};
+use crate::resources::{ParticleConfig, SpeciesRegistry};
+use bevy::prelude::*;
+
+/// Attach render components to new particles
+///
+/// Runs whenever the [`ParticleMarker`] is added to an entity, both for
+/// newly spawned particles and for revived dormant ones. Places the
+/// [`Transform`] at the particle's [`Position`], makes it visible and
+/// inserts a circle (2D) or sphere (3D) sized and colored by the
+/// particle's species.
+#[allow(clippy::needless_pass_by_value)]
+pub fn attach_particle_mesh(
+    add: On<Add, ParticleMarker>,
+    mut commands: Commands,
+    mut assets: ParticleAssets,
+    particles: Query<(&ParticleType, &Position)>,
+    registry: Res<SpeciesRegistry>,
+    config: Res<ParticleConfig>,
+) {
+    let Ok((particle_type, position)) = particles.get(add.entity) else {
+        return;
+    };
+    let Some(species) = registry.get(*particle_type) else {
+        return;
+    };
+    Particle::insert_render(
+        &mut commands.entity(add.entity),
+        &mut assets,
+        position.value,
+        species,
+        config.dimension,
+    );
+}
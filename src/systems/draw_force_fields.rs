@@ -0,0 +1,59 @@
+//! Draw force fields with gizmos
+//!
+//! Shows the range of every [`ForceField`] as a circle (a sphere in 3D)
+//! and the direction of gravity fields as an arrow.
+
+use crate::components::{ForceField, ForceFieldKind};
+use crate::resources::ParticleConfig;
+use bevy::color::palettes::css::{AQUA, GOLD, ORANGE_RED, SILVER, VIOLET};
+use bevy::prelude::*;
+
+/// Length of the arrow drawn for gravity and vortex fields
+const ARROW_LENGTH: f32 = 80.0;
+
+/// Draw force fields with gizmos
+///
+/// Fields with an infinite range only show their center marker and,
+/// for gravity, the direction arrow.
+#[allow(clippy::needless_pass_by_value)]
+pub fn draw_force_fields(
+    mut gizmos: Gizmos,
+    fields: Query<&ForceField>,
+    config: Res<ParticleConfig>,
+) {
+    for field in &fields {
+        let color = match field.kind {
+            ForceFieldKind::Gravity => GOLD,
+            ForceFieldKind::Radial if field.strength < 0.0 => ORANGE_RED,
+            ForceFieldKind::Radial => AQUA,
+            ForceFieldKind::Vortex => VIOLET,
+            ForceFieldKind::Damping => SILVER,
+        };
+        let isometry = Isometry3d::from_translation(field.center);
+
+        if field.radius.is_finite() {
+            if config.dimension.is_3d() {
+                gizmos.sphere(isometry, field.radius, color);
+            } else {
+                gizmos.circle(isometry, field.radius, color);
+            }
+        }
+        gizmos.circle(isometry, 4.0, color);
+
+        match field.kind {
+            ForceFieldKind::Gravity => {
+                let tip = field.center + field.direction.normalize_or_zero() * ARROW_LENGTH;
+                gizmos.arrow(field.center, tip, color);
+            }
+            ForceFieldKind::Vortex => {
+                // A tangent beside the center shows the sense of rotation
+                let axis = field.direction.normalize_or_zero();
+                let outward = axis.any_orthonormal_vector();
+                let start = field.center + outward * 20.0;
+                let tangent = axis.cross(outward) * field.strength.signum();
+                gizmos.arrow(start, start + tangent * ARROW_LENGTH, color);
+            }
+            ForceFieldKind::Radial | ForceFieldKind::Damping => {}
+        }
+    }
+}
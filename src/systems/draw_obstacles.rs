@@ -0,0 +1,117 @@
+//! Draw obstacles with gizmos
+//!
+//! Outlines every obstacle in the xy plane. In 3D mode the outline is
+//! drawn at the top and bottom of the map, joined at the corners, to show
+//! the walls obstacles form along z.
+//!
+//! Obstacles with a membrane show a per-species pattern: their outline is
+//! split into dashes in the colors of the species the membrane treats
+//! differently. Species that pass through leave a gap after their dash,
+//! so the more open a membrane is, the more broken its outline looks.
+
+use crate::resources::{Membrane, Membranes, ObstacleShape, Obstacles, ParticleConfig};
+use crate::resources::{SpeciesRegistry, WallInteraction};
+use bevy::color::palettes::css::LIGHT_GRAY;
+use bevy::prelude::*;
+
+/// Length of one dash of a membrane outline
+const DASH_LENGTH: f32 = 12.0;
+
+/// Number of edges used to outline a circle
+const CIRCLE_EDGES: u16 = 48;
+
+/// Draw obstacles with gizmos
+#[allow(clippy::needless_pass_by_value)]
+pub fn draw_obstacles(
+    mut gizmos: Gizmos,
+    obstacles: Res<Obstacles>,
+    membranes: Res<Membranes>,
+    registry: Res<SpeciesRegistry>,
+    config: Res<ParticleConfig>,
+) {
+    let half_depth = config.depth() / 2.0;
+    let layers: &[f32] = if config.dimension.is_3d() {
+        &[-half_depth, half_depth]
+    } else {
+        &[0.0]
+    };
+
+    for obstacle in obstacles.as_slice() {
+        let pattern = obstacle
+            .membrane
+            .as_deref()
+            .and_then(|name| membranes.get(name))
+            .map(|membrane| membrane_pattern(membrane, &registry))
+            .unwrap_or_default();
+        let edges = outline(&obstacle.shape);
+
+        for &z in layers {
+            if pattern.is_empty() {
+                for (a, b) in &edges {
+                    gizmos.line(a.extend(z), b.extend(z), LIGHT_GRAY);
+                }
+            } else {
+                let mut dash = 0;
+                for (a, b) in &edges {
+                    let length = a.distance(*b);
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let steps = (length / DASH_LENGTH).ceil().max(1.0) as usize;
+                    for step in 0..steps {
+                        #[allow(clippy::cast_precision_loss)]
+                        let (start, end) = (
+                            a.lerp(*b, step as f32 / steps as f32),
+                            a.lerp(*b, (step + 1) as f32 / steps as f32),
+                        );
+                        if let Some(color) = pattern[dash % pattern.len()] {
+                            gizmos.line(start.extend(z), end.extend(z), color);
+                        }
+                        dash += 1;
+                    }
+                }
+            }
+        }
+        if config.dimension.is_3d() {
+            for corner in obstacle.shape.corners() {
+                gizmos.line(
+                    corner.extend(-half_depth),
+                    corner.extend(half_depth),
+                    LIGHT_GRAY,
+                );
+            }
+        }
+    }
+}
+
+/// Returns the dash colors of a membrane outline
+///
+/// Each species with a non-default rule contributes a dash in its color,
+/// followed by a gap (`None`) if it passes through. Empty if the
+/// membrane treats every species like a plain obstacle.
+fn membrane_pattern(membrane: &Membrane, registry: &SpeciesRegistry) -> Vec<Option<Color>> {
+    let mut pattern = Vec::new();
+    for (particle_type, WallInteraction { solid, .. }) in membrane.custom() {
+        pattern.push(Some(registry.color(particle_type)));
+        if !solid {
+            pattern.push(None);
+        }
+    }
+    pattern
+}
+
+/// Returns the outline of a shape as straight edges
+///
+/// Circles are approximated by a regular polygon.
+fn outline(shape: &ObstacleShape) -> Vec<(Vec2, Vec2)> {
+    if let ObstacleShape::Circle { center, radius } = shape {
+        let points: Vec<Vec2> = (0..CIRCLE_EDGES)
+            .map(|index| {
+                let angle = std::f32::consts::TAU * f32::from(index) / f32::from(CIRCLE_EDGES);
+                *center + Vec2::from_angle(angle) * *radius
+            })
+            .collect();
+        let next = points.iter().cycle().skip(1);
+        points.iter().copied().zip(next.copied()).collect()
+    } else {
+        shape.edges()
+    }
+}
@@ -0,0 +1,17 @@
+//! Hide dormant particles
+//!
+//! Entities of dead particles are kept as [`Dormant`] for recycling.
+//! This observer hides them until they are revived.
+
+use crate::components::Dormant;
+use bevy::prelude::*;
+
+/// Hide dormant particles
+///
+/// Runs whenever the [`Dormant`] marker is added to an entity. Revived
+/// particles are made visible again by the `attach_particle_mesh`
+/// observer.
+#[allow(clippy::needless_pass_by_value)]
+pub fn hide_dormant_particle(add: On<Add, Dormant>, mut commands: Commands) {
+    commands.entity(add.entity).insert(Visibility::Hidden);
+}
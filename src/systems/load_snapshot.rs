@@ -0,0 +1,188 @@
+//! Capture and restore the simulation as a snapshot
+//!
+//! Shared by the `save_snapshot` and `load_snapshot` console commands,
+//! the startup snapshot named by the [`StartupConfig`] and headless runs.
+
+use crate::bundles::Particle;
+use crate::components::{
+    Dormant, Energy, Genome, OrbitCamera, ParticleMarker, ParticleType, Position, Velocity,
+};
+use crate::resources::{
+    ParticleConfig, ParticleInteractionTable, SimRng, SpeciesRegistry, StartupConfig,
+};
+use crate::simulation::Simulation;
+use crate::snapshot::{CameraState, OrbitState, ParticleState, PhysicsState, Snapshot};
+use crate::systems::clean_particle;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+/// Everything a snapshot captures
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+pub struct SnapshotSource<'w, 's> {
+    /// Living particles with their optional energy and genome
+    pub particles: Query<
+        'w,
+        's,
+        (
+            &'static ParticleType,
+            &'static Position,
+            &'static Velocity,
+            Option<&'static Energy>,
+            Option<&'static Genome>,
+        ),
+        With<ParticleMarker>,
+    >,
+    /// Particle configuration
+    pub config: Res<'w, ParticleConfig>,
+    /// Species registry
+    pub registry: Res<'w, SpeciesRegistry>,
+    /// Interaction table
+    pub interaction_table: Res<'w, ParticleInteractionTable>,
+    /// Seeded random number generator
+    pub rng: Res<'w, SimRng>,
+    /// Physics engine stepped by `update_particle`
+    pub simulation: Res<'w, Simulation>,
+    /// 2D camera transform
+    pub cameras_2d: Query<'w, 's, &'static Transform, (With<Camera2d>, Without<Camera3d>)>,
+    /// 3D orbit camera
+    pub orbit_cameras: Query<'w, 's, &'static OrbitCamera>,
+}
+
+impl SnapshotSource<'_, '_> {
+    /// Returns a snapshot of the live particles and state
+    ///
+    /// Captures every particle with its type, position, velocity and
+    /// (when present) energy and genome, together with the
+    /// configuration, species, interaction table, [`SimRng`] state,
+    /// [`PhysicsState`] and camera. Without a camera, as in headless
+    /// runs, none is saved.
+    #[must_use]
+    pub fn capture(&self) -> Snapshot {
+        let mut snapshot = Snapshot::new(
+            &self.config,
+            &self.registry,
+            &self.interaction_table,
+            &self.rng,
+        );
+        snapshot.physics = Some(PhysicsState::from(&*self.simulation));
+        snapshot.camera = self.cameras_2d.iter().next().map(|transform| CameraState {
+            orbit: self.orbit_cameras.iter().next().map(OrbitState::from),
+            ..CameraState::new(transform)
+        });
+        snapshot.particles = self
+            .particles
+            .iter()
+            .map(
+                |(ptype, position, velocity, energy, genome)| ParticleState {
+                    energy: energy.map(|energy| energy.value),
+                    genome: genome.cloned(),
+                    ..ParticleState::new(*ptype, position.value, velocity.value)
+                },
+            )
+            .collect();
+        snapshot
+    }
+}
+
+/// Everything a snapshot replaces
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+pub struct SnapshotTarget<'w, 's> {
+    /// Command queue for despawning and spawning particles
+    pub commands: Commands<'w, 's>,
+    /// Living and dormant particle entities to remove
+    pub particles: Query<'w, 's, Entity, Or<(With<ParticleMarker>, With<Dormant>)>>,
+    /// Particle configuration
+    pub config: ResMut<'w, ParticleConfig>,
+    /// Species registry
+    pub registry: ResMut<'w, SpeciesRegistry>,
+    /// Interaction table
+    pub interaction_table: ResMut<'w, ParticleInteractionTable>,
+    /// Seeded random number generator
+    pub rng: ResMut<'w, SimRng>,
+    /// Physics engine stepped by `update_particle`
+    pub simulation: ResMut<'w, Simulation>,
+    /// Physics clock, follows `config.tick_rate`
+    pub fixed_time: ResMut<'w, Time<Fixed>>,
+    /// 2D camera transform
+    pub cameras_2d: Query<'w, 's, &'static mut Transform, (With<Camera2d>, Without<Camera3d>)>,
+    /// 3D orbit camera
+    pub orbit_cameras:
+        Query<'w, 's, (&'static mut OrbitCamera, &'static mut Transform), Without<Camera2d>>,
+}
+
+impl SnapshotTarget<'_, '_> {
+    /// Replaces the live particles and state with a snapshot
+    ///
+    /// Restores the configuration, species, interaction table, [`SimRng`]
+    /// state and camera, then despawns all particles and spawns the ones
+    /// of the snapshot with their velocity, energy and genome.
+    /// The [`Simulation`] continues from the saved physics state; version 1
+    /// snapshots without one reseed it from the configuration instead.
+    /// Obstacles, fields, reactions and the ecosystem, evolution and
+    /// annealing settings are kept as they are.
+    pub fn apply(&mut self, snapshot: &Snapshot) {
+        *self.config = snapshot.config.clone();
+        self.fixed_time.set_timestep_hz(self.config.tick_rate);
+        *self.registry = snapshot.registry();
+        *self.interaction_table = snapshot.interaction_table();
+        *self.rng = snapshot.rng.clone();
+        match &snapshot.physics {
+            Some(physics) => physics.restore(&mut self.simulation),
+            None => self.simulation.reseed(self.config.seed),
+        }
+
+        clean_particle(self.commands.reborrow(), self.particles.reborrow());
+        for particle in &snapshot.particles {
+            let particle_type = particle.particle_type();
+            if self.registry.get(particle_type).is_none() {
+                continue;
+            }
+            let entity = Particle::spawn(&mut self.commands, particle.position(), particle_type);
+            let mut entity = self.commands.entity(entity);
+            entity.insert(Velocity::new(particle.velocity()));
+            if let Some(energy) = particle.energy {
+                entity.insert(Energy::new(energy));
+            }
+            if let Some(genome) = &particle.genome {
+                entity.insert(genome.clone());
+            }
+        }
+
+        if let Some(camera) = snapshot.camera {
+            for mut transform in &mut self.cameras_2d {
+                *transform = camera.transform();
+            }
+            if let Some(orbit) = camera.orbit {
+                for (mut orbit_camera, mut transform) in &mut self.orbit_cameras {
+                    *orbit_camera = orbit.to_orbit_camera();
+                    *transform = orbit_camera.transform();
+                }
+            }
+        }
+    }
+}
+
+/// Load the startup snapshot, if one is configured
+///
+/// Runs in `PostStartup`, after the cameras exist and the initial
+/// particles were spawned, and replaces them with the snapshot named by
+/// `startup.snapshot`. If the snapshot cannot be loaded the initial
+/// particles are kept.
+#[allow(clippy::needless_pass_by_value)]
+pub fn load_startup_snapshot(startup: Res<StartupConfig>, mut target: SnapshotTarget) {
+    let Some(path) = startup.snapshot.as_deref() else {
+        return;
+    };
+    match Snapshot::from_file(path) {
+        Ok(snapshot) => target.apply(&snapshot),
+        Err(e) => {
+            bevy::log::warn!(
+                "Could not load snapshot {}, keeping spawned particles",
+                path
+            );
+            bevy::log::error!("Error: {}", e);
+        }
+    }
+}
@@ -2,18 +2,38 @@
 //!
 //! This module contains all Bevy systems used in the game.
 
+mod anneal_temperature;
+mod attach_particle_mesh;
+mod draw_force_fields;
+mod draw_obstacles;
+mod hide_dormant_particle;
+mod load_snapshot;
 mod move_camera;
+mod orbit_camera;
+mod place_force_field;
 mod respawn_particle;
 pub mod setup;
+mod sync_camera_mode;
+mod sync_particle_species;
 mod sync_transform;
 mod toggle_particle_update;
 mod update_input_focus;
 mod update_particle;
 
-pub use move_camera::{move_camera, ParticleChunk};
+pub use anneal_temperature::anneal_temperature;
+pub use attach_particle_mesh::attach_particle_mesh;
+pub use draw_force_fields::draw_force_fields;
+pub use draw_obstacles::draw_obstacles;
+pub use hide_dormant_particle::hide_dormant_particle;
+pub use load_snapshot::{SnapshotSource, SnapshotTarget, load_startup_snapshot};
+pub use move_camera::move_camera;
+pub use orbit_camera::orbit_camera;
+pub use place_force_field::place_force_field;
 pub use respawn_particle::{clean_particle, respawn_particle, spawn_particle};
 pub use setup::setup;
+pub use sync_camera_mode::sync_camera_mode;
+pub use sync_particle_species::sync_particle_species;
 pub use sync_transform::sync_transform;
 pub use toggle_particle_update::toggle_particle_update;
 pub use update_input_focus::update_input_focus;
-pub use update_particle::update_particle;
+pub use update_particle::{SimulationCounters, update_particle};
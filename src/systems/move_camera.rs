@@ -5,18 +5,11 @@
 //! - **+/-**: Zoom in/out
 //!
 //! This system only responds to input when the game has focus
-//! (as opposed to the console focus).
+//! (as opposed to the console focus) and the simulation is in 2D mode.
 
-use crate::resources::{CameraMoveConfig, InputFocus};
+use crate::resources::{CameraMoveConfig, InputFocus, ParticleConfig};
 use bevy::prelude::*;
 
-/// Type alias for particle chunk data in spatial partitioning
-pub type ParticleChunk = Vec<(
-    Entity,
-    crate::components::ParticleType,
-    crate::components::Position,
-)>;
-
 /// Camera movement and zoom control system
 ///
 /// Controls:
@@ -24,7 +17,8 @@ pub type ParticleChunk = Vec<(
 /// - **+/-**: Zoom in/out
 ///
 /// This system only responds to input when the game has focus
-/// (as opposed to the console focus).
+/// (as opposed to the console focus) and the simulation is in 2D mode.
+/// The 3D mode is handled by `orbit_camera`.
 ///
 /// # System Parameters
 /// - `Query<(&mut Transform, &Camera), With<Camera2d>>`: Camera transform and projection
@@ -32,6 +26,7 @@ pub type ParticleChunk = Vec<(
 /// - `Res<Time>`: Time delta for frame-independent movement
 /// - `Res<InputFocus>`: Current focus state (game vs console)
 /// - `Res<CameraMoveConfig>`: Movement configuration
+/// - `Res<ParticleConfig>`: Simulation configuration (for the dimension)
 #[allow(clippy::needless_pass_by_value)]
 pub fn move_camera(
     mut camera: Query<(&mut Transform, &Camera), With<Camera2d>>,
@@ -39,8 +34,9 @@ pub fn move_camera(
     time: Res<Time>,
     input_focus: Res<InputFocus>,
     config: Res<CameraMoveConfig>,
+    particle_config: Res<ParticleConfig>,
 ) {
-    if !input_focus.is_game() {
+    if !input_focus.is_game() || particle_config.dimension.is_3d() {
         return;
     }
 
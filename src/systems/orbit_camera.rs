@@ -0,0 +1,68 @@
+//! Orbit camera control system for the 3D mode
+//!
+//! Controls:
+//! - **A/D**: Orbit left/right around the focus point
+//! - **W/S**: Orbit up/down
+//! - **+/-**: Zoom in/out
+//!
+//! This system only responds to input when the game has focus
+//! (as opposed to the console focus).
+
+use crate::components::OrbitCamera;
+use crate::resources::{CameraMoveConfig, InputFocus};
+use bevy::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+
+/// Largest pitch of the orbit camera, just short of the poles
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+
+/// Orbit camera control system for the 3D mode
+///
+/// Controls:
+/// - **A/D**: Orbit left/right around the focus point
+/// - **W/S**: Orbit up/down
+/// - **+/-**: Zoom in/out
+///
+/// This system only responds to input when the game has focus
+/// (as opposed to the console focus).
+#[allow(clippy::needless_pass_by_value)]
+pub fn orbit_camera(
+    mut camera: Query<(&mut Transform, &mut OrbitCamera)>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    input_focus: Res<InputFocus>,
+    config: Res<CameraMoveConfig>,
+) {
+    if !input_focus.is_game() {
+        return;
+    }
+
+    let Ok((mut transform, mut orbit)) = camera.single_mut() else {
+        return;
+    };
+
+    let angle = config.orbit_speed * time.delta_secs();
+    if keys.pressed(KeyCode::KeyA) {
+        orbit.yaw -= angle;
+    }
+    if keys.pressed(KeyCode::KeyD) {
+        orbit.yaw += angle;
+    }
+    if keys.pressed(KeyCode::KeyW) {
+        orbit.pitch += angle;
+    }
+    if keys.pressed(KeyCode::KeyS) {
+        orbit.pitch -= angle;
+    }
+    orbit.pitch = orbit.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+
+    if keys.pressed(KeyCode::Minus) || keys.pressed(KeyCode::NumpadAdd) {
+        orbit.radius *= config.zoom_speed.mul_add(time.delta_secs(), 1.0);
+    }
+    if keys.pressed(KeyCode::Equal) || keys.pressed(KeyCode::NumpadSubtract) {
+        orbit.radius *= config.zoom_speed.mul_add(-time.delta_secs(), 1.0);
+    }
+    orbit.radius = orbit.radius.max(1.0);
+
+    *transform = orbit.transform();
+}
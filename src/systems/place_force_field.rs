@@ -0,0 +1,85 @@
+//! Place and remove force fields with the mouse
+//!
+//! Controls:
+//! - **Left click**: Spawn the [`FieldBrush`] field at the cursor
+//! - **Right click**: Remove the force field nearest to the cursor
+//!
+//! This system only responds to input when the game has focus.
+
+use crate::components::ForceField;
+use crate::resources::{FieldBrush, InputFocus, ParticleConfig};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Distance from a field center within which a right click removes it
+const PICK_DISTANCE: f32 = 50.0;
+
+/// Place and remove force fields with the mouse
+///
+/// The cursor is projected onto the `z = 0` plane through the camera of
+/// the current dimension. A right click removes the nearest field if the
+/// cursor is inside its range or within `PICK_DISTANCE` of its center.
+///
+/// # System Parameters
+/// - `Res<ButtonInput<MouseButton>>`: Mouse input
+/// - `Res<InputFocus>`: Current focus state (game vs console)
+/// - `Res<FieldBrush>`: Field to place on left click
+/// - `Res<ParticleConfig>`: Simulation configuration (for the dimension)
+/// - `Single<&Window, With<PrimaryWindow>>`: Window holding the cursor
+/// - `Query<(&Camera, &GlobalTransform, Has<Camera3d>)>`: Cameras to project through
+/// - `Query<(Entity, &ForceField)>`: Existing fields
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub fn place_force_field(
+    mut commands: Commands,
+    buttons: Res<ButtonInput<MouseButton>>,
+    input_focus: Res<InputFocus>,
+    brush: Res<FieldBrush>,
+    config: Res<ParticleConfig>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform, Has<Camera3d>)>,
+    fields: Query<(Entity, &ForceField)>,
+) {
+    let place = buttons.just_pressed(MouseButton::Left) && brush.field.is_some();
+    let remove = buttons.just_pressed(MouseButton::Right);
+    if !input_focus.is_game() || !(place || remove) {
+        return;
+    }
+
+    let is_3d = config.dimension.is_3d();
+    let Some(cursor) = cameras
+        .iter()
+        .find(|(camera, _, has_3d)| camera.is_active && *has_3d == is_3d)
+        .and_then(|(camera, transform, _)| cursor_on_plane(&window, camera, transform))
+    else {
+        return;
+    };
+
+    if let Some(template) = brush.field.filter(|_| place) {
+        let field = ForceField {
+            center: cursor,
+            ..template
+        };
+        commands.spawn(field);
+        bevy::log::info!("Placed {} field at {:.0}", field.kind, cursor);
+    }
+
+    if remove {
+        let nearest = fields
+            .iter()
+            .map(|(entity, field)| (entity, field, field.center.distance(cursor)))
+            .filter(|(_, field, distance)| *distance < field.radius.max(PICK_DISTANCE))
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        if let Some((entity, field, _)) = nearest {
+            commands.entity(entity).despawn();
+            bevy::log::info!("Removed {} field at {:.0}", field.kind, field.center);
+        }
+    }
+}
+
+/// Returns the point on the `z = 0` plane under the cursor
+fn cursor_on_plane(window: &Window, camera: &Camera, transform: &GlobalTransform) -> Option<Vec3> {
+    let cursor = window.cursor_position()?;
+    let ray = camera.viewport_to_world(transform, cursor).ok()?;
+    let distance = ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Z))?;
+    Some(ray.get_point(distance))
+}
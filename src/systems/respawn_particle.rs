@@ -4,11 +4,10 @@
 //! according to current configuration.
 
 use crate::bundles::Particle;
-use crate::components::ParticleMarker;
-use crate::components::ParticleType;
-use crate::resources::{InputFocus, ParticleConfig};
+use crate::components::{Dormant, ParticleMarker};
+use crate::resources::{InputFocus, ParticleConfig, SimRng, SpeciesRegistry};
+use crate::simulation::initial_particles;
 use bevy::prelude::*;
-use bevy::sprite_render::ColorMaterial;
 
 /// Respawn particles when requested
 ///
@@ -17,26 +16,31 @@ use bevy::sprite_render::ColorMaterial;
 ///
 /// This is triggered by the `respawn_particle` console command
 /// or the R key when the game has focus.
-#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
 pub fn respawn_particle(
     mut commands: Commands,
-    query: Query<Entity, With<ParticleMarker>>,
-    meshes: ResMut<Assets<Mesh>>,
-    material: ResMut<Assets<ColorMaterial>>,
+    query: Query<Entity, Or<(With<ParticleMarker>, With<Dormant>)>>,
     config: Res<ParticleConfig>,
+    registry: Res<SpeciesRegistry>,
+    rng: ResMut<SimRng>,
     keys: Res<ButtonInput<KeyCode>>,
     input_focus: Res<InputFocus>,
 ) {
     if input_focus.is_game() && keys.just_pressed(KeyCode::KeyR) {
         clean_particle(commands.reborrow(), query);
-        spawn_particle(commands, meshes, material, config);
+        spawn_particle(commands, config, registry, rng);
     }
 }
 
 /// Remove all particles from the simulation
 ///
-/// Despawns all entities with the [`ParticleMarker`] component.
-pub fn clean_particle(mut commands: Commands, query: Query<Entity, With<ParticleMarker>>) {
+/// Despawns all entities with the [`ParticleMarker`] component together
+/// with the [`Dormant`] entities kept for recycling.
+#[allow(clippy::type_complexity)]
+pub fn clean_particle(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<ParticleMarker>, With<Dormant>)>>,
+) {
     for entity in query.iter() {
         commands.entity(entity).despawn();
     }
@@ -46,34 +50,30 @@ pub fn clean_particle(mut commands: Commands, query: Query<Entity, With<Particle
 /// Spawn initial particles according to configuration
 ///
 /// Creates the specified number of particles with random positions
-/// and types within the map boundaries.
+/// within the map boundaries (a box in 3D mode) and random types from
+/// the species registry.
+///
+/// The generator is reseeded from `config.seed` first, so the same seed
+/// and configuration always produce the same initial layout (see
+/// [`initial_particles`]). Meshes and materials are attached by the
+/// `attach_particle_mesh` observer.
 ///
 /// # Arguments
 /// - `commands`: Bevy command queue
-/// - `meshes`: Mesh assets resource
-/// - `material`: Material assets resource
 /// - `config`: Particle configuration with spawn parameters
+/// - `registry`: Species to choose particle types from
+/// - `rng`: Seeded random number generator
 #[allow(clippy::needless_pass_by_value)]
 pub fn spawn_particle(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut material: ResMut<Assets<ColorMaterial>>,
     config: Res<ParticleConfig>,
+    registry: Res<SpeciesRegistry>,
+    mut rng: ResMut<SimRng>,
 ) {
-    let particle_types = ParticleType::all_types();
-
-    for _ in 0..config.init_particle_num {
-        let x = rand::random_range(-config.map_width / 2.0..config.map_width / 2.0);
-        let y = rand::random_range(-config.map_height / 2.0..config.map_height / 2.0);
-
-        let particle_type = particle_types[rand::random_range(0..particle_types.len())];
-
-        Particle::spawn(
-            &mut commands,
-            &mut meshes,
-            &mut material,
-            Transform::from_xyz(x, y, 0.0),
-            particle_type,
-        );
+    if registry.is_empty() {
+        bevy::log::warn!("No species registered, no particles spawned");
+    }
+    for (particle_type, position) in initial_particles(&config, registry.len(), &mut rng) {
+        Particle::spawn(&mut commands, position, particle_type);
     }
 }
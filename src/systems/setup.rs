@@ -1,34 +1,48 @@
 //! Setup function that runs once at startup
 //!
 //! This system:
-//! 1. Loads particle interactions from CSV file (if present)
-//! 2. Spawns initial particles according to configuration
+//! 1. Loads particle interactions and species from the matrix CSV file (if present)
+//! 2. Loads reaction rules from the reactions CSV file (if present)
+//! 3. Spawns initial particles according to configuration
 
-use crate::resources::{ParticleConfig, ParticleInteractionTable};
+use crate::resources::{
+    ParticleConfig, ParticleInteractionTable, ReactionTable, SimRng, SpeciesRegistry, StartupConfig,
+};
 use crate::systems::spawn_particle;
 use bevy::prelude::*;
-use bevy::sprite_render::ColorMaterial;
 
 /// Setup function that runs once at startup
 ///
-/// 1. Loads particle interactions from CSV file (if present)
-/// 2. Spawns initial particles according to configuration
+/// 1. Loads particle interactions and species from the matrix CSV file
+///    named by the [`StartupConfig`] (if present)
+/// 2. Loads reaction rules from the reactions CSV file named by the
+///    [`StartupConfig`] (if present)
+/// 3. Spawns initial particles according to configuration
+///
+/// The species in the CSV header replace the registered species. Without
+/// a CSV file the registered species are kept and the interaction table
+/// is sized to match them.
 ///
 /// # Arguments
 /// - `commands`: Bevy command queue
-/// - `meshes`: Mesh assets resource
-/// - `material`: Material assets resource
 /// - `interaction_table`: Interaction table resource to populate
 /// - `config`: Particle configuration with spawn parameters
+/// - `registry`: Species registry, rebuilt from the CSV header
+/// - `startup`: Startup settings naming the matrix and reactions files
+/// - `reactions`: Reaction table to populate
+/// - `rng`: Seeded random number generator
+#[allow(clippy::needless_pass_by_value)]
 pub fn setup(
     commands: Commands,
-    meshes: ResMut<Assets<Mesh>>,
-    material: ResMut<Assets<ColorMaterial>>,
     mut interaction_table: ResMut<ParticleInteractionTable>,
     config: Res<ParticleConfig>,
+    mut registry: ResMut<SpeciesRegistry>,
+    startup: Res<StartupConfig>,
+    mut reactions: ResMut<ReactionTable>,
+    rng: ResMut<SimRng>,
 ) {
-    let csv_path = "particle_interactions.csv";
-    match ParticleInteractionTable::from_csv_file(csv_path) {
+    let csv_path = startup.matrix.as_str();
+    match ParticleInteractionTable::from_csv_file(csv_path, &mut registry) {
         Ok(loaded_table) => {
             *interaction_table = loaded_table;
             bevy::log::info!(
@@ -39,8 +53,17 @@ pub fn setup(
         Err(e) => {
             bevy::log::warn!("Could not load {}, using default interactions", csv_path);
             bevy::log::error!("Error: {}", e);
+            interaction_table.resize(registry.len());
+        }
+    }
+
+    let reactions_path = startup.reactions.as_str();
+    if std::path::Path::new(reactions_path).exists() {
+        match ReactionTable::from_csv_file(reactions_path, &registry) {
+            Ok(loaded) => *reactions = loaded,
+            Err(e) => bevy::log::error!("Could not load {}: {}", reactions_path, e),
         }
     }
 
-    spawn_particle(commands, meshes, material, config);
+    spawn_particle(commands, config, registry.into(), rng);
 }
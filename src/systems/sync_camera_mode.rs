@@ -0,0 +1,55 @@
+//! Switch cameras between the 2D and 3D modes
+//!
+//! The 2D camera always exists, because the console and UI render
+//! through it. In 3D mode an orbit camera is added below it and the 2D
+//! camera stops clearing the screen, so the 3D scene shows through.
+
+use crate::components::OrbitCamera;
+use crate::resources::ParticleConfig;
+use bevy::prelude::*;
+
+/// Switch cameras between the 2D and 3D modes
+///
+/// Spawns the 3D orbit camera the first time the 3D mode is selected
+/// and activates it only while `config.dimension` is 3D. The 2D camera
+/// keeps rendering on top without clearing the screen in 3D mode.
+///
+/// Runs whenever the [`ParticleConfig`] changes.
+#[allow(clippy::needless_pass_by_value)]
+pub fn sync_camera_mode(
+    mut commands: Commands,
+    config: Res<ParticleConfig>,
+    mut cameras_2d: Query<&mut Camera, (With<Camera2d>, Without<Camera3d>)>,
+    mut cameras_3d: Query<&mut Camera, (With<Camera3d>, Without<Camera2d>)>,
+) {
+    let is_3d = config.dimension.is_3d();
+
+    if is_3d && cameras_3d.is_empty() {
+        let orbit = OrbitCamera::new(
+            config
+                .map_width
+                .max(config.map_height)
+                .max(config.map_depth)
+                * 1.5,
+        );
+        commands.spawn((
+            Camera3d::default(),
+            Camera {
+                order: -1,
+                ..default()
+            },
+            orbit.transform(),
+            orbit,
+        ));
+    }
+    for mut camera in &mut cameras_3d {
+        camera.is_active = is_3d;
+    }
+    for mut camera in &mut cameras_2d {
+        camera.clear_color = if is_3d {
+            ClearColorConfig::None
+        } else {
+            ClearColorConfig::Default
+        };
+    }
+}
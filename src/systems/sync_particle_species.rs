@@ -0,0 +1,62 @@
+//! Sync particle appearance to its species
+//!
+//! Reactions change the `ParticleType` of existing particles. This
+//! system gives converted particles the color and size of their new
+//! species.
+
+use crate::bundles::ParticleAssets;
+use crate::components::{ParticleMarker, ParticleType};
+use crate::resources::SpeciesRegistry;
+use bevy::prelude::*;
+use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
+
+/// Sync particle appearance to its species
+///
+/// Only particles whose `ParticleType` changed after they were spawned
+/// are updated: they switch to the shared mesh and material of their new
+/// species from the [`ParticleMeshCache`], so no assets are added.
+/// Particles that already use those handles, such as revived ones, are
+/// left untouched.
+///
+/// [`ParticleMeshCache`]: crate::resources::ParticleMeshCache
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+pub fn sync_particle_species(
+    mut query: Query<
+        (
+            Ref<ParticleType>,
+            Option<(&mut Mesh2d, &mut MeshMaterial2d<ColorMaterial>)>,
+            Option<(&mut Mesh3d, &mut MeshMaterial3d<StandardMaterial>)>,
+        ),
+        (With<ParticleMarker>, Changed<ParticleType>),
+    >,
+    registry: Res<SpeciesRegistry>,
+    mut assets: ParticleAssets,
+) {
+    for (ptype, handles_2d, handles_3d) in &mut query {
+        if ptype.is_added() {
+            continue;
+        }
+        let Some(species) = registry.get(*ptype) else {
+            continue;
+        };
+
+        if let Some((mut mesh, mut material)) = handles_2d {
+            let (new_mesh, new_material) = assets.handles_2d(species);
+            if mesh.0 != new_mesh.0 {
+                *mesh = new_mesh;
+            }
+            if material.0 != new_material.0 {
+                *material = new_material;
+            }
+        }
+        if let Some((mut mesh, mut material)) = handles_3d {
+            let (new_mesh, new_material) = assets.handles_3d(species);
+            if mesh.0 != new_mesh.0 {
+                *mesh = new_mesh;
+            }
+            if material.0 != new_material.0 {
+                *material = new_material;
+            }
+        }
+    }
+}
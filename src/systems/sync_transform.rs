@@ -4,7 +4,8 @@
 //! rendering `Transform` component. This allows the physics system
 //! to update positions independently from the rendering system.
 
-use crate::components::{ParticleMarker, Position};
+use crate::components::{ParticleMarker, Position, PreviousPosition};
+use crate::resources::{ParticleConfig, ParticleUpdateToggle};
 use bevy::prelude::*;
 
 /// Sync particle positions to transform for rendering
@@ -13,10 +14,29 @@ use bevy::prelude::*;
 /// rendering `Transform` component. This allows the physics system
 /// to update positions independently from the rendering system.
 ///
+/// Physics runs in `FixedUpdate`, so the rendered position is
+/// interpolated between `PreviousPosition` and `Position` by the
+/// fraction of the next fixed tick that has already elapsed. The
+/// interpolation follows the boundary modes, so particles crossing a
+/// periodic seam do not streak across the map.
+///
 /// This system runs every frame to ensure particles are rendered
 /// at their current physics positions.
-pub fn sync_transform(mut query: Query<(&Position, &mut Transform), With<ParticleMarker>>) {
-    for (position, mut transform) in &mut query {
-        transform.translation = position.value;
+#[allow(clippy::needless_pass_by_value)]
+pub fn sync_transform(
+    mut query: Query<(&Position, &PreviousPosition, &mut Transform), With<ParticleMarker>>,
+    fixed_time: Res<Time<Fixed>>,
+    config: Res<ParticleConfig>,
+    toggle: Res<ParticleUpdateToggle>,
+) {
+    let alpha = if toggle.is_enabled() {
+        fixed_time.overstep_fraction()
+    } else {
+        1.0
+    };
+
+    for (position, previous, mut transform) in &mut query {
+        let offset = config.displacement(position.value, previous.value);
+        transform.translation = offset.mul_add(Vec3::splat(1.0 - alpha), position.value);
     }
 }
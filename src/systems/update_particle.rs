@@ -1,119 +1,232 @@
 //! Update particle physics positions
 //!
-//! This system updates only the `Position` and `Velocity` components.
-//! It performs:
+//! This system runs in `FixedUpdate` and updates only the `Position`,
+//! `PreviousPosition`, `Velocity` and (through reactions) `ParticleType`
+//! components.
+//! It is a thin adapter over [`Simulation`], which performs:
 //!
 //! 1. Spatial partitioning for efficient neighbor queries
-//! 2. Calculation of interaction forces between particles
+//! 2. Calculation of interaction forces between particles and of the
+//!    external force fields (in parallel)
 //! 3. Collision detection and resolution
-//! 4. Velocity integration and boundary checks
+//! 4. Velocity integration and boundary handling
+//! 5. Swept collisions with static obstacles
+//! 6. Repair of particles that became non-finite
+//! 7. Species conversion by the reaction rules
+//! 8. Feeding, deaths and births in ecosystem mode
+//! 9. Genome inheritance and periodic respawns in evolution mode
 //!
-//! The `sync_transform` system will copy updated positions to the
-//! `Transform` component for rendering.
+//! In ecosystem mode the system also recycles entities: particles that
+//! died become [`Dormant`] and newborn particles reuse dormant entities
+//! before new ones are spawned.
+//!
+//! The `sync_transform` system will interpolate updated positions into
+//! the `Transform` component for rendering.
 
-use crate::components::{ParticleMarker, ParticleType, Position, Velocity};
+use crate::bundles::Particle;
+use crate::components::{
+    Dormant, Energy, ForceField, Genome, ParticleMarker, ParticleType, Position, PreviousPosition,
+    Velocity,
+};
+use crate::resources::Ecosystem;
+use crate::resources::EcosystemStats;
+use crate::resources::Evolution;
+use crate::resources::GenomeStats;
+use crate::resources::Membranes;
+use crate::resources::Obstacles;
 use crate::resources::ParticleConfig;
 use crate::resources::ParticleInteractionTable;
-use crate::systems::ParticleChunk;
+use crate::resources::ReactionStats;
+use crate::resources::ReactionTable;
+use crate::resources::SimulationHealth;
+use crate::resources::SpeciesRegistry;
+use crate::simulation::Simulation;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use std::collections::HashMap;
+
+/// Counters written back after every update
+#[derive(SystemParam)]
+pub struct SimulationCounters<'w> {
+    /// Numerical health counters
+    pub health: ResMut<'w, SimulationHealth>,
+    /// Reaction firing counts
+    pub reactions: ResMut<'w, ReactionStats>,
+    /// Per-species ecosystem metrics
+    pub ecosystem: ResMut<'w, EcosystemStats>,
+    /// Genome diversity metrics
+    pub genomes: ResMut<'w, GenomeStats>,
+}
 
 /// Update particle physics positions
 ///
-/// This system updates only the `Position` and `Velocity` components.
-/// It performs:
+/// This system updates only the `Position`, `PreviousPosition`,
+/// `Velocity`, `ParticleType`, [`Energy`] and [`Genome`] components. It
+/// copies the particle state, the species properties and all
+/// [`ForceField`] entities into the [`Simulation`] resource, hands it the
+/// [`Obstacles`], [`Membranes`], [`ReactionTable`], [`Ecosystem`] and
+/// [`Evolution`] settings whenever they change, advances it by
+/// `config.substeps` substeps (each `config.substep_dt()` long) and
+/// writes the result back together with the [`SimulationCounters`].
+///
+/// The simulation keeps its generator and step count between ticks and
+/// is only reseeded when `config.seed` changes; loading a snapshot
+/// restores both.
 ///
-/// 1. Spatial partitioning for efficient neighbor queries
-/// 2. Calculation of interaction forces between particles
-/// 3. Collision detection and resolution
-/// 4. Velocity integration and boundary checks
+/// Particle types are only written when a reaction changed them, so
+/// `Changed<ParticleType>` picks out the converted particles. Energies
+/// are only read and written in ecosystem mode; particles without an
+/// [`Energy`] component get one with the ecosystem's initial energy.
+/// Genomes are only read and written in evolution mode; particles without
+/// one become founders of a new lineage.
 ///
-/// The `sync_transform` system will copy updated positions to the
-/// `Transform` component for rendering.
-#[allow(clippy::needless_pass_by_value)]
+/// Entities of particles that died lose their [`ParticleMarker`] and become
+/// [`Dormant`]. Newborn particles revive dormant entities with
+/// [`Particle::revive`] and only spawn new ones when none are left.
+///
+/// `PreviousPosition` is only written for particles that have one, which
+/// the `ParticleRenderPlugin` adds; the `sync_transform` system will then
+/// interpolate updated positions into the `Transform` component for
+/// rendering.
+#[allow(
+    clippy::needless_pass_by_value,
+    clippy::too_many_arguments,
+    clippy::too_many_lines,
+    clippy::type_complexity
+)]
 pub fn update_particle(
-    query: Query<(Entity, &ParticleType, &mut Velocity, &mut Position), With<ParticleMarker>>,
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &mut ParticleType,
+            &mut Velocity,
+            &mut Position,
+            Option<&mut PreviousPosition>,
+            Option<&mut Energy>,
+            Option<&mut Genome>,
+        ),
+        With<ParticleMarker>,
+    >,
+    dormant: Query<Entity, With<Dormant>>,
     interaction_table: Res<ParticleInteractionTable>,
+    registry: Res<SpeciesRegistry>,
+    fields: Query<&ForceField>,
+    obstacles: Res<Obstacles>,
+    membranes: Res<Membranes>,
+    reactions: Res<ReactionTable>,
+    ecosystem: Res<Ecosystem>,
+    evolution: Res<Evolution>,
     config: Res<ParticleConfig>,
+    mut counters: SimulationCounters,
+    mut simulation: ResMut<Simulation>,
 ) {
-    let mut chunk: HashMap<(i32, i32), ParticleChunk> = HashMap::with_capacity(1000);
-    for (entity, ptype, _, pos) in query.iter() {
-        #[allow(clippy::cast_possible_truncation)]
-        let x = (pos.value.x / config.r).floor() as i32;
-        #[allow(clippy::cast_possible_truncation)]
-        let y = (pos.value.y / config.r).floor() as i32;
-        chunk
-            .entry((x, y))
-            .and_modify(|inner| inner.push((entity, ptype.to_owned(), pos.to_owned())))
-            .or_insert_with(|| [(entity, ptype.to_owned(), pos.to_owned())].into());
+    if simulation.seed() != config.seed {
+        simulation.reseed(config.seed);
+    }
+    simulation.config.clone_from(&config);
+    simulation.config.dt = config.substep_dt();
+    simulation.interaction_table.clone_from(&interaction_table);
+    simulation.species.clear();
+    simulation
+        .species
+        .extend(registry.types().map(|ptype| registry.properties(ptype)));
+    simulation.fields.clear();
+    simulation.fields.extend(fields.iter().copied());
+    if obstacles.is_changed() || membranes.is_changed() {
+        simulation.set_obstacles(obstacles.as_slice(), &membranes);
+    }
+    if reactions.is_changed() {
+        simulation.set_reactions(&reactions);
+    }
+    if ecosystem.is_changed() {
+        simulation.set_ecosystem(&ecosystem);
+    }
+    simulation.set_evolution(*evolution);
+    simulation.clear();
+    let mut entities = Vec::with_capacity(query.iter().len());
+    for (entity, ptype, velocity, position, _, energy, genome) in &query {
+        let index = simulation.add_particle(*ptype, position.value, velocity.value);
+        if let Some(energy) = energy.filter(|_| ecosystem.enabled) {
+            simulation.energies_mut()[index] = energy.value;
+        }
+        if let Some(genome) = genome.filter(|_| evolution.enabled) {
+            simulation.genomes_mut()[index].clone_from(genome);
+        }
+        entities.push(entity);
     }
 
-    for (entity, ptype, mut velocity, mut position) in query {
-        let my_type = *ptype;
-        let my_index = entity.index();
-
-        #[allow(clippy::cast_possible_truncation)]
-        let chunk_x = (position.value.x / config.r).floor() as i32;
-        #[allow(clippy::cast_possible_truncation)]
-        let chunk_y = (position.value.y / config.r).floor() as i32;
+    for _ in 0..config.substeps.max(1) {
+        simulation.step();
+    }
 
-        let mut components: ParticleChunk = Vec::with_capacity(1000);
-        for x in chunk_x - 1..=chunk_x + 1 {
-            for y in chunk_y - 1..=chunk_y + 1 {
-                chunk
-                    .entry((x, y))
-                    .and_modify(|inner| components.append(inner.to_owned().as_mut()));
+    let mut survived = vec![false; entities.len()];
+    let mut dormant = dormant.iter();
+    for (index, origin) in simulation.origins().iter().enumerate() {
+        let (new_type, new_position, new_velocity, new_energy) = (
+            simulation.types()[index],
+            simulation.positions()[index],
+            simulation.velocities()[index],
+            simulation.energies()[index],
+        );
+        let Some(origin) = *origin else {
+            if registry.get(new_type).is_none() {
+                continue;
             }
+            let entity = if let Some(entity) = dormant.next() {
+                Particle::revive(&mut commands, entity, new_position, new_type);
+                entity
+            } else {
+                Particle::spawn(&mut commands, new_position, new_type)
+            };
+            let mut entity = commands.entity(entity);
+            entity.insert(Velocity::new(new_velocity));
+            if ecosystem.enabled {
+                entity.insert(Energy::new(new_energy));
+            }
+            if evolution.enabled {
+                entity.insert(simulation.genomes()[index].clone());
+            }
+            continue;
+        };
+        survived[origin] = true;
+        let Ok((entity, mut ptype, mut velocity, mut position, previous, energy, genome)) =
+            query.get_mut(entities[origin])
+        else {
+            continue;
+        };
+        ptype.set_if_neq(new_type);
+        if let Some(mut previous) = previous {
+            previous.value = position.value;
         }
-
-        let acceleration = components
-            .iter()
-            .filter(|(other_entity, _, _)| other_entity.index() != my_index)
-            .fold(Vec3::default(), |acc, (_, p, pos)| {
-                let b = 0.35;
-                let d1 = config.r * b;
-                let d2 = config.r * (1.0 - b) / 2.0;
-                let d3 = config.r;
-                let distance = position.value.distance(pos.value);
-                let direction = (pos.value - position.value) / distance;
-                let distance_factor;
-
-                if distance < d1 {
-                    distance_factor = (distance - d1) / d1;
-                    let actual_acceleration = direction * distance_factor * config.repel_force;
-                    return acc + actual_acceleration;
-                } else if distance >= d3 {
-                    return acc;
-                } else if distance >= d2 {
-                    distance_factor = (d3 - distance) / (d3 - d2);
-                } else {
-                    distance_factor = (distance - d1) / (d2 - d1);
-                }
-
-                let other_type = *p;
-                let strength = interaction_table.get_interaction(my_type, other_type);
-                let actual_acceleration = direction * strength * distance_factor;
-
-                acc + actual_acceleration
-            });
-
-        velocity.value *= 0.5f32.powf(config.dt / config.dt_half);
-        velocity.value += acceleration * config.dt;
-
-        let half_width = config.map_width / 2.0;
-        let half_height = config.map_height / 2.0;
-
-        if (position.value.x > half_width && velocity.value.x > 0.0)
-            || (position.value.x < -half_width && velocity.value.x < 0.0)
-        {
-            velocity.value.x = -velocity.value.x;
+        position.value = new_position;
+        velocity.value = new_velocity;
+        if ecosystem.enabled {
+            if let Some(mut energy) = energy {
+                energy.value = new_energy;
+            } else {
+                commands.entity(entity).insert(Energy::new(new_energy));
+            }
         }
-        if (position.value.y > half_height && velocity.value.y > 0.0)
-            || (position.value.y < -half_height && velocity.value.y < 0.0)
-        {
-            velocity.value.y = -velocity.value.y;
+        if evolution.enabled {
+            let new_genome = &simulation.genomes()[index];
+            if let Some(mut genome) = genome {
+                genome.set_if_neq(new_genome.clone());
+            } else {
+                commands.entity(entity).insert(new_genome.clone());
+            }
         }
+    }
+    for (entity, _) in entities.iter().zip(&survived).filter(|(_, alive)| !**alive) {
+        commands
+            .entity(*entity)
+            .remove::<ParticleMarker>()
+            .insert(Dormant);
+    }
 
-        position.value += velocity.value * config.dt;
+    *counters.health = simulation.health();
+    counters.reactions.clone_from(simulation.reaction_stats());
+    counters.ecosystem.clone_from(simulation.ecosystem_stats());
+    if evolution.enabled {
+        *counters.genomes = simulation.genome_stats();
     }
 }
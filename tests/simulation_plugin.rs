@@ -0,0 +1,51 @@
+//! Runs the simulation without a window or renderer
+//!
+//! The `SimulationPlugin` only needs `MinimalPlugins`: particles are
+//! spawned without meshes or materials and `FixedUpdate` moves them.
+
+use bevy::prelude::*;
+use particle_life::SimulationPlugin;
+use particle_life::components::{ParticleMarker, Position};
+use particle_life::resources::ParticleConfig;
+use std::collections::HashMap;
+
+/// Returns the position of every living particle
+fn positions(app: &mut App) -> HashMap<Entity, Vec3> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<(Entity, &Position), With<ParticleMarker>>()
+        .iter(world)
+        .map(|(entity, position)| (entity, position.value))
+        .collect()
+}
+
+#[test]
+fn fixed_update_moves_particles_under_minimal_plugins() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        SimulationPlugin {
+            config: ParticleConfig {
+                init_particle_num: 200,
+                temperature: 1.0,
+                ..default()
+            },
+            ..default()
+        },
+    ));
+    app.update();
+    let before = positions(&mut app);
+    assert_eq!(before.len(), 200);
+
+    for _ in 0..5 {
+        app.world_mut().run_schedule(FixedUpdate);
+    }
+    let after = positions(&mut app);
+
+    assert_eq!(after.len(), before.len());
+    assert!(
+        before
+            .iter()
+            .all(|(entity, position)| after[entity] != *position)
+    );
+}